@@ -70,6 +70,10 @@ impl AsyncHandle {
 }
 
 extern "C" fn async_cb(ptr: *mut uv_async_t) {
+    if crate::shutdown::is_shut_down() {
+        return;
+    }
+
     let handle: Handle<_, Callback> = unsafe { Handle::from_raw(ptr) };
 
     let callback = unsafe { handle.get_data() };
@@ -1,3 +1,6 @@
+use std::ffi::CStr;
+
+use libuv_sys2 as ffi;
 use thiserror::Error as ThisError;
 
 pub(crate) type Result<T> = std::result::Result<T, Error>;
@@ -13,9 +16,25 @@ pub enum Error {
     #[error("Couldn't allocate memory for a new handle")]
     HandleMemAlloc,
 
+    #[error("The stream has already been closed")]
+    StreamClosed,
+
+    #[error("Couldn't restart timer handle")]
+    TimerAgain,
+
     #[error("Couldn't start timer handle")]
     TimerStart,
 
     #[error("Couldn't stop timer handle")]
     TimerStop,
+
+    /// An error returned by libuv, wrapping one of the negative
+    /// `uv_errno_t` codes.
+    #[error("{}", strerror(*.0))]
+    Uv(i32),
+}
+
+fn strerror(code: i32) -> String {
+    let msg = unsafe { CStr::from_ptr(ffi::uv_strerror(code)) };
+    msg.to_string_lossy().into_owned()
 }
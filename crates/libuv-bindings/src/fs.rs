@@ -0,0 +1,528 @@
+//! Asynchronous file system operations.
+//!
+//! All the operations run on libuv's threadpool, and their callbacks are
+//! called on the main thread once they complete.
+
+use std::cell::{Cell, RefCell};
+use std::ffi::{c_int, c_void, CStr, CString, OsStr};
+use std::path::Path;
+use std::ptr;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+use libuv_sys2::{
+    self as ffi,
+    uv_dirent_t,
+    uv_file,
+    uv_fs_cb,
+    uv_fs_t,
+    uv_loop_t,
+    uv_req_t,
+    uv_stat_t,
+    uv_timespec_t,
+};
+
+use crate::Error;
+
+/// The number of bytes read at a time by [`read_file`].
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The bit mask of the file type in `st_mode`.
+const S_IFMT: u64 = 0o170000;
+const S_IFDIR: u64 = 0o040000;
+const S_IFREG: u64 = 0o100000;
+const S_IFLNK: u64 = 0o120000;
+
+type FsCallback = Box<dyn FnOnce(*mut uv_fs_t) + 'static>;
+
+type Callback<T> = Box<dyn FnOnce(Result<T, Error>) + 'static>;
+
+/// The type of a file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FileType {
+    Directory,
+    File,
+    Symlink,
+    Other,
+}
+
+/// An entry returned by [`readdir`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirEntry {
+    pub name: String,
+    pub file_type: FileType,
+}
+
+/// Metadata about a file, returned by [`stat`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Metadata {
+    pub file_type: FileType,
+
+    /// The size of the file in bytes.
+    pub len: u64,
+
+    /// The permission bits of the file.
+    pub mode: u32,
+
+    pub accessed: SystemTime,
+    pub modified: SystemTime,
+}
+
+/// A handle to a pending file system operation, which can be used to cancel
+/// it.
+pub struct FsRequest {
+    state: Rc<FsState>,
+}
+
+impl FsRequest {
+    /// Cancels the operation. If it hasn't completed yet its callback is
+    /// called with an `ECANCELED` error.
+    ///
+    /// Operations which are already running on the threadpool can't be
+    /// interrupted, so e.g. a file being read may still be fully read before
+    /// the callback is called.
+    pub fn cancel(&self) {
+        self.state.cancelled.set(true);
+
+        let req = self.state.req.get();
+
+        if !req.is_null() {
+            unsafe { ffi::uv_cancel(req as *mut uv_req_t) };
+        }
+    }
+}
+
+/// The state shared by all the requests making up an operation.
+#[derive(Default)]
+struct FsState {
+    cancelled: Cell<bool>,
+
+    /// The request currently in flight, or null if there isn't one.
+    req: Cell<*mut uv_fs_t>,
+}
+
+/// An operation made up of one or more requests, whose result is passed to
+/// the user's callback.
+struct Op<T> {
+    state: Rc<FsState>,
+    callback: RefCell<Option<Callback<T>>>,
+}
+
+impl<T> Op<T> {
+    fn new<Cb>(callback: Cb) -> Rc<Self>
+    where
+        Cb: FnOnce(Result<T, Error>) + 'static,
+    {
+        Rc::new(Self {
+            state: Rc::default(),
+            callback: RefCell::new(Some(Box::new(callback))),
+        })
+    }
+
+    fn request(&self) -> FsRequest {
+        FsRequest { state: Rc::clone(&self.state) }
+    }
+
+    fn finish(&self, res: Result<T, Error>) {
+        let callback = self.callback.borrow_mut().take();
+
+        if let Some(callback) = callback {
+            if !crate::shutdown::is_shut_down() {
+                callback(res);
+            }
+        }
+    }
+}
+
+/// Reads the whole contents of a file.
+pub fn read_file<P, Cb>(path: P, callback: Cb) -> Result<FsRequest, Error>
+where
+    P: AsRef<Path>,
+    Cb: FnOnce(Result<Vec<u8>, Error>) + 'static,
+{
+    let path = to_cstring(path.as_ref())?;
+    let op = Op::new(callback);
+
+    let flags = ffi::UV_FS_O_RDONLY as c_int;
+
+    fs_call(
+        &op.state,
+        |uv_loop, req, cb| unsafe {
+            ffi::uv_fs_open(uv_loop, req, path.as_ptr(), flags, 0, cb)
+        },
+        result_as_file,
+        {
+            let op = Rc::clone(&op);
+            move |res| match res {
+                Ok(file) => read_chunk(op, file, Vec::new()),
+                Err(err) => op.finish(Err(err)),
+            }
+        },
+    )?;
+
+    Ok(op.request())
+}
+
+/// Reads the next chunk of `file` into `buf`, until the end of the file is
+/// reached.
+fn read_chunk(op: Rc<Op<Vec<u8>>>, file: uv_file, mut buf: Vec<u8>) {
+    buf.reserve(READ_CHUNK_SIZE);
+
+    let uv_buf = unsafe {
+        let base = buf.as_mut_ptr().add(buf.len());
+        ffi::uv_buf_init(base as *mut _, READ_CHUNK_SIZE as _)
+    };
+
+    let res = fs_call(
+        &op.state,
+        |uv_loop, req, cb| unsafe {
+            ffi::uv_fs_read(uv_loop, req, file, &uv_buf, 1, -1, cb)
+        },
+        result_as_usize,
+        {
+            let op = Rc::clone(&op);
+            move |res| match res {
+                Ok(0) => {
+                    close_file(file);
+                    op.finish(Ok(buf));
+                },
+
+                Ok(read) => {
+                    unsafe { buf.set_len(buf.len() + read) };
+                    read_chunk(op, file, buf);
+                },
+
+                Err(err) => {
+                    close_file(file);
+                    op.finish(Err(err));
+                },
+            }
+        },
+    );
+
+    if let Err(err) = res {
+        close_file(file);
+        op.finish(Err(err));
+    }
+}
+
+/// Writes `data` to a file, creating it if it doesn't exist and truncating
+/// it if it does.
+pub fn write_file<P, Cb>(
+    path: P,
+    data: Vec<u8>,
+    callback: Cb,
+) -> Result<FsRequest, Error>
+where
+    P: AsRef<Path>,
+    Cb: FnOnce(Result<(), Error>) + 'static,
+{
+    let path = to_cstring(path.as_ref())?;
+    let op = Op::new(callback);
+
+    let flags = (ffi::UV_FS_O_WRONLY | ffi::UV_FS_O_CREAT | ffi::UV_FS_O_TRUNC)
+        as c_int;
+
+    fs_call(
+        &op.state,
+        |uv_loop, req, cb| unsafe {
+            ffi::uv_fs_open(uv_loop, req, path.as_ptr(), flags, 0o644, cb)
+        },
+        result_as_file,
+        {
+            let op = Rc::clone(&op);
+            move |res| match res {
+                Ok(file) => write_chunk(op, file, data, 0),
+                Err(err) => op.finish(Err(err)),
+            }
+        },
+    )?;
+
+    Ok(op.request())
+}
+
+/// Writes `data[written..]` to `file`, until all of `data` has been written.
+fn write_chunk(op: Rc<Op<()>>, file: uv_file, data: Vec<u8>, written: usize) {
+    if written == data.len() {
+        close_file(file);
+        return op.finish(Ok(()));
+    }
+
+    let uv_buf = unsafe {
+        let base = data.as_ptr().add(written);
+        ffi::uv_buf_init(base as *mut _, (data.len() - written) as _)
+    };
+
+    let res = fs_call(
+        &op.state,
+        |uv_loop, req, cb| unsafe {
+            ffi::uv_fs_write(uv_loop, req, file, &uv_buf, 1, -1, cb)
+        },
+        result_as_usize,
+        {
+            let op = Rc::clone(&op);
+            move |res| match res {
+                Ok(n) => write_chunk(op, file, data, written + n),
+
+                Err(err) => {
+                    close_file(file);
+                    op.finish(Err(err));
+                },
+            }
+        },
+    );
+
+    if let Err(err) = res {
+        close_file(file);
+        op.finish(Err(err));
+    }
+}
+
+/// Returns the metadata of a file, following symlinks.
+pub fn stat<P, Cb>(path: P, callback: Cb) -> Result<FsRequest, Error>
+where
+    P: AsRef<Path>,
+    Cb: FnOnce(Result<Metadata, Error>) + 'static,
+{
+    let path = to_cstring(path.as_ref())?;
+    let op = Op::new(callback);
+
+    fs_call(
+        &op.state,
+        |uv_loop, req, cb| unsafe {
+            ffi::uv_fs_stat(uv_loop, req, path.as_ptr(), cb)
+        },
+        |req| unsafe { Metadata::from(&(*req).statbuf) },
+        {
+            let op = Rc::clone(&op);
+            move |res| op.finish(res)
+        },
+    )?;
+
+    Ok(op.request())
+}
+
+/// Returns the entries of a directory, excluding `.` and `..`.
+pub fn readdir<P, Cb>(path: P, callback: Cb) -> Result<FsRequest, Error>
+where
+    P: AsRef<Path>,
+    Cb: FnOnce(Result<Vec<DirEntry>, Error>) + 'static,
+{
+    let path = to_cstring(path.as_ref())?;
+    let op = Op::new(callback);
+
+    fs_call(
+        &op.state,
+        |uv_loop, req, cb| unsafe {
+            ffi::uv_fs_scandir(uv_loop, req, path.as_ptr(), 0, cb)
+        },
+        |req| {
+            let mut entries = Vec::new();
+            let mut dirent = unsafe { std::mem::zeroed::<uv_dirent_t>() };
+
+            while unsafe { ffi::uv_fs_scandir_next(req, &mut dirent) } >= 0 {
+                entries.push(DirEntry::from(&dirent));
+            }
+
+            entries
+        },
+        {
+            let op = Rc::clone(&op);
+            move |res| op.finish(res)
+        },
+    )?;
+
+    Ok(op.request())
+}
+
+/// Creates a new directory with the given permission bits.
+pub fn mkdir<P, Cb>(
+    path: P,
+    mode: u32,
+    callback: Cb,
+) -> Result<FsRequest, Error>
+where
+    P: AsRef<Path>,
+    Cb: FnOnce(Result<(), Error>) + 'static,
+{
+    let path = to_cstring(path.as_ref())?;
+    let op = Op::new(callback);
+
+    fs_call(
+        &op.state,
+        |uv_loop, req, cb| unsafe {
+            ffi::uv_fs_mkdir(uv_loop, req, path.as_ptr(), mode as c_int, cb)
+        },
+        |_| (),
+        {
+            let op = Rc::clone(&op);
+            move |res| op.finish(res)
+        },
+    )?;
+
+    Ok(op.request())
+}
+
+/// Starts a single file system request, calling `then` with the value
+/// returned by `extract` once it completes.
+fn fs_call<T, S, X, F>(
+    state: &Rc<FsState>,
+    start: S,
+    extract: X,
+    then: F,
+) -> Result<(), Error>
+where
+    S: FnOnce(*mut uv_loop_t, *mut uv_fs_t, uv_fs_cb) -> c_int,
+    X: FnOnce(*mut uv_fs_t) -> T + 'static,
+    F: FnOnce(Result<T, Error>) + 'static,
+{
+    let req =
+        Box::into_raw(Box::new(unsafe { std::mem::zeroed::<uv_fs_t>() }));
+
+    let retv = unsafe {
+        crate::with_loop(|uv_loop| start(uv_loop, req, Some(fs_cb as _)))
+    };
+
+    if retv < 0 {
+        unsafe { free_req(req) };
+        return Err(Error::Uv(retv));
+    }
+
+    state.req.set(req);
+
+    let state = Rc::clone(state);
+
+    let callback: FsCallback = Box::new(move |req| {
+        let result = unsafe { (*req).result };
+
+        let res = if state.cancelled.get() {
+            // An open which completed before being cancelled still returns a
+            // file, which nobody else is going to close.
+            let is_open =
+                unsafe { (*req).fs_type } == ffi::uv_fs_type_UV_FS_OPEN;
+
+            if is_open && result >= 0 {
+                close_file(result as uv_file);
+            }
+
+            Err(Error::Uv(ffi::uv_errno_t_UV_ECANCELED as _))
+        } else if result < 0 {
+            Err(Error::Uv(result as _))
+        } else {
+            Ok(extract(req))
+        };
+
+        unsafe { free_req(req) };
+        state.req.set(ptr::null_mut());
+
+        then(res)
+    });
+
+    // The callback is never called before the next iteration of the loop, so
+    // it's fine to set the data after starting the request.
+    unsafe { (*req).data = Box::into_raw(Box::new(callback)) as *mut c_void };
+
+    Ok(())
+}
+
+/// Closes a file in the background. The file is closed even if the
+/// operation that opened it is cancelled.
+fn close_file(file: uv_file) {
+    let _ = fs_call(
+        &Rc::default(),
+        |uv_loop, req, cb| unsafe { ffi::uv_fs_close(uv_loop, req, file, cb) },
+        |_| (),
+        |_| {},
+    );
+}
+
+unsafe fn free_req(req: *mut uv_fs_t) {
+    ffi::uv_fs_req_cleanup(req);
+    drop(Box::from_raw(req));
+}
+
+fn result_as_file(req: *mut uv_fs_t) -> uv_file {
+    unsafe { (*req).result as uv_file }
+}
+
+fn result_as_usize(req: *mut uv_fs_t) -> usize {
+    unsafe { (*req).result as usize }
+}
+
+/// Converts a path, or any other OS string, to the C string expected by
+/// libuv. On Unix the string is passed as is, while on Windows libuv expects
+/// it to be UTF-8.
+pub(crate) fn to_cstring<S: AsRef<OsStr>>(str: S) -> Result<CString, Error> {
+    let str = str.as_ref();
+
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        str.as_bytes().to_vec()
+    };
+
+    #[cfg(not(unix))]
+    let bytes = match str.to_str() {
+        Some(str) => str.as_bytes().to_vec(),
+        None => return Err(Error::Uv(ffi::uv_errno_t_UV_EINVAL as _)),
+    };
+
+    CString::new(bytes).map_err(|_| Error::Uv(ffi::uv_errno_t_UV_EINVAL as _))
+}
+
+extern "C" fn fs_cb(req: *mut uv_fs_t) {
+    let callback = unsafe { Box::from_raw((*req).data as *mut FsCallback) };
+    callback(req);
+}
+
+impl From<&uv_dirent_t> for DirEntry {
+    fn from(dirent: &uv_dirent_t) -> Self {
+        let name = unsafe { CStr::from_ptr(dirent.name) };
+
+        let file_type = match dirent.type_ {
+            ffi::uv_dirent_type_t_UV_DIRENT_DIR => FileType::Directory,
+            ffi::uv_dirent_type_t_UV_DIRENT_FILE => FileType::File,
+            ffi::uv_dirent_type_t_UV_DIRENT_LINK => FileType::Symlink,
+            _ => FileType::Other,
+        };
+
+        Self { name: name.to_string_lossy().into_owned(), file_type }
+    }
+}
+
+impl From<&uv_stat_t> for Metadata {
+    fn from(stat: &uv_stat_t) -> Self {
+        let file_type = match stat.st_mode & S_IFMT {
+            S_IFDIR => FileType::Directory,
+            S_IFREG => FileType::File,
+            S_IFLNK => FileType::Symlink,
+            _ => FileType::Other,
+        };
+
+        Self {
+            file_type,
+            len: stat.st_size,
+            mode: (stat.st_mode & 0o7777) as u32,
+            accessed: to_system_time(&stat.st_atim),
+            modified: to_system_time(&stat.st_mtim),
+        }
+    }
+}
+
+/// Converts a timestamp relative to the Unix epoch, which can precede it.
+/// Timestamps which can't be represented fall back to the epoch itself, since
+/// this runs inside libuv's callbacks where panicking isn't an option.
+fn to_system_time(time: &uv_timespec_t) -> SystemTime {
+    let secs = Duration::from_secs(time.tv_sec.unsigned_abs());
+    let nanos = Duration::from_nanos(time.tv_nsec as u64);
+
+    let time = if time.tv_sec < 0 {
+        SystemTime::UNIX_EPOCH.checked_sub(secs)
+    } else {
+        SystemTime::UNIX_EPOCH.checked_add(secs)
+    };
+
+    time.and_then(|time| time.checked_add(nanos))
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
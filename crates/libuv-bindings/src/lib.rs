@@ -1,13 +1,23 @@
 mod r#async;
 mod error;
+pub mod fs;
 mod handle;
 mod r#loop;
+mod pipe;
+mod process;
+mod shutdown;
+mod stream;
+mod tcp;
 mod timer;
 
 pub use error::Error;
 use error::Result;
 use handle::Handle;
+pub use pipe::PipeHandle;
+pub use process::{Process, SpawnOpts, Stdio};
 pub use r#async::AsyncHandle;
 pub use r#loop::init;
 use r#loop::with_loop;
+pub use shutdown::{on_shutdown, shutdown};
+pub use tcp::{TcpHandle, TcpStream};
 pub use timer::TimerHandle;
@@ -0,0 +1,79 @@
+use libuv_sys2::{self as ffi, uv_pipe_t, uv_stream_t};
+
+use crate::stream::{Stream, StreamData};
+use crate::{Error, Handle};
+
+/// Binding to libuv's [pipe handle][1], used to communicate with the
+/// standard streams of a [`Process`](crate::Process).
+///
+/// Cloning a pipe doesn't create a new one, it just returns another
+/// reference to the same one. The pipe is closed once it's closed explicitly
+/// or once all its clones, including the one held by the `Process`, have
+/// been dropped.
+///
+/// [1]: http://docs.libuv.org/en/v1.x/pipe.html
+#[derive(Clone)]
+pub struct PipeHandle {
+    stream: Stream<uv_pipe_t>,
+}
+
+impl PipeHandle {
+    pub(crate) fn new() -> Result<Self, Error> {
+        let mut handle = Handle::new(|uv_loop, handle| unsafe {
+            ffi::uv_pipe_init(uv_loop, handle.as_mut_ptr(), 0)
+        })?;
+
+        unsafe { handle.set_data(StreamData::default()) };
+
+        let stream = unsafe { Stream::from_raw(handle.as_mut_ptr()) };
+
+        Ok(Self { stream })
+    }
+
+    /// Starts reading from the pipe, calling `callback` with the bytes read
+    /// every time new data is available. The callback is called with an
+    /// empty buffer once the other end has closed the pipe.
+    ///
+    /// Calling this again replaces the previous callback.
+    pub fn read_start<Cb>(&mut self, callback: Cb) -> Result<(), Error>
+    where
+        Cb: FnMut(Result<Vec<u8>, Error>) + 'static,
+    {
+        self.stream.read_start(callback)
+    }
+
+    /// Stops reading from the pipe.
+    pub fn read_stop(&mut self) {
+        self.stream.read_stop()
+    }
+
+    /// Writes `data` to the pipe, calling `callback` with the number of
+    /// bytes written once the write has completed. The callback is called on
+    /// the main thread.
+    pub fn write<Cb>(
+        &mut self,
+        data: Vec<u8>,
+        callback: Cb,
+    ) -> Result<(), Error>
+    where
+        Cb: FnOnce(Result<usize, Error>) + 'static,
+    {
+        self.stream.write(data, callback)
+    }
+
+    /// Closes the pipe, calling `callback` once it's been closed. Closing
+    /// the stdin of a process signals it that there's no more input.
+    ///
+    /// Using any of the other clones of the pipe after this fails with
+    /// [`Error::StreamClosed`].
+    pub fn close<Cb>(self, callback: Cb)
+    where
+        Cb: FnOnce() + 'static,
+    {
+        self.stream.close(callback)
+    }
+
+    pub(crate) fn as_stream_ptr(&mut self) -> *mut uv_stream_t {
+        self.stream.as_stream_ptr()
+    }
+}
@@ -0,0 +1,384 @@
+use std::alloc::{self, Layout};
+use std::cell::Cell;
+use std::ffi::{c_char, c_int, c_uint, CString, OsString};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::{iter, mem, ptr};
+
+use libuv_sys2::{
+    self as ffi,
+    uv_handle_t,
+    uv_process_options_t,
+    uv_process_t,
+    uv_stdio_container_t,
+};
+
+use crate::fs::to_cstring;
+use crate::{Error, Handle, PipeHandle};
+
+type ExitCallback = Box<dyn FnOnce(i64, i32) + 'static>;
+
+/// The data attached to a process handle.
+struct ProcessData {
+    on_exit: Option<ExitCallback>,
+    exited: Rc<Cell<bool>>,
+}
+
+/// How one of the standard streams of a child process is set up.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Stdio {
+    /// Connects the stream to a [`PipeHandle`], returned by
+    /// [`Process::stdin`], [`Process::stdout`] or [`Process::stderr`].
+    #[default]
+    Pipe,
+
+    /// Inherits the stream from Neovim. Writing to Neovim's stdout is
+    /// likely to mess up its UI.
+    Inherit,
+
+    /// Redirects the stream to `/dev/null`, or its equivalent on Windows.
+    Null,
+}
+
+/// Options passed to [`Process::spawn`].
+///
+/// By default the process inherits Neovim's environment and working
+/// directory, and all its standard streams are piped.
+#[derive(Default)]
+pub struct SpawnOpts {
+    env: Vec<(OsString, OsString)>,
+    clear_env: bool,
+    cwd: Option<PathBuf>,
+    detached: bool,
+    stdio: [Stdio; 3],
+    on_exit: Option<ExitCallback>,
+}
+
+impl SpawnOpts {
+    /// Sets the environment variable `key` to `value` in the environment of
+    /// the process.
+    pub fn env(
+        mut self,
+        key: impl Into<OsString>,
+        value: impl Into<OsString>,
+    ) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Doesn't pass Neovim's environment to the process, which only gets
+    /// the variables set with [`env`](SpawnOpts::env).
+    pub fn clear_env(mut self) -> Self {
+        self.clear_env = true;
+        self
+    }
+
+    /// Sets the working directory of the process.
+    pub fn cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Whether to run the process in its own process group (or in its own
+    /// console on Windows), so that it keeps running after Neovim exits.
+    pub fn detached(mut self, detached: bool) -> Self {
+        self.detached = detached;
+        self
+    }
+
+    /// Sets up the stdin of the process.
+    pub fn stdin(mut self, stdin: Stdio) -> Self {
+        self.stdio[0] = stdin;
+        self
+    }
+
+    /// Sets up the stdout of the process.
+    pub fn stdout(mut self, stdout: Stdio) -> Self {
+        self.stdio[1] = stdout;
+        self
+    }
+
+    /// Sets up the stderr of the process.
+    pub fn stderr(mut self, stderr: Stdio) -> Self {
+        self.stdio[2] = stderr;
+        self
+    }
+
+    /// Registers a callback to be called on the main thread once the
+    /// process has exited, with its exit status and the number of the
+    /// signal that terminated it, or zero if it exited normally.
+    pub fn on_exit<Cb>(mut self, callback: Cb) -> Self
+    where
+        Cb: FnOnce(i64, i32) + 'static,
+    {
+        self.on_exit = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns the environment of the process as `KEY=VALUE` strings, or
+    /// `None` if it's the same as Neovim's.
+    fn environment(&self) -> Option<Vec<OsString>> {
+        if self.env.is_empty() && !self.clear_env {
+            return None;
+        }
+
+        let inherited = std::env::vars_os().filter(|_| !self.clear_env);
+
+        // Later values override earlier ones, so the explicitly set
+        // variables win over the inherited ones.
+        let mut vars = Vec::<(OsString, OsString)>::new();
+        for (key, value) in inherited.chain(self.env.iter().cloned()) {
+            match vars.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, v)) => *v = value,
+                None => vars.push((key, value)),
+            }
+        }
+
+        let vars = vars.into_iter().map(|(mut var, value)| {
+            var.push("=");
+            var.push(value);
+            var
+        });
+
+        Some(vars.collect())
+    }
+}
+
+/// Binding to libuv's [process handle][1], used to spawn child processes
+/// and communicate with them without blocking.
+///
+/// Dropping a `Process` doesn't kill the process, and its
+/// [`on_exit`](SpawnOpts::on_exit) callback is still called once it exits.
+/// Its pipes are closed once the `Process` and all the clones returned by
+/// [`stdin`](Process::stdin) and friends have been dropped.
+///
+/// [1]: http://docs.libuv.org/en/v1.x/process.html
+pub struct Process {
+    handle: Handle<uv_process_t, ProcessData>,
+    pid: i32,
+    exited: Rc<Cell<bool>>,
+    stdin: Option<PipeHandle>,
+    stdout: Option<PipeHandle>,
+    stderr: Option<PipeHandle>,
+}
+
+impl Process {
+    /// Spawns `cmd` with the given arguments. The command is looked up in
+    /// `$PATH` if it's not a path.
+    pub fn spawn(
+        cmd: &str,
+        args: &[&str],
+        opts: SpawnOpts,
+    ) -> Result<Self, Error> {
+        let file = to_cstring(cmd)?;
+
+        let args = iter::once(cmd)
+            .chain(args.iter().copied())
+            .map(to_cstring)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let env = opts
+            .environment()
+            .map(|env| {
+                env.iter().map(to_cstring).collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        let cwd = opts.cwd.as_ref().map(to_cstring).transpose()?;
+
+        let mut args = null_terminated(&args);
+        let mut env = env.as_deref().map(null_terminated);
+
+        let mut pipes = [None, None, None];
+        let mut pipe_err = None;
+
+        for (pipe, stdio) in pipes.iter_mut().zip(opts.stdio) {
+            if stdio != Stdio::Pipe {
+                continue;
+            }
+
+            match PipeHandle::new() {
+                Ok(new) => *pipe = Some(new),
+
+                Err(err) => {
+                    pipe_err = Some(err);
+                    break;
+                },
+            }
+        }
+
+        if let Some(err) = pipe_err {
+            close_pipes(pipes);
+            return Err(err);
+        }
+
+        let mut stdio: [uv_stdio_container_t; 3] = unsafe { mem::zeroed() };
+
+        for (fd, (container, pipe)) in
+            stdio.iter_mut().zip(pipes.iter_mut()).enumerate()
+        {
+            match (opts.stdio[fd], pipe) {
+                (Stdio::Pipe, Some(pipe)) => {
+                    // The child reads from its stdin and writes to its
+                    // stdout and stderr.
+                    let direction = if fd == 0 {
+                        ffi::uv_stdio_flags_UV_READABLE_PIPE
+                    } else {
+                        ffi::uv_stdio_flags_UV_WRITABLE_PIPE
+                    };
+
+                    container.flags =
+                        (ffi::uv_stdio_flags_UV_CREATE_PIPE | direction) as _;
+                    container.data.stream = pipe.as_stream_ptr();
+                },
+
+                (Stdio::Inherit, _) => {
+                    container.flags = ffi::uv_stdio_flags_UV_INHERIT_FD as _;
+                    container.data.fd = fd as c_int;
+                },
+
+                _ => container.flags = ffi::uv_stdio_flags_UV_IGNORE as _,
+            }
+        }
+
+        let mut options: uv_process_options_t = unsafe { mem::zeroed() };
+        options.exit_cb = Some(exit_cb as _);
+        options.file = file.as_ptr();
+        options.args = args.as_mut_ptr();
+        options.env = env.as_mut().map_or(ptr::null_mut(), |e| e.as_mut_ptr());
+        options.cwd = cwd.as_ref().map_or(ptr::null(), |cwd| cwd.as_ptr());
+        options.stdio_count = stdio.len() as c_int;
+        options.stdio = stdio.as_mut_ptr();
+
+        if opts.detached {
+            options.flags |=
+                ffi::uv_process_flags_UV_PROCESS_DETACHED as c_uint;
+        }
+
+        let mut retv = 0;
+
+        let mut handle = Handle::new(|uv_loop, handle| {
+            retv = unsafe {
+                ffi::uv_spawn(uv_loop, handle.as_mut_ptr(), &options)
+            };
+            // The handle is initialized even if spawning the process failed,
+            // and it has to be closed either way.
+            0
+        })?;
+
+        let exited = Rc::new(Cell::new(false));
+
+        unsafe {
+            handle.set_data(ProcessData {
+                on_exit: opts.on_exit,
+                exited: Rc::clone(&exited),
+            })
+        };
+
+        if retv < 0 {
+            unsafe { close(handle.as_mut_ptr()) };
+            close_pipes(pipes);
+            return Err(Error::Uv(retv));
+        }
+
+        let pid = unsafe { ffi::uv_process_get_pid(handle.as_ptr()) };
+
+        let [stdin, stdout, stderr] = pipes;
+
+        Ok(Self { handle, pid: pid as i32, exited, stdin, stdout, stderr })
+    }
+
+    /// Returns the id of the process.
+    #[inline]
+    pub fn pid(&self) -> i32 {
+        self.pid
+    }
+
+    /// Returns the pipe connected to the stdin of the process, if it was
+    /// spawned with [`Stdio::Pipe`].
+    pub fn stdin(&self) -> Option<PipeHandle> {
+        self.stdin.clone()
+    }
+
+    /// Returns the pipe connected to the stdout of the process, if it was
+    /// spawned with [`Stdio::Pipe`].
+    pub fn stdout(&self) -> Option<PipeHandle> {
+        self.stdout.clone()
+    }
+
+    /// Returns the pipe connected to the stderr of the process, if it was
+    /// spawned with [`Stdio::Pipe`].
+    pub fn stderr(&self) -> Option<PipeHandle> {
+        self.stderr.clone()
+    }
+
+    /// Sends `signal` to the process. Fails with `ESRCH` if the process has
+    /// already exited.
+    pub fn kill(&mut self, signal: i32) -> Result<(), Error> {
+        if self.exited.get() {
+            return Err(Error::Uv(ffi::uv_errno_t_UV_ESRCH as _));
+        }
+
+        let retv =
+            unsafe { ffi::uv_process_kill(self.handle.as_mut_ptr(), signal) };
+
+        if retv < 0 {
+            return Err(Error::Uv(retv));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the pointers to the given strings followed by a null pointer, as
+/// expected by libuv for the arguments and the environment of a process.
+fn null_terminated(strings: &[CString]) -> Vec<*mut c_char> {
+    strings
+        .iter()
+        .map(|str| str.as_ptr() as *mut c_char)
+        .chain(iter::once(ptr::null_mut()))
+        .collect()
+}
+
+fn close_pipes(pipes: [Option<PipeHandle>; 3]) {
+    for pipe in pipes.into_iter().flatten() {
+        pipe.close(|| ());
+    }
+}
+
+/// Closes a process handle, freeing its memory once libuv is done with it.
+unsafe fn close(process: *mut uv_process_t) {
+    ffi::uv_close(process as *mut uv_handle_t, Some(close_cb as _));
+}
+
+extern "C" fn exit_cb(
+    ptr: *mut uv_process_t,
+    exit_status: i64,
+    term_signal: c_int,
+) {
+    let handle: Handle<_, ProcessData> = unsafe { Handle::from_raw(ptr) };
+
+    let data = unsafe { &mut *handle.get_data() };
+    data.exited.set(true);
+    let on_exit = data.on_exit.take();
+
+    unsafe { close(ptr) };
+
+    if crate::shutdown::is_shut_down() {
+        return;
+    }
+
+    if let Some(on_exit) = on_exit {
+        on_exit(exit_status, term_signal);
+    }
+}
+
+extern "C" fn close_cb(ptr: *mut uv_handle_t) {
+    let handle: Handle<uv_process_t, ProcessData> =
+        unsafe { Handle::from_raw(ptr as *mut _) };
+
+    unsafe {
+        drop(Box::from_raw(handle.get_data()));
+        alloc::dealloc(ptr as *mut u8, Layout::new::<uv_process_t>());
+    }
+}
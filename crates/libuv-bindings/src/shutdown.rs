@@ -0,0 +1,70 @@
+use std::cell::{Cell, RefCell};
+
+use libuv_sys2::{self as ffi, uv_timer_t};
+
+type Hook = Box<dyn FnOnce() + 'static>;
+
+thread_local! {
+    static HOOKS: RefCell<Vec<Hook>> = RefCell::new(Vec::new());
+
+    /// Pointers to all the timer handles created by this crate which haven't
+    /// been closed yet.
+    static TIMERS: RefCell<Vec<*mut uv_timer_t>> = RefCell::new(Vec::new());
+
+    static IS_SHUT_DOWN: Cell<bool> = Cell::new(false);
+}
+
+/// Registers a hook to be called once when Neovim is about to exit, i.e. on
+/// [`VimLeavePre`](https://neovim.io/doc/user/autocmd.html#VimLeavePre), or
+/// when [`shutdown`] is called.
+///
+/// Hooks registered after the shutdown has already happened are called
+/// immediately.
+pub fn on_shutdown<F>(hook: F)
+where
+    F: FnOnce() + 'static,
+{
+    if is_shut_down() {
+        hook();
+    } else {
+        HOOKS.with(|hooks| hooks.borrow_mut().push(Box::new(hook)));
+    }
+}
+
+/// Stops all the timers created by this crate, prevents the callbacks of
+/// all the other handles from being called and then calls the hooks
+/// registered via [`on_shutdown`].
+///
+/// This is called automatically on `VimLeavePre`, so it's only needed to
+/// tear down a plugin while Neovim keeps running. Calling it more than once
+/// has no effect.
+pub fn shutdown() {
+    if IS_SHUT_DOWN.with(|flag| flag.replace(true)) {
+        return;
+    }
+
+    for timer in TIMERS.with(|timers| timers.take()) {
+        // Stopping a timer can't fail.
+        unsafe { ffi::uv_timer_stop(timer) };
+    }
+
+    // Take the hooks out first in case a hook registers another one.
+    for hook in HOOKS.with(|hooks| hooks.take()) {
+        hook();
+    }
+}
+
+/// Returns `true` if [`shutdown`] has already been called.
+pub(crate) fn is_shut_down() -> bool {
+    IS_SHUT_DOWN.with(Cell::get)
+}
+
+/// Registers a timer handle to be stopped on shutdown.
+pub(crate) fn track_timer(timer: *mut uv_timer_t) {
+    TIMERS.with(|timers| timers.borrow_mut().push(timer));
+}
+
+/// Stops tracking a timer handle which is being closed.
+pub(crate) fn untrack_timer(timer: *mut uv_timer_t) {
+    TIMERS.with(|timers| timers.borrow_mut().retain(|&ptr| ptr != timer));
+}
@@ -0,0 +1,330 @@
+use std::alloc::{self, Layout};
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::ptr;
+use std::rc::Rc;
+
+use libuv_sys2::{
+    self as ffi,
+    uv_buf_t,
+    uv_handle_t,
+    uv_stream_t,
+    uv_write_t,
+};
+
+use crate::{Error, Handle};
+
+type ReadCallback = Box<dyn FnMut(Result<Vec<u8>, Error>) + 'static>;
+
+type WriteCallback = Box<dyn FnOnce(Result<usize, Error>) + 'static>;
+
+type CloseCallback = Box<dyn FnOnce() + 'static>;
+
+/// The data attached to a stream handle.
+#[derive(Default)]
+pub(crate) struct StreamData {
+    on_read: Option<ReadCallback>,
+    on_close: Option<CloseCallback>,
+    /// Whether the stream is being read from, which stays `true` while the
+    /// read callback is running and `on_read` is temporarily empty.
+    reading: bool,
+}
+
+/// The data attached to a write request. The buffer has to be kept alive
+/// until libuv is done writing it.
+struct WriteData {
+    buf: Vec<u8>,
+    callback: WriteCallback,
+}
+
+/// The operations shared by all the [stream handles][1], like TCP streams
+/// and pipes. `T` is the type of the underlying handle.
+///
+/// All the clones of a stream share the same handle. Once it's been closed
+/// the methods of every clone fail with [`Error::StreamClosed`], and the
+/// handle is closed automatically when the last clone is dropped.
+///
+/// [1]: http://docs.libuv.org/en/v1.x/stream.html
+pub(crate) struct Stream<T: 'static> {
+    inner: Rc<StreamInner<T>>,
+}
+
+/// The handle shared by the clones of a [`Stream`], or a null pointer once
+/// it's been closed.
+struct StreamInner<T> {
+    ptr: Cell<*mut T>,
+}
+
+impl<T> Drop for StreamInner<T> {
+    fn drop(&mut self) {
+        let ptr = self.ptr.replace(ptr::null_mut());
+
+        if !ptr.is_null() {
+            unsafe { close(ptr) };
+        }
+    }
+}
+
+impl<T> Clone for Stream<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Rc::clone(&self.inner) }
+    }
+}
+
+impl<T> Stream<T> {
+    /// Creates a stream from a handle whose data has been set to a
+    /// [`StreamData`].
+    pub(crate) unsafe fn from_raw(ptr: *mut T) -> Self {
+        Self { inner: Rc::new(StreamInner { ptr: Cell::new(ptr) }) }
+    }
+
+    pub(crate) fn read_start<Cb>(&mut self, callback: Cb) -> Result<(), Error>
+    where
+        Cb: FnMut(Result<Vec<u8>, Error>) + 'static,
+    {
+        let handle = self.handle()?;
+
+        let data = unsafe { handle.get_data() };
+        unsafe { (*data).on_read = Some(Box::new(callback)) };
+
+        let retv = unsafe {
+            ffi::uv_read_start(
+                handle.as_ptr() as *mut uv_stream_t,
+                Some(alloc_cb as _),
+                Some(read_cb as _),
+            )
+        };
+
+        if retv < 0 {
+            unsafe { (*data).on_read = None };
+            return Err(Error::Uv(retv));
+        }
+
+        unsafe { (*data).reading = true };
+
+        Ok(())
+    }
+
+    pub(crate) fn read_stop(&mut self) {
+        let handle = match self.handle() {
+            Ok(handle) => handle,
+            Err(_) => return,
+        };
+
+        // Stopping a stream can't fail.
+        unsafe {
+            ffi::uv_read_stop(handle.as_ptr() as *mut uv_stream_t);
+            let data = handle.get_data();
+            (*data).on_read = None;
+            (*data).reading = false;
+        }
+    }
+
+    pub(crate) fn write<Cb>(
+        &mut self,
+        data: Vec<u8>,
+        callback: Cb,
+    ) -> Result<(), Error>
+    where
+        Cb: FnOnce(Result<usize, Error>) + 'static,
+    {
+        let handle = self.handle()?;
+
+        let len = data.len();
+
+        let req = alloc_req::<uv_write_t, WriteData>(WriteData {
+            buf: data,
+            callback: Box::new(callback),
+        });
+
+        let retv = unsafe {
+            let data = &mut *((*req).data as *mut WriteData);
+            let buf =
+                ffi::uv_buf_init(data.buf.as_mut_ptr() as *mut _, len as _);
+
+            ffi::uv_write(
+                req,
+                handle.as_ptr() as *mut uv_stream_t,
+                &buf,
+                1,
+                Some(write_cb as _),
+            )
+        };
+
+        if retv < 0 {
+            unsafe { drop(free_req::<_, WriteData>(req)) };
+            return Err(Error::Uv(retv));
+        }
+
+        Ok(())
+    }
+
+    /// Closes the stream, calling `callback` once it's been closed. Does
+    /// nothing if the stream has already been closed.
+    pub(crate) fn close<Cb>(self, callback: Cb)
+    where
+        Cb: FnOnce() + 'static,
+    {
+        let ptr = self.inner.ptr.replace(ptr::null_mut());
+
+        if ptr.is_null() {
+            return;
+        }
+
+        unsafe {
+            let handle = Handle::<T, StreamData>::from_raw(ptr);
+            (*handle.get_data()).on_close = Some(Box::new(callback));
+            close(ptr);
+        }
+    }
+
+    /// Returns a pointer to the underlying handle as a `uv_stream_t`, or
+    /// a null pointer if the stream has been closed.
+    pub(crate) fn as_stream_ptr(&self) -> *mut uv_stream_t {
+        self.inner.ptr.get() as *mut uv_stream_t
+    }
+
+    fn handle(&self) -> Result<Handle<T, StreamData>, Error> {
+        let ptr = self.inner.ptr.get();
+
+        if ptr.is_null() {
+            Err(Error::StreamClosed)
+        } else {
+            Ok(unsafe { Handle::from_raw(ptr) })
+        }
+    }
+}
+
+/// Allocates a new libuv request of type `R`, storing `data` in its `data`
+/// field.
+pub(crate) fn alloc_req<R, D>(data: D) -> *mut R {
+    let req = unsafe { alloc::alloc_zeroed(Layout::new::<R>()) as *mut R };
+
+    if req.is_null() {
+        alloc::handle_alloc_error(Layout::new::<R>());
+    }
+
+    // All libuv requests start with the same fields as `uv_req_t`.
+    unsafe {
+        (*(req as *mut ffi::uv_req_t)).data =
+            Box::into_raw(Box::new(data)) as *mut c_void
+    };
+
+    req
+}
+
+/// Frees a request allocated with [`alloc_req`], returning its data.
+pub(crate) unsafe fn free_req<R, D>(req: *mut R) -> D {
+    let data = (*(req as *mut ffi::uv_req_t)).data as *mut D;
+    alloc::dealloc(req as *mut u8, Layout::new::<R>());
+    *Box::from_raw(data)
+}
+
+/// Closes a stream handle of type `T`, freeing its memory and its
+/// [`StreamData`] once libuv is done with it.
+pub(crate) unsafe fn close<T>(handle: *mut T) {
+    ffi::uv_close(handle as *mut uv_handle_t, Some(close_cb::<T> as _));
+}
+
+extern "C" fn alloc_cb(
+    _handle: *mut uv_handle_t,
+    suggested_size: usize,
+    buf: *mut uv_buf_t,
+) {
+    let mut bytes = Vec::<u8>::with_capacity(suggested_size);
+    let capacity = bytes.capacity();
+    let base = bytes.as_mut_ptr();
+    std::mem::forget(bytes);
+
+    unsafe { *buf = ffi::uv_buf_init(base as *mut _, capacity as _) };
+}
+
+extern "C" fn read_cb(
+    stream: *mut uv_stream_t,
+    nread: isize,
+    buf: *const uv_buf_t,
+) {
+    // Take back ownership of the buffer allocated in `alloc_cb`. The buffer
+    // can be null if `nread` is an error.
+    let bytes = unsafe {
+        let base = (*buf).base as *mut u8;
+        // `len` is a `ULONG` on Windows.
+        #[allow(clippy::unnecessary_cast)]
+        let capacity = (*buf).len as usize;
+
+        if base.is_null() {
+            Vec::new()
+        } else {
+            Vec::from_raw_parts(base, nread.max(0) as usize, capacity)
+        }
+    };
+
+    // A zero `nread` is equivalent to `EAGAIN`, nothing to do.
+    if nread == 0 || crate::shutdown::is_shut_down() {
+        return;
+    }
+
+    let handle: Handle<uv_stream_t, StreamData> =
+        unsafe { Handle::from_raw(stream) };
+
+    let data = unsafe { handle.get_data() };
+
+    // Take the callback out of the handle's data while it runs, so that it
+    // can stop, restart or close the stream without freeing itself or
+    // aliasing the data.
+    let mut callback = match unsafe { (*data).on_read.take() } {
+        Some(callback) => callback,
+        None => return,
+    };
+
+    if nread > 0 {
+        callback(Ok(bytes));
+    } else if nread == ffi::uv_errno_t_UV_EOF as isize {
+        callback(Ok(Vec::new()));
+    } else {
+        callback(Err(Error::Uv(nread as i32)));
+    }
+
+    // Put it back unless it's been replaced by `read_start`, removed by
+    // `read_stop` or the stream is being closed, in which case the data
+    // could be freed anytime after this.
+    let is_closing =
+        unsafe { ffi::uv_is_closing(stream as *const uv_handle_t) } != 0;
+
+    unsafe {
+        if !is_closing && (*data).reading && (*data).on_read.is_none() {
+            (*data).on_read = Some(callback);
+        }
+    }
+}
+
+extern "C" fn write_cb(req: *mut uv_write_t, status: i32) {
+    let WriteData { buf, callback } = unsafe { free_req::<_, WriteData>(req) };
+
+    if crate::shutdown::is_shut_down() {
+        return;
+    }
+
+    if status < 0 {
+        callback(Err(Error::Uv(status)));
+    } else {
+        callback(Ok(buf.len()));
+    }
+}
+
+extern "C" fn close_cb<T>(ptr: *mut uv_handle_t) {
+    let handle: Handle<T, StreamData> =
+        unsafe { Handle::from_raw(ptr as *mut _) };
+
+    let data = unsafe { Box::from_raw(handle.get_data()) };
+
+    unsafe { alloc::dealloc(ptr as *mut u8, Layout::new::<T>()) };
+
+    if crate::shutdown::is_shut_down() {
+        return;
+    }
+
+    if let Some(callback) = data.on_close {
+        callback();
+    }
+}
@@ -0,0 +1,177 @@
+use std::ffi::CString;
+use std::mem::MaybeUninit;
+use std::net::SocketAddr;
+
+use libuv_sys2::{
+    self as ffi,
+    sockaddr,
+    sockaddr_in,
+    sockaddr_in6,
+    uv_connect_t,
+    uv_tcp_t,
+};
+
+use crate::stream::{self, Stream, StreamData};
+use crate::{Error, Handle};
+
+type ConnectCallback = Box<dyn FnOnce(Result<TcpStream, Error>) + 'static>;
+
+/// Binding to libuv's [TCP handle][1] which hasn't been connected yet.
+///
+/// [1]: http://docs.libuv.org/en/v1.x/tcp.html
+pub struct TcpHandle {
+    handle: Handle<uv_tcp_t, StreamData>,
+}
+
+impl TcpHandle {
+    /// Creates a new TCP handle.
+    pub fn new() -> Result<Self, Error> {
+        let mut handle = Handle::new(|uv_loop, handle| unsafe {
+            ffi::uv_tcp_init(uv_loop, handle.as_mut_ptr())
+        })?;
+
+        unsafe { handle.set_data(StreamData::default()) };
+
+        Ok(Self { handle })
+    }
+
+    /// Connects the handle to `addr`, calling `callback` with the connected
+    /// stream once the connection has been established. The callback is
+    /// called on the main thread.
+    pub fn connect<Cb>(
+        self,
+        addr: SocketAddr,
+        callback: Cb,
+    ) -> Result<(), Error>
+    where
+        Cb: FnOnce(Result<TcpStream, Error>) + 'static,
+    {
+        // From now on the handle is owned by the connect request, or by the
+        // stream it results in.
+        let tcp = self.handle.as_ptr() as *mut uv_tcp_t;
+        std::mem::forget(self);
+
+        let req = stream::alloc_req::<uv_connect_t, ConnectCallback>(
+            Box::new(callback),
+        );
+
+        let retv = with_sockaddr(addr, |addr| unsafe {
+            ffi::uv_tcp_connect(req, tcp, addr, Some(connect_cb as _))
+        });
+
+        if retv < 0 {
+            unsafe {
+                drop(stream::free_req::<_, ConnectCallback>(req));
+                stream::close(tcp);
+            }
+            return Err(Error::Uv(retv));
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for TcpHandle {
+    fn drop(&mut self) {
+        unsafe { stream::close(self.handle.as_mut_ptr()) };
+    }
+}
+
+/// A connected [`TcpHandle`].
+///
+/// Cloning a stream doesn't open a new connection, it just returns another
+/// reference to the same one. Once a clone has been
+/// [`close`](TcpStream::close)d the methods of all the others fail with
+/// [`Error::StreamClosed`], and the connection is closed automatically when
+/// the last clone is dropped.
+#[derive(Clone)]
+pub struct TcpStream {
+    stream: Stream<uv_tcp_t>,
+}
+
+impl TcpStream {
+    /// Starts reading from the stream, calling `callback` with the bytes
+    /// read every time new data is available. The callback is called with an
+    /// empty buffer once the other end has closed the connection.
+    ///
+    /// Calling this again replaces the previous callback.
+    pub fn read_start<Cb>(&mut self, callback: Cb) -> Result<(), Error>
+    where
+        Cb: FnMut(Result<Vec<u8>, Error>) + 'static,
+    {
+        self.stream.read_start(callback)
+    }
+
+    /// Stops reading from the stream. Does nothing if the stream has been
+    /// closed.
+    pub fn read_stop(&mut self) {
+        self.stream.read_stop()
+    }
+
+    /// Writes `data` to the stream, calling `callback` with the number of
+    /// bytes written once the write has completed. The callback is called on
+    /// the main thread.
+    pub fn write<Cb>(
+        &mut self,
+        data: Vec<u8>,
+        callback: Cb,
+    ) -> Result<(), Error>
+    where
+        Cb: FnOnce(Result<usize, Error>) + 'static,
+    {
+        self.stream.write(data, callback)
+    }
+
+    /// Closes the stream, calling `callback` once it's been closed. Does
+    /// nothing if the stream has already been closed.
+    pub fn close<Cb>(self, callback: Cb)
+    where
+        Cb: FnOnce() + 'static,
+    {
+        self.stream.close(callback)
+    }
+}
+
+/// Converts `addr` into a `sockaddr` and passes it to `fun`.
+fn with_sockaddr<F, R>(addr: SocketAddr, fun: F) -> R
+where
+    F: FnOnce(*const sockaddr) -> R,
+{
+    // The string representation of an IP address never contains nul bytes.
+    let ip = CString::new(addr.ip().to_string()).unwrap();
+    let port = addr.port() as _;
+
+    match addr {
+        SocketAddr::V4(_) => {
+            let mut sa = MaybeUninit::<sockaddr_in>::uninit();
+            unsafe { ffi::uv_ip4_addr(ip.as_ptr(), port, sa.as_mut_ptr()) };
+            fun(sa.as_ptr() as *const sockaddr)
+        },
+
+        SocketAddr::V6(_) => {
+            let mut sa = MaybeUninit::<sockaddr_in6>::uninit();
+            unsafe { ffi::uv_ip6_addr(ip.as_ptr(), port, sa.as_mut_ptr()) };
+            fun(sa.as_ptr() as *const sockaddr)
+        },
+    }
+}
+
+extern "C" fn connect_cb(req: *mut uv_connect_t, status: i32) {
+    let tcp = unsafe { (*req).handle as *mut uv_tcp_t };
+    let callback = unsafe { stream::free_req::<_, ConnectCallback>(req) };
+
+    if status < 0 {
+        unsafe { stream::close(tcp) };
+    }
+
+    if crate::shutdown::is_shut_down() {
+        return;
+    }
+
+    if status < 0 {
+        callback(Err(Error::Uv(status)));
+    } else {
+        let stream = unsafe { Stream::from_raw(tcp) };
+        callback(Ok(TcpStream { stream }));
+    }
+}
@@ -1,7 +1,8 @@
+use std::alloc::{self, Layout};
 use std::error::Error as StdError;
 use std::time::Duration;
 
-use libuv_sys2::{self as ffi, uv_timer_t};
+use libuv_sys2::{self as ffi, uv_handle_t, uv_timer_t};
 
 use crate::{Error, Handle};
 
@@ -12,6 +13,9 @@ pub(crate) type Callback = Box<
 /// Binding to libuv's [Timer handle][1] used to schedule callbacks to be
 /// called in the future.
 ///
+/// Dropping a `TimerHandle` doesn't stop it. Its memory is only freed once
+/// it's been [closed](TimerHandle::close).
+///
 /// [1]: http://docs.libuv.org/en/v1.x/timer.html
 pub struct TimerHandle {
     handle: Handle<uv_timer_t, Callback>,
@@ -23,6 +27,8 @@ impl TimerHandle {
             ffi::uv_timer_init(uv_loop, handle.as_mut_ptr())
         })?;
 
+        crate::shutdown::track_timer(handle.as_ptr() as *mut _);
+
         Ok(Self { handle })
     }
 
@@ -58,6 +64,7 @@ impl TimerHandle {
         };
 
         if retv < 0 {
+            timer.close();
             return Err(Error::TimerStart);
         }
 
@@ -80,6 +87,45 @@ impl TimerHandle {
         })
     }
 
+    /// Stops the timer and restarts it using the repeat interval as the
+    /// timeout. Fails if the timer has never been started.
+    ///
+    /// If the repeat interval is zero the timer is just stopped.
+    pub fn again(&mut self) -> Result<(), Error> {
+        let retv = unsafe { ffi::uv_timer_again(self.handle.as_mut_ptr()) };
+
+        if retv < 0 {
+            return Err(Error::TimerAgain);
+        }
+
+        Ok(())
+    }
+
+    /// Sets the interval the callback is repeated at. If the timer is
+    /// running the new interval is used from the next time it fires, it
+    /// doesn't have to be restarted.
+    pub fn set_repeat(&mut self, repeat: Duration) {
+        unsafe {
+            ffi::uv_timer_set_repeat(
+                self.handle.as_mut_ptr(),
+                repeat.as_millis() as u64,
+            )
+        };
+    }
+
+    /// Returns the interval the callback is repeated at.
+    pub fn get_repeat(&self) -> Duration {
+        let ms = unsafe { ffi::uv_timer_get_repeat(self.handle.as_ptr()) };
+        Duration::from_millis(ms)
+    }
+
+    /// Returns how long until the timer fires next, or zero if it's already
+    /// due.
+    pub fn get_due_in(&self) -> Duration {
+        let ms = unsafe { ffi::uv_timer_get_due_in(self.handle.as_ptr()) };
+        Duration::from_millis(ms)
+    }
+
     /// Stops the timer.
     pub fn stop(&mut self) -> Result<(), Error> {
         let retv = unsafe { ffi::uv_timer_stop(self.handle.as_mut_ptr()) };
@@ -90,9 +136,26 @@ impl TimerHandle {
 
         Ok(())
     }
+
+    /// Stops the timer and frees it, together with its callback, once libuv
+    /// is done with it.
+    ///
+    /// The timer can be closed from inside its own callback, which is
+    /// dropped after it returns.
+    pub fn close(mut self) {
+        let ptr = self.handle.as_mut_ptr();
+
+        crate::shutdown::untrack_timer(ptr);
+
+        unsafe { ffi::uv_close(ptr as *mut uv_handle_t, Some(close_cb as _)) };
+    }
 }
 
 extern "C" fn timer_cb(ptr: *mut uv_timer_t) {
+    if crate::shutdown::is_shut_down() {
+        return;
+    }
+
     let handle: Handle<_, Callback> = unsafe { Handle::from_raw(ptr) };
 
     let callback = unsafe { handle.get_data() };
@@ -106,3 +169,13 @@ extern "C" fn timer_cb(ptr: *mut uv_timer_t) {
         }
     }
 }
+
+extern "C" fn close_cb(ptr: *mut uv_handle_t) {
+    let handle: Handle<uv_timer_t, Callback> =
+        unsafe { Handle::from_raw(ptr as *mut _) };
+
+    unsafe {
+        drop(Box::from_raw(handle.get_data()));
+        alloc::dealloc(ptr as *mut u8, Layout::new::<uv_timer_t>());
+    }
+}
@@ -114,6 +114,13 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#lua_pushvalue
     pub fn lua_pushvalue(L: *mut lua_State, index: c_int);
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_rawequal
+    pub fn lua_rawequal(
+        L: *mut lua_State,
+        index1: c_int,
+        index2: c_int,
+    ) -> c_int;
+
     // https://www.lua.org/manual/5.1/manual.html#lua_rawgeti
     pub fn lua_rawgeti(L: *mut lua_State, index: c_int, n: c_int);
 
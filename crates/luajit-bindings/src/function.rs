@@ -1,10 +1,12 @@
+use std::any::Any;
 use std::error::Error;
-use std::ffi::{c_int, CStr};
+use std::ffi::c_int;
 use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 use std::ptr;
 
 use crate::ffi::{self, lua_State};
-use crate::{utils, Poppable, Pushable};
+use crate::{Poppable, Pushable};
 
 /// Stores a function in the Lua registry, returning its ref.
 pub fn store<F, A, R, E>(fun: F) -> c_int
@@ -24,7 +26,7 @@ where
             &**upv
         };
 
-        fun(lstate).unwrap_or_else(|err| utils::handle_error(lstate, &err))
+        crate::pcall_fn(lstate, fun)
     }
 
     unsafe {
@@ -45,6 +47,43 @@ where
     }
 }
 
+/// Calls `fun`, turning both the error it returns and any panic it raises
+/// into a Lua error.
+///
+/// Unwinding from Rust into C is undefined behavior, so this should wrap
+/// the body of every `extern "C"` function called by Lua. The message of a
+/// panic becomes the message of the Lua error.
+pub unsafe fn pcall_fn<F, E>(lstate: *mut lua_State, fun: F) -> c_int
+where
+    F: FnOnce(*mut lua_State) -> Result<c_int, E>,
+    E: Error,
+{
+    let msg = match panic::catch_unwind(AssertUnwindSafe(|| fun(lstate))) {
+        Ok(Ok(nresults)) => return nresults,
+        Ok(Err(err)) => err.to_string(),
+        Err(payload) => panic_message(payload),
+    };
+
+    ffi::lua_pushlstring(lstate, msg.as_ptr() as *const _, msg.len());
+    // `lua_error` never returns, so the message has to be dropped before
+    // calling it.
+    drop(msg);
+    ffi::lua_error(lstate)
+}
+
+/// Extracts the message from the payload of a panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let msg = match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(msg) => (*msg).to_owned(),
+            Err(_) => String::from("Box<dyn Any>"),
+        },
+    };
+
+    format!("Rust code panicked: {msg}")
+}
+
 /// Calls a function previously stored in the Lua registry via [store].
 pub fn call<A, R>(lua_ref: c_int, args: A) -> Result<R, crate::Error>
 where
@@ -60,9 +99,7 @@ where
                 ffi::LUA_OK => R::pop(lstate),
 
                 err_code => {
-                    let msg = CStr::from_ptr(ffi::lua_tostring(lstate, -1))
-                        .to_string_lossy()
-                        .to_string();
+                    let msg = crate::utils::error_message(lstate, -1);
 
                     ffi::lua_pop(lstate, 1);
 
@@ -93,3 +130,17 @@ pub fn remove(lua_ref: c_int) {
         })
     }
 }
+
+/// Returns whether the values referenced by `a` and `b` in the Lua registry
+/// are the same, without invoking any metamethod.
+pub fn ref_eq(a: c_int, b: c_int) -> bool {
+    unsafe {
+        crate::with_state(|lstate| {
+            ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, a);
+            ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, b);
+            let eq = ffi::lua_rawequal(lstate, -1, -2) != 0;
+            ffi::lua_settop(lstate, -3);
+            eq
+        })
+    }
+}
@@ -5,12 +5,15 @@ pub mod function;
 pub mod macros;
 mod poppable;
 mod pushable;
+mod stack_guard;
 mod state;
 pub mod utils;
 
 pub use error::Error;
+pub use function::pcall_fn;
 #[doc(hidden)]
 pub use macros::__print;
 pub use poppable::Poppable;
 pub use pushable::Pushable;
+pub use stack_guard::StackGuard;
 pub use state::{init, with_state};
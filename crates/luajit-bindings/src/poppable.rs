@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::hash::Hash;
 
@@ -137,6 +138,18 @@ impl Poppable for String {
     }
 }
 
+impl Poppable for Cow<'_, str> {
+    unsafe fn pop(state: *mut lua_State) -> Result<Self, Error> {
+        <String as Poppable>::pop(state).map(Cow::Owned)
+    }
+}
+
+impl Poppable for Box<str> {
+    unsafe fn pop(state: *mut lua_State) -> Result<Self, Error> {
+        <String as Poppable>::pop(state).map(String::into_boxed_str)
+    }
+}
+
 impl<T> Poppable for Option<T>
 where
     T: Poppable,
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::ffi::{c_char, c_int};
 
 use crate::ffi::{self, lua_Integer, lua_Number, lua_State};
@@ -125,6 +126,39 @@ impl Pushable for String {
     }
 }
 
+impl Pushable for &str {
+    unsafe fn push(
+        self,
+        lstate: *mut lua_State,
+    ) -> Result<c_int, crate::Error> {
+        ffi::lua_pushlstring(
+            lstate,
+            self.as_ptr() as *const c_char,
+            self.len(),
+        );
+        Ok(1)
+    }
+}
+
+impl Pushable for Cow<'_, str> {
+    unsafe fn push(
+        self,
+        lstate: *mut lua_State,
+    ) -> Result<c_int, crate::Error> {
+        // Lua copies the bytes, so there's no need to own them.
+        self.as_ref().push(lstate)
+    }
+}
+
+impl Pushable for Box<str> {
+    unsafe fn push(
+        self,
+        lstate: *mut lua_State,
+    ) -> Result<c_int, crate::Error> {
+        self.as_ref().push(lstate)
+    }
+}
+
 impl<T> Pushable for Option<T>
 where
     T: Pushable,
@@ -0,0 +1,48 @@
+use std::ffi::c_int;
+use std::mem;
+
+use crate::ffi::{self, lua_State};
+
+/// A guard which records the height of the Lua stack when it's created and
+/// resets the stack to that height when it's dropped, so that the values
+/// pushed while the guard is alive are popped on every exit path.
+///
+/// Values popped below the recorded height are not replaced.
+#[must_use = "the stack is reset as soon as the guard is dropped"]
+pub struct StackGuard {
+    lstate: *mut lua_State,
+    top: c_int,
+}
+
+impl StackGuard {
+    /// Creates a new guard recording the current height of the stack.
+    ///
+    /// NOTE: `lstate` must be a valid Lua state that outlives the guard.
+    #[inline]
+    pub unsafe fn new(lstate: *mut lua_State) -> Self {
+        Self { lstate, top: ffi::lua_gettop(lstate) }
+    }
+
+    /// Returns the height of the stack recorded by the guard.
+    #[inline]
+    pub fn top(&self) -> c_int {
+        self.top
+    }
+
+    /// Consumes the guard without resetting the stack, leaving the values
+    /// pushed since its creation on the stack.
+    #[inline]
+    pub fn leak(self) {
+        mem::forget(self)
+    }
+}
+
+impl Drop for StackGuard {
+    fn drop(&mut self) {
+        unsafe {
+            if ffi::lua_gettop(self.lstate) > self.top {
+                ffi::lua_settop(self.lstate, self.top);
+            }
+        }
+    }
+}
@@ -32,6 +32,22 @@ pub unsafe fn debug_value(
     }
 }
 
+/// Returns the message of the error at a given stack index, e.g. the one
+/// left by a failed `lua_pcall`. Errors which aren't strings or numbers
+/// (like a table passed to `error()`) get the same generic message the Lua
+/// interpreter would print.
+pub unsafe fn error_message(lstate: *mut lua_State, n: c_int) -> String {
+    match ffi::lua_type(lstate, n) {
+        ffi::LUA_TSTRING | ffi::LUA_TNUMBER => {
+            CStr::from_ptr(ffi::lua_tostring(lstate, n))
+                .to_string_lossy()
+                .into_owned()
+        },
+
+        _ => format!("(error object is a {} value)", debug_type(lstate, n)),
+    }
+}
+
 /// Assumes that the value at index `index` is a table and returns whether it's
 /// an array table (as opposed to a dictionary table).
 pub unsafe fn is_table_array(lstate: *mut lua_State, index: c_int) -> bool {
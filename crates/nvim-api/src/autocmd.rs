@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use nvim_types::{
     self as nvim,
     conversion::FromObject,
@@ -12,7 +14,8 @@ use super::types::*;
 use super::LUA_INTERNAL_CALL;
 use crate::choose;
 use crate::iterator::SuperIterator;
-use crate::Result;
+use crate::trait_utils::StringOrAutocmdEvent;
+use crate::{Error, Result};
 
 /// Binding to [`nvim_clear_autocmds`][1].
 ///
@@ -50,23 +53,32 @@ pub fn create_augroup(name: &str, opts: &CreateAugroupOpts) -> Result<u32> {
 
 /// Binding to [`nvim_create_autocmd`][1].
 ///
-/// Creates a new autocommand.
+/// Creates a new autocommand. The `events` can either be given as strings or
+/// as [`AutocmdEvent`]s. The patterns of [`AutocmdEvent::User`] events are
+/// used as the [`patterns`](CreateAutocmdOptsBuilder::patterns) of `opts`,
+/// so they can't be mixed with other events or with patterns set on `opts`.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_create_autocmd()
-pub fn create_autocmd<'a, I>(
-    events: I,
-    opts: &CreateAutocmdOpts,
-) -> Result<u32>
+pub fn create_autocmd<I, E>(events: I, opts: &CreateAutocmdOpts) -> Result<u32>
 where
-    I: IntoIterator<Item = &'a str>,
+    I: IntoIterator<Item = E>,
+    E: StringOrAutocmdEvent,
 {
-    let events = Object::from(Array::from_iter(events));
+    let (events, user_patterns) = split_events(events, &opts.patterns)?;
+
+    let patterns = match user_patterns.len() {
+        0 => None,
+        1 => Some(Object::from(user_patterns.into_iter().next().unwrap())),
+        _ => Some(Array::from_iter(user_patterns).into()),
+    };
+
+    let opts = with_patterns(opts, patterns, |opts| &mut opts.patterns);
     let mut err = nvim::Error::new();
     let id = unsafe {
         nvim_create_autocmd(
             LUA_INTERNAL_CALL,
             events.non_owning(),
-            &opts.into(),
+            &(&*opts).into(),
             &mut err,
         )
     };
@@ -110,14 +122,37 @@ pub fn del_autocmd(id: u32) -> Result<()> {
 /// Binding to [`nvim_exec_autocmds`][1].
 ///
 /// Executes all the autocommands registered on the given `events` that also
-/// match `opts`.
+/// match `opts`. Like in [`create_autocmd`], the patterns of
+/// [`AutocmdEvent::User`] events can't be mixed with other events or with
+/// patterns set on `opts`.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_exec_autocmds()
-pub fn exec_autocmds<'a, I>(events: I, opts: &ExecAutocmdsOpts) -> Result<()>
+pub fn exec_autocmds<I, E>(events: I, opts: &ExecAutocmdsOpts) -> Result<()>
 where
-    I: IntoIterator<Item = &'a str>,
+    I: IntoIterator<Item = E>,
+    E: StringOrAutocmdEvent,
 {
-    let events = Object::from(Array::from_iter(events));
+    let (events, user_patterns) = split_events(events, &opts.patterns)?;
+
+    if user_patterns.is_empty() {
+        return exec_autocmds_inner(&events, opts);
+    }
+
+    // Up to 0.7 `exec_autocmds` only accepts a single pattern as a string.
+    for pattern in user_patterns {
+        let pattern = Some(Object::from(pattern));
+        let opts = with_patterns(opts, pattern, |opts| &mut opts.patterns);
+        exec_autocmds_inner(&events, &opts)?;
+    }
+
+    Ok(())
+}
+
+/// Calls `nvim_exec_autocmds` with the already split `events`.
+fn exec_autocmds_inner(
+    events: &Object,
+    opts: &ExecAutocmdsOpts,
+) -> Result<()> {
     let mut err = nvim::Error::new();
     unsafe { nvim_exec_autocmds(events.non_owning(), &opts.into(), &mut err) };
     choose!(err, ())
@@ -145,3 +180,63 @@ pub fn get_autocmds(
         })
     )
 }
+
+/// Splits `events` into an array of event names and the patterns of their
+/// [`AutocmdEvent::User`] events.
+///
+/// The patterns of `User` events can only be passed through the `pattern`
+/// option, which applies to all the events of a call, so they can't be mixed
+/// with other events or with the patterns of `opts`.
+fn split_events<I, E>(
+    events: I,
+    patterns: &Object,
+) -> Result<(Object, Vec<String>)>
+where
+    I: IntoIterator<Item = E>,
+    E: StringOrAutocmdEvent,
+{
+    let mut names = Array::new();
+    let mut user_patterns = Vec::new();
+    let mut others = false;
+
+    for event in events {
+        match event.to_event() {
+            (_, Some(pattern)) => {
+                if user_patterns.is_empty() {
+                    names.push(Object::from("User"));
+                }
+                user_patterns.push(pattern);
+            },
+
+            (name, None) => {
+                others = true;
+                names.push(name.into());
+            },
+        }
+    }
+
+    if !user_patterns.is_empty() && (others || !patterns.is_nil()) {
+        return Err(Error::custom(
+            "`User` events with a pattern can't be mixed with other events \
+             or patterns",
+        ));
+    }
+
+    Ok((names.into(), user_patterns))
+}
+
+/// Returns `opts` with its patterns replaced by `patterns`, if any.
+fn with_patterns<'a, O: Clone>(
+    opts: &'a O,
+    patterns: Option<Object>,
+    field: impl FnOnce(&mut O) -> &mut Object,
+) -> Cow<'a, O> {
+    match patterns {
+        Some(patterns) => {
+            let mut opts = opts.clone();
+            *field(&mut opts) = patterns;
+            Cow::Owned(opts)
+        },
+        None => Cow::Borrowed(opts),
+    }
+}
@@ -20,7 +20,17 @@ use crate::choose;
 use crate::ffi::buffer::*;
 use crate::iterator::SuperIterator;
 use crate::opts::*;
-use crate::types::{CommandArgs, CommandInfos, KeymapInfos, Mode};
+#[cfg(feature = "unstable")]
+use crate::types::BufStats;
+use crate::types::{
+    ColumnUnit,
+    CommandArgs,
+    CommandInfos,
+    KeymapInfos,
+    Mode,
+    Position,
+    Region,
+};
 use crate::utils;
 use crate::StringOrFunction;
 use crate::LUA_INTERNAL_CALL;
@@ -96,14 +106,17 @@ impl Buffer {
 
     /// Binding to [`nvim_buf_attach`](https://neovim.io/doc/user/api.html#nvim_buf_attach()).
     ///
-    /// Used to register a set of callbacks on specific buffer events.
+    /// Used to register a set of callbacks on specific buffer events. The
+    /// `on_lines` and `on_bytes` callbacks are detached automatically when
+    /// the buffer is deleted via [`Buffer::wipe`].
     pub fn attach(
         &self,
         send_buffer: bool,
         opts: &BufAttachOpts,
     ) -> Result<()> {
         let mut err = nvim::Error::new();
-        let opts = Dictionary::from(opts);
+        let attachment = TrackedAttachment::new(self.0);
+        let opts = attachment.to_dict(opts);
         let has_attached = unsafe {
             nvim_buf_attach(
                 LUA_INTERNAL_CALL,
@@ -116,12 +129,35 @@ impl Buffer {
         choose!(
             err,
             match has_attached {
-                true => Ok(()),
+                true => {
+                    attachment.register();
+                    Ok(())
+                },
                 _ => Err(Error::custom("Attaching to buffer failed")),
             }
         )
     }
 
+    /// Converts the byte offset `byte` on the 0-indexed `line` into the
+    /// number of characters preceding it. If `byte` falls inside a multibyte
+    /// character the index of that character is returned.
+    pub fn byte_to_char_col(&self, line: usize, byte: usize) -> Result<usize> {
+        let text = self.get_line(line)?;
+
+        if byte > text.len() {
+            return Err(Error::custom(format!(
+                "Byte {byte} is out of range on line {line}"
+            )));
+        }
+
+        // Only count the characters that end before `byte`, so that a byte in
+        // the middle of a character maps to that character.
+        Ok(text
+            .char_indices()
+            .filter(|&(idx, ch)| idx + ch.len_utf8() <= byte)
+            .count())
+    }
+
     /// Binding to [`nvim_buf_call`](https://neovim.io/doc/user/api.html#nvim_buf_call()).
     ///
     /// Calls a function with this buffer as the temporary current buffer.
@@ -140,6 +176,25 @@ impl Buffer {
         })
     }
 
+    /// Converts the character offset `col` on the 0-indexed `line` into a
+    /// byte offset. A `col` equal to the number of characters in the line
+    /// maps to the line's byte length.
+    pub fn char_to_byte_col(&self, line: usize, col: usize) -> Result<usize> {
+        let text = self.get_line(line)?;
+
+        let byte = text
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .chain(std::iter::once(text.len()))
+            .nth(col);
+
+        byte.ok_or_else(|| {
+            Error::custom(format!(
+                "Column {col} is out of range on line {line}"
+            ))
+        })
+    }
+
     /// Binding to [`nvim_buf_create_user_command`](https://neovim.io/doc/user/api.html#nvim_buf_create_user_command()).
     ///
     /// Creates a new buffer-local user command.
@@ -243,6 +298,25 @@ impl Buffer {
         choose!(err, ())
     }
 
+    /// Calls `fun` making all the edits it applies to the buffer a single undo
+    /// step.
+    ///
+    /// The undo sequence is broken both before and after calling `fun`, so
+    /// the block is never merged with the changes made before it, and the
+    /// next change made after it (e.g. by the user) starts a new undo step.
+    /// Changes joined via
+    /// [`:undojoin`](https://neovim.io/doc/user/undo.html#:undojoin) inside
+    /// the block stay part of it.
+    pub fn edit_as_undo_block<F, R>(&mut self, fun: F) -> Result<R>
+    where
+        F: FnOnce(&mut Self) -> Result<R>,
+    {
+        self.break_undo_sequence()?;
+        let res = fun(self);
+        self.break_undo_sequence()?;
+        res
+    }
+
     /// Binding to [`nvim_buf_get_changedtick`](https://neovim.io/doc/user/api.html#nvim_buf_get_changedtick()).
     pub fn get_changedtick(&self) -> Result<u32> {
         let mut err = nvim::Error::new();
@@ -353,6 +427,17 @@ impl Buffer {
         choose!(err, Ok(name.into()))
     }
 
+    /// Gets the buffer number, i.e. the number shown by
+    /// [`:ls`](https://neovim.io/doc/user/windows.html#:ls) and returned by
+    /// [`bufnr()`](https://neovim.io/doc/user/builtin.html#bufnr()).
+    pub fn get_number(&self) -> Result<u32> {
+        if !self.is_valid() {
+            let msg = format!("Invalid buffer id: {}", self.0);
+            return Err(Error::custom(msg));
+        }
+        Ok(self.0.try_into().expect("always positive"))
+    }
+
     /// Binding to [`nvim_buf_get_offset`](https://neovim.io/doc/user/api.html#nvim_buf_get_offset()).
     ///
     /// Returns the 0-indexed byte offset of a line.
@@ -378,6 +463,25 @@ impl Buffer {
         choose!(err, Ok(Opt::from_object(obj)?))
     }
 
+    /// Gets the text in `region` using
+    /// [`get_text`](Buffer::get_text), returning one string per line.
+    ///
+    /// Returns an error if the end of the region comes before its start.
+    pub fn get_region(&self, region: Region) -> Result<Vec<String>> {
+        let ((start_row, start_col), (end_row, end_col)) =
+            self.region_to_bytes(region)?;
+
+        Ok(self
+            .get_text(
+                start_row..=end_row,
+                start_col,
+                end_col,
+                &Default::default(),
+            )?
+            .map(|line| line.to_string_lossy().into_owned())
+            .collect())
+    }
+
     /// Binding to [`nvim_buf_get_text`](https://neovim.io/doc/user/api.html#nvim_buf_get_text()).
     ///
     /// Gets a range from the buffer. This differs from `Buffer::get_lines` in
@@ -422,7 +526,8 @@ impl Buffer {
 
     /// Binding to [`nvim_buf_get_var`](https://neovim.io/doc/user/api.html#nvim_buf_get_var()).
     ///
-    /// Gets a buffer-scoped (`b:`) variable.
+    /// Gets a buffer-scoped (`b:`) variable. Fails with
+    /// [`Error::VarNotFound`] if the variable isn't set.
     pub fn get_var<Var>(&self, name: &str) -> Result<Var>
     where
         Var: FromObject,
@@ -431,7 +536,21 @@ impl Buffer {
         let name = nvim::String::from(name);
         let obj =
             unsafe { nvim_buf_get_var(self.0, name.non_owning(), &mut err) };
-        choose!(err, Ok(Var::from_object(obj)?))
+        if err.is_err() {
+            return Err(Error::from_get_var(err, &name, self.is_valid()));
+        }
+        Ok(Var::from_object(obj)?)
+    }
+
+    /// Returns whether the buffer-scoped (`b:`) variable `name` is set.
+    /// Unlike [`get_var`](Self::get_var), this doesn't fail if the variable
+    /// doesn't exist.
+    pub fn has_var(&self, name: &str) -> bool {
+        let mut err = nvim::Error::new();
+        let name = nvim::String::from(name);
+        let _ =
+            unsafe { nvim_buf_get_var(self.0, name.non_owning(), &mut err) };
+        !err.is_err()
     }
 
     /// Binding to [`nvim_buf_is_loaded`](https://neovim.io/doc/user/api.html#nvim_buf_is_loaded()).
@@ -441,6 +560,13 @@ impl Buffer {
         unsafe { nvim_buf_is_loaded(self.0) }
     }
 
+    /// Returns whether the buffer has unsaved changes, i.e. the value of its
+    /// [`'modified'`](https://neovim.io/doc/user/options.html#'modified')
+    /// option.
+    pub fn is_modified(&self) -> Result<bool> {
+        self.get_option("modified")
+    }
+
     /// Binding to [`nvim_buf_is_valid`](https://neovim.io/doc/user/api.html#nvim_buf_is_valid()).
     ///
     /// Checks if a buffer is valid.
@@ -586,6 +712,25 @@ impl Buffer {
         choose!(err, ())
     }
 
+    /// Replaces the text in `region` with `replacement` using
+    /// [`set_text`](Buffer::set_text).
+    ///
+    /// Returns an error if the end of the region comes before its start.
+    pub fn set_region<Line, Lines>(
+        &mut self,
+        region: Region,
+        replacement: Lines,
+    ) -> Result<()>
+    where
+        Lines: IntoIterator<Item = Line>,
+        Line: Into<nvim::String>,
+    {
+        let ((start_row, start_col), (end_row, end_col)) =
+            self.region_to_bytes(region)?;
+
+        self.set_text(start_row..=end_row, start_col, end_col, replacement)
+    }
+
     /// Binding to [`nvim_buf_set_text`](https://neovim.io/doc/user/api.html#nvim_buf_set_text()).
     ///
     /// Sets (replaces) a range in the buffer. Indexing is zero-based, with
@@ -644,4 +789,96 @@ impl Buffer {
         };
         choose!(err, ())
     }
+
+    /// Binding to the internal `nvim__buf_stats` function.
+    ///
+    /// Returns some internal statistics about the buffer, mostly useful to
+    /// debug performance issues. This function is **unstable**: it's not part
+    /// of Neovim's public API and it can change or be removed in any release.
+    /// If the running Neovim instance doesn't provide it an error is returned.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    pub fn stats(&self) -> Result<BufStats> {
+        let exists =
+            crate::call_function::<_, u8>("exists", ("*nvim__buf_stats",))?;
+
+        if exists == 0 {
+            return Err(Error::custom(
+                "`nvim__buf_stats` is unsupported by this version of Neovim",
+            ));
+        }
+
+        crate::call_function("nvim__buf_stats", (self.clone(),))
+    }
+
+    /// Wipes out the buffer via
+    /// [`nvim_buf_delete`](https://neovim.io/doc/user/api.html#nvim_buf_delete()).
+    ///
+    /// Unlike [`Buffer::delete`], the `on_lines` and `on_bytes` callbacks
+    /// registered via [`Buffer::attach`] are detached first, so they're
+    /// guaranteed not to fire on the dead buffer. The `on_detach` callbacks
+    /// are still invoked.
+    pub fn wipe(self, opts: &WipeOpts) -> Result<()> {
+        detach_all(self.0);
+        let mut err = nvim::Error::new();
+        let opts = Dictionary::from(opts);
+        unsafe { nvim_buf_delete(self.0, opts.non_owning(), &mut err) };
+        choose!(err, ())
+    }
+
+    /// Converts the start and end of a [`Region`] into (row, byte column)
+    /// pairs, checking that the end doesn't come before the start.
+    fn region_to_bytes(
+        &self,
+        region: Region,
+    ) -> Result<((usize, usize), (usize, usize))> {
+        let start = (region.start.row, self.byte_col(region.start)?);
+        let end = (region.end.row, self.byte_col(region.end)?);
+
+        if end < start {
+            return Err(Error::custom(format!(
+                "Invalid region: end {end:?} comes before start {start:?}"
+            )));
+        }
+
+        Ok((start, end))
+    }
+
+    /// Closes the current undo block of the buffer, so that the next change
+    /// starts a new one. See `:h undo-break`.
+    fn break_undo_sequence(&self) -> Result<()> {
+        self.call(|()| crate::command("let &g:undolevels = &g:undolevels"))
+    }
+
+    /// Returns the byte offset of the column of `pos` in its line.
+    fn byte_col(&self, pos: Position) -> Result<usize> {
+        match pos.unit {
+            ColumnUnit::Byte => Ok(pos.col),
+            ColumnUnit::Char => self.char_to_byte_col(pos.row, pos.col),
+        }
+    }
+
+    /// Returns the contents of the 0-indexed `line`.
+    fn get_line(&self, line: usize) -> Result<nvim::String> {
+        let mut err = nvim::Error::new();
+        let line = line as Integer;
+        let lines = unsafe {
+            nvim_buf_get_lines(
+                LUA_INTERNAL_CALL,
+                self.0,
+                line,
+                line + 1,
+                true,
+                &mut err,
+            )
+        };
+        choose!(err, {
+            Ok(lines
+                .into_iter()
+                .next()
+                .map(nvim::String::from_object)
+                .transpose()?
+                .unwrap_or_default())
+        })
+    }
 }
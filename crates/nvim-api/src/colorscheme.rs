@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use nvim_types::conversion::FromObject;
+#[cfg(any(feature = "neovim-0-7", feature = "neovim-0-8"))]
+use nvim_types::Array;
+#[cfg(feature = "neovim-nightly")]
+use nvim_types::Dictionary;
+
+use crate::types::HighlightInfos;
+use crate::Result;
+
+/// Returns the definitions of all the highlight groups, keyed by group name.
+///
+/// If `resolve_links` is `true` a linked group gets the attributes of the
+/// group at the end of its chain of links. Otherwise it only has its
+/// [`link`](HighlightInfos::link) field set, which is what's needed to
+/// convert a colorscheme without losing its structure. Colors are always
+/// returned in RGB.
+///
+/// The whole table is read with a single call, so this is cheap even with
+/// thousands of groups.
+///
+/// On Neovim 0.7 and 0.8, whose API doesn't expose links, a linked group
+/// links to the group at the end of its chain instead of the one it
+/// directly links to.
+pub fn export_colorscheme(
+    resolve_links: bool,
+) -> Result<HashMap<String, HighlightInfos>> {
+    get_hl_defs(resolve_links)
+}
+
+/// Reads the definitions of all the highlight groups with
+/// [`nvim_get_hl`](https://neovim.io/doc/user/api.html#nvim_get_hl()).
+#[cfg(feature = "neovim-nightly")]
+fn get_hl_defs(
+    resolve_links: bool,
+) -> Result<HashMap<String, HighlightInfos>> {
+    let opts = Dictionary::from_iter([("link", !resolve_links)]);
+
+    crate::call_function::<_, Dictionary>("nvim_get_hl", (0, opts))?
+        .into_iter()
+        .map(|(name, infos)| {
+            // `nvim_get_hl` uses the short names of the colors.
+            let infos = Dictionary::from_object(infos)?
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key.as_bytes() {
+                        b"fg" => "foreground".into(),
+                        b"bg" => "background".into(),
+                        b"sp" => "special".into(),
+                        _ => key,
+                    };
+                    (key, value)
+                })
+                .collect::<Dictionary>();
+
+            let infos = HighlightInfos::from_object(infos.into())?;
+            Ok((name.to_string_lossy().into_owned(), infos))
+        })
+        .collect()
+}
+
+/// Reads the definitions of all the highlight groups by evaluating a single
+/// expression which calls
+/// [`nvim_get_hl_by_name`](https://neovim.io/doc/user/api.html#nvim_get_hl_by_name())
+/// on every group, together with the name of the group it resolves to.
+#[cfg(any(feature = "neovim-0-7", feature = "neovim-0-8"))]
+fn get_hl_defs(
+    resolve_links: bool,
+) -> Result<HashMap<String, HighlightInfos>> {
+    const DEFS: &str = "map(filter(getcompletion('', 'highlight'), \
+                        'hlexists(v:val)'), {_, name -> [name, \
+                        synIDattr(synIDtrans(hlID(name)), 'name'), \
+                        nvim_get_hl_by_name(name, v:true)]})";
+
+    crate::eval::<Vec<Array>>(DEFS)?
+        .into_iter()
+        .map(|def| {
+            let mut def = def.into_iter();
+            let mut next = || def.next().unwrap_or_default();
+
+            let name = String::from_object(next())?;
+            let resolved = String::from_object(next())?;
+
+            let infos = if resolve_links || resolved == name {
+                HighlightInfos::from_object(next())?
+            } else {
+                HighlightInfos { link: Some(resolved), ..Default::default() }
+            };
+
+            Ok((name, infos))
+        })
+        .collect()
+}
+
+/// Returns the group the highlight group `name` links to, if any.
+pub(crate) fn get_link(name: &str) -> Result<Option<String>> {
+    let output =
+        crate::exec(&format!("highlight {name}"), true)?.unwrap_or_default();
+    Ok(parse_links(&output).remove(name))
+}
+
+/// Parses the links out of the output of `:highlight`.
+fn parse_links(output: &str) -> HashMap<String, String> {
+    let mut links = HashMap::new();
+    let mut group = "";
+
+    for line in output.lines() {
+        // Long definitions continue on the following indented lines.
+        if !line.starts_with(char::is_whitespace) {
+            group = line.split_whitespace().next().unwrap_or_default();
+        }
+
+        if let Some((_, link)) = line.split_once("links to ") {
+            links.insert(group.to_owned(), link.trim().to_owned());
+        }
+    }
+
+    links
+}
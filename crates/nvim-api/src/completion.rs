@@ -0,0 +1,131 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use luajit_bindings::{self as lua, ffi::*};
+use nvim_types::{Array, BufHandle, Function, Integer, Object};
+
+use crate::opts::{OptionScope, OptionValueOpts};
+use crate::types::{CompletionItem, CompletionRequest};
+use crate::{Buffer, Error, Result, Window};
+
+type CompletionFunc =
+    Box<dyn FnMut(CompletionRequest) -> Result<Vec<CompletionItem>>>;
+
+thread_local! {
+    /// The completion functions registered via [`register_completion_func`],
+    /// keyed by the buffer they complete.
+    static COMPLETION_FUNCS: RefCell<HashMap<BufHandle, CompletionFunc>> =
+        RefCell::new(HashMap::new());
+
+    /// The `v:lua` expression calling the function which dispatches to the
+    /// completion function of the current buffer, once it's been created.
+    static EXPR: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Registers `fun` as the
+/// [`completefunc`](https://neovim.io/doc/user/options.html#'completefunc')
+/// of the current buffer, i.e. the function used by
+/// [`i_CTRL-X_CTRL-U`](https://neovim.io/doc/user/insert.html#i_CTRL-X_CTRL-U).
+/// Registering another function for the same buffer replaces it.
+///
+/// Neovim calls completion functions twice: first to find where the text to
+/// complete starts, then to get the matches. The first call is handled
+/// internally by taking the keyword right before the cursor (as defined by
+/// [`iskeyword`](https://neovim.io/doc/user/options.html#'iskeyword')), so
+/// `fun` is only called once per completion.
+///
+/// Returns the `v:lua.{name}` expression Neovim uses to call the function,
+/// which can also be used to set the
+/// [`omnifunc`](https://neovim.io/doc/user/options.html#'omnifunc') of the
+/// buffer. The expression is the same for every buffer.
+pub fn register_completion_func<F>(fun: F) -> Result<String>
+where
+    F: FnMut(CompletionRequest) -> Result<Vec<CompletionItem>> + 'static,
+{
+    let expr = completion_expr();
+
+    COMPLETION_FUNCS.with(|funcs| {
+        funcs.borrow_mut().insert(Buffer::current().0, Box::new(fun))
+    });
+
+    let opts = OptionValueOpts::builder().scope(OptionScope::Local).build();
+    crate::set_option_value("completefunc", expr.as_str(), &opts)?;
+
+    Ok(expr)
+}
+
+/// Returns the `v:lua` expression calling the completion function of the
+/// current buffer, creating the function the first time it's needed.
+fn completion_expr() -> String {
+    if let Some(expr) = EXPR.with(|expr| expr.borrow().clone()) {
+        return expr;
+    }
+
+    let start = Cell::new(0);
+
+    let completefunc =
+        Function::from_fn(move |(findstart, base): (Integer, String)| {
+            if findstart == 1 {
+                start.set(keyword_start()?);
+                return Ok::<_, Error>(Object::from(start.get() as Integer));
+            }
+
+            let buffer = Buffer::current();
+            let (row, _) = Window::current().get_cursor()?;
+
+            let request = CompletionRequest {
+                buffer: buffer.clone(),
+                row,
+                col: start.get(),
+                base,
+            };
+
+            // The function is taken out of the map while it runs so that it
+            // can register completion functions itself.
+            let fun = COMPLETION_FUNCS
+                .with(|funcs| funcs.borrow_mut().remove(&buffer.0));
+
+            let mut fun = match fun {
+                Some(fun) => fun,
+                None => return Ok(Array::new().into()),
+            };
+
+            let items = fun(request);
+
+            COMPLETION_FUNCS.with(|funcs| {
+                funcs.borrow_mut().entry(buffer.0).or_insert(fun);
+            });
+
+            Ok(items?.into_iter().map(Object::from).collect::<Array>().into())
+        });
+
+    // The name is unique to this plugin, so that plugins don't replace each
+    // other's function.
+    let name = format!("nvim_oxi_completefunc_{}", completefunc.lua_ref());
+
+    // `v:lua` can only call functions reachable from the global table.
+    unsafe {
+        lua::with_state(|lstate| {
+            lua_pushlstring(lstate, name.as_ptr() as *const _, name.len());
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, completefunc.lua_ref());
+            lua_rawset(lstate, LUA_GLOBALSINDEX);
+        })
+    };
+
+    let expr = format!("v:lua.{name}");
+    EXPR.with(|cached| *cached.borrow_mut() = Some(expr.clone()));
+    expr
+}
+
+/// Returns the 0-indexed byte column where the keyword right before the
+/// cursor starts, or the cursor's column if there isn't one.
+fn keyword_start() -> Result<usize> {
+    let (_, col) = Window::current().get_cursor()?;
+    let line = crate::get_current_line()?;
+    let before_cursor = line.get(..col).unwrap_or(&line);
+
+    let start =
+        crate::call_function::<_, Integer>("match", (before_cursor, "\\k*$"))?;
+
+    Ok(usize::try_from(start).unwrap_or(col))
+}
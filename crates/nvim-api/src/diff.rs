@@ -0,0 +1,316 @@
+use std::ops::Range;
+
+use crate::opts::DiffOpts;
+use crate::{Buffer, Result};
+
+/// A run of consecutive lines which differ between the old and the new
+/// version of a buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Hunk {
+    /// The lines replaced in the old version.
+    old: Range<usize>,
+
+    /// The lines replacing them in the new version.
+    new: Range<usize>,
+}
+
+/// A single step of the edit script turning the old lines into the new
+/// ones.
+#[derive(Clone, Copy)]
+enum Edit {
+    Keep,
+    Delete,
+    Insert,
+}
+
+impl Buffer {
+    /// Replaces the contents of the buffer with `new_lines`, only touching
+    /// the lines which actually changed.
+    ///
+    /// The changed lines are found with Myers' diff algorithm and every hunk
+    /// is applied separately via [`set_lines`](Buffer::set_lines), so the
+    /// extmarks and the cursors on the unchanged lines stay where they are.
+    /// This is what formatters should use instead of replacing the whole
+    /// buffer. All the hunks are applied as a single undo step.
+    pub fn apply_diff<S>(
+        &mut self,
+        new_lines: &[S],
+        opts: &DiffOpts,
+    ) -> Result<()>
+    where
+        S: AsRef<str>,
+    {
+        let old_lines = self.get_lines(.., true)?.collect::<Vec<_>>();
+        let new_lines =
+            new_lines.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+
+        let hunks = diff(&old_lines, &new_lines, opts.max_edits);
+
+        if hunks.is_empty() {
+            return Ok(());
+        }
+
+        crate::global::with_noautocmd(opts.noautocmd, || {
+            self.edit_as_undo_block(|buffer| {
+                // Going bottom to top the hunks still to be applied don't
+                // get shifted by the ones already applied.
+                hunks.iter().rev().try_for_each(|hunk| {
+                    buffer.set_lines(
+                        hunk.old.clone(),
+                        true,
+                        new_lines[hunk.new.clone()].iter().copied(),
+                    )
+                })
+            })
+        })
+    }
+}
+
+/// Computes the hunks turning `old` into `new`, sorted by position.
+///
+/// If more than `max_edits` lines would have to be inserted or deleted the
+/// lines between the common prefix and suffix are returned as a single hunk.
+fn diff<A, B>(old: &[A], new: &[B], max_edits: usize) -> Vec<Hunk>
+where
+    A: PartialEq<B>,
+{
+    let prefix =
+        old.iter().zip(new).take_while(|(old, new)| *old == *new).count();
+
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| *old == *new)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    if old_mid.is_empty() && new_mid.is_empty() {
+        return Vec::new();
+    }
+
+    let edits = match edit_script(old_mid, new_mid, max_edits) {
+        Some(edits) => edits,
+
+        None => {
+            return vec![Hunk {
+                old: prefix..old.len() - suffix,
+                new: prefix..new.len() - suffix,
+            }]
+        },
+    };
+
+    let mut hunks = Vec::<Hunk>::new();
+    let (mut x, mut y) = (prefix, prefix);
+    let mut in_hunk = false;
+
+    for edit in edits {
+        if let Edit::Keep = edit {
+            in_hunk = false;
+            x += 1;
+            y += 1;
+            continue;
+        }
+
+        if !in_hunk {
+            hunks.push(Hunk { old: x..x, new: y..y });
+            in_hunk = true;
+        }
+
+        let hunk = hunks.last_mut().expect("just pushed");
+
+        if let Edit::Delete = edit {
+            x += 1;
+            hunk.old.end = x;
+        } else {
+            y += 1;
+            hunk.new.end = y;
+        }
+    }
+
+    hunks
+}
+
+/// Returns the shortest edit script turning `old` into `new` using Myers'
+/// algorithm, or `None` if it's longer than `max_edits`.
+fn edit_script<A, B>(
+    old: &[A],
+    new: &[B],
+    max_edits: usize,
+) -> Option<Vec<Edit>>
+where
+    A: PartialEq<B>,
+{
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let limit = (old.len() + new.len()).min(max_edits) as isize;
+
+    // `v[k]` is the furthest `x` reached on diagonal `k = x - y`, offset so
+    // that `k - 1` and `k + 1` are always valid indices.
+    let offset = limit + 1;
+    let mut v = vec![0isize; 2 * limit as usize + 3];
+
+    // The diagonals `-d..=d` of `v` after every step `d`, which are the only
+    // ones the next step reads.
+    let mut trace = Vec::<Vec<isize>>::new();
+    let mut edits_len = None;
+
+    'search: for d in 0..=limit {
+        for k in (-d..=d).step_by(2) {
+            let idx = (k + offset) as usize;
+
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            v[idx] = x;
+
+            if x >= n && y >= m {
+                edits_len = Some(d);
+                break 'search;
+            }
+        }
+
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+    }
+
+    // Walk back from the end, following the path that led to it.
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+
+    for d in (1..=edits_len?).rev() {
+        // The furthest `x` on diagonal `k` after step `d - 1`.
+        let prev = &trace[d as usize - 1];
+        let furthest = |k: isize| prev[(k + d - 1) as usize];
+
+        let k = x - y;
+
+        let prev_k =
+            if k == -d || (k != d && furthest(k - 1) < furthest(k + 1)) {
+                k + 1
+            } else {
+                k - 1
+            };
+        let prev_x = furthest(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            edits.push(Edit::Keep);
+            x -= 1;
+            y -= 1;
+        }
+
+        edits.push(if x == prev_x { Edit::Insert } else { Edit::Delete });
+
+        x = prev_x;
+        y = prev_y;
+    }
+
+    // The lines matched before the first edit.
+    edits.extend((0..x).map(|_| Edit::Keep));
+
+    edits.reverse();
+    Some(edits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(old: Range<usize>, new: Range<usize>) -> Hunk {
+        Hunk { old, new }
+    }
+
+    #[test]
+    fn diff_identical() {
+        assert_eq!(
+            Vec::<Hunk>::new(),
+            diff(&["a", "b"], &["a", "b"], usize::MAX)
+        );
+        assert_eq!(
+            Vec::<Hunk>::new(),
+            diff::<&str, &str>(&[], &[], usize::MAX)
+        );
+    }
+
+    #[test]
+    fn diff_replace_insert_delete() {
+        let old = ["a", "b", "c", "d", "e"];
+
+        let new = ["a", "B", "c", "d", "e"];
+        assert_eq!(vec![hunk(1..2, 1..2)], diff(&old, &new, usize::MAX));
+
+        let new = ["a", "b", "x", "y", "c", "d", "e"];
+        assert_eq!(vec![hunk(2..2, 2..4)], diff(&old, &new, usize::MAX));
+
+        let new = ["b", "c", "e"];
+        assert_eq!(
+            vec![hunk(0..1, 0..0), hunk(3..4, 2..2)],
+            diff(&old, &new, usize::MAX)
+        );
+
+        let new = ["x", "a", "c", "d", "y"];
+        assert_eq!(
+            vec![hunk(0..0, 0..1), hunk(1..2, 2..2), hunk(4..5, 4..5)],
+            diff(&old, &new, usize::MAX)
+        );
+    }
+
+    #[test]
+    fn diff_max_edits() {
+        let old = ["a", "b", "c", "d", "e"];
+        let new = ["a", "x", "c", "y", "e"];
+        assert_eq!(
+            vec![hunk(1..2, 1..2), hunk(3..4, 3..4)],
+            diff(&old, &new, 4)
+        );
+        assert_eq!(vec![hunk(1..4, 1..4)], diff(&old, &new, 3));
+    }
+
+    #[test]
+    fn diff_applies() {
+        // A small linear congruential generator, to get the same "random"
+        // lines on every run.
+        let mut seed = 42u32;
+        let mut lines = |len: usize| {
+            (0..len)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                    (seed >> 16) % 4
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for len in [0, 1, 5, 20, 100] {
+            let old = lines(len);
+            let new = lines(len + 3);
+
+            for max_edits in [usize::MAX, 1000, 10, 0] {
+                let mut applied = old.clone();
+
+                for hunk in diff(&old, &new, max_edits).iter().rev() {
+                    applied.splice(
+                        hunk.old.clone(),
+                        new[hunk.new.clone()].iter().copied(),
+                    );
+                }
+
+                assert_eq!(new, applied, "len: {len}, max: {max_edits}");
+            }
+        }
+    }
+
+    #[test]
+    fn diff_opts_default_max_edits() {
+        assert_eq!(1000, DiffOpts::default().max_edits);
+    }
+}
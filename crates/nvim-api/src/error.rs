@@ -20,10 +20,31 @@ pub enum Error {
 
     #[error("{0}")]
     Other(String),
+
+    /// Returned when getting a variable that isn't set.
+    #[error("Variable not found: {0}")]
+    VarNotFound(String),
 }
 
 impl Error {
     pub(crate) fn custom<M: Display>(msg: M) -> Self {
         Self::Other(msg.to_string())
     }
+
+    /// Converts the error set by one of the `nvim_*_get_var` functions.
+    ///
+    /// Those only fail if the buffer, window or tab page the variable is
+    /// scoped to is invalid or if the variable isn't set, so when the scope
+    /// is valid the error is turned into [`Error::VarNotFound`].
+    pub(crate) fn from_get_var(
+        err: nvim_types::Error,
+        name: &nvim_types::String,
+        is_scope_valid: bool,
+    ) -> Self {
+        if is_scope_valid {
+            Self::VarNotFound(name.to_string_lossy().into_owned())
+        } else {
+            Self::Nvim(err)
+        }
+    }
 }
@@ -82,13 +82,17 @@ impl Buffer {
     /// Removes an extmark from the buffer.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_del_extmark()
-    pub fn del_extmark(&mut self, ns_id: u32, extmark_id: u32) -> Result<()> {
+    pub fn del_extmark(
+        &mut self,
+        ns_id: u32,
+        extmark_id: ExtmarkId,
+    ) -> Result<()> {
         let mut err = nvim::Error::new();
         let was_found = unsafe {
             nvim_buf_del_extmark(
                 self.0,
                 ns_id as Integer,
-                extmark_id as Integer,
+                extmark_id.0 as Integer,
                 &mut err,
             )
         };
@@ -97,7 +101,8 @@ impl Buffer {
             match was_found {
                 true => Ok(()),
                 _ => Err(Error::custom(format!(
-                    "No extmark with id {extmark_id} was found"
+                    "No extmark with id {} was found",
+                    extmark_id.0
                 ))),
             }
         )
@@ -114,7 +119,7 @@ impl Buffer {
     pub fn get_extmark_by_id(
         &self,
         ns_id: u32,
-        extmark_id: u32,
+        extmark_id: ExtmarkId,
         opts: &GetExtmarkByIdOpts,
     ) -> Result<(usize, usize, Option<ExtmarkInfos>)> {
         let opts = Dictionary::from(opts);
@@ -123,7 +128,7 @@ impl Buffer {
             nvim_buf_get_extmark_by_id(
                 self.0,
                 ns_id as Integer,
-                extmark_id as Integer,
+                extmark_id.0 as Integer,
                 opts.non_owning(),
                 &mut err,
             )
@@ -131,7 +136,8 @@ impl Buffer {
         choose!(err, {
             if tuple.is_empty() {
                 return Err(Error::custom(format!(
-                    "No extmark with id {extmark_id} was found"
+                    "No extmark with id {} was found",
+                    extmark_id.0
                 )));
             }
 
@@ -162,8 +168,9 @@ impl Buffer {
         start: ExtmarkPosition,
         end: ExtmarkPosition,
         opts: &GetExtmarksOpts,
-    ) -> Result<impl SuperIterator<(u32, usize, usize, Option<ExtmarkInfos>)>>
-    {
+    ) -> Result<
+        impl SuperIterator<(ExtmarkId, usize, usize, Option<ExtmarkInfos>)>,
+    > {
         let opts = Dictionary::from(opts);
         let mut err = nvim::Error::new();
         let extmarks = unsafe {
@@ -198,7 +205,7 @@ impl Buffer {
                         .map(ExtmarkInfos::from_object)
                         .transpose()
                         .unwrap();
-                    (id, row, col, infos)
+                    (ExtmarkId(id), row, col, infos)
                 })
             })
         )
@@ -214,7 +221,7 @@ impl Buffer {
         line: usize,
         col: usize,
         opts: &SetExtmarkOpts,
-    ) -> Result<u32> {
+    ) -> Result<ExtmarkId> {
         let mut err = nvim::Error::new();
         let id = unsafe {
             nvim_buf_set_extmark(
@@ -226,7 +233,70 @@ impl Buffer {
                 &mut err,
             )
         };
-        choose!(err, Ok(id.try_into().expect("always positive")))
+        choose!(err, Ok(ExtmarkId(id.try_into().expect("always positive"))))
+    }
+
+    /// Same as [`Buffer::set_extmark`], but returns an [`ExtmarkGuard`] which
+    /// deletes the extmark when dropped. Useful for transient extmarks that
+    /// should only live for the duration of an operation.
+    pub fn set_extmark_scoped(
+        &mut self,
+        ns_id: u32,
+        line: usize,
+        col: usize,
+        opts: &SetExtmarkOpts,
+    ) -> Result<ExtmarkGuard> {
+        let id = self.set_extmark(ns_id, line, col, opts)?;
+        Ok(ExtmarkGuard { buffer: self.clone(), ns_id, id, forgotten: false })
+    }
+}
+
+/// An extmark which is deleted from its buffer when the guard is dropped.
+///
+/// Returned by [`Buffer::set_extmark_scoped`]. Use
+/// [`forget`](ExtmarkGuard::forget) to keep the extmark around.
+#[derive(Debug)]
+#[must_use = "the extmark is deleted as soon as the guard is dropped"]
+pub struct ExtmarkGuard {
+    buffer: Buffer,
+    ns_id: u32,
+    id: ExtmarkId,
+    forgotten: bool,
+}
+
+impl ExtmarkGuard {
+    /// The buffer the extmark was set in.
+    #[inline(always)]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// The id of the extmark.
+    #[inline(always)]
+    pub fn id(&self) -> ExtmarkId {
+        self.id
+    }
+
+    /// The namespace the extmark was set in.
+    #[inline(always)]
+    pub fn ns_id(&self) -> u32 {
+        self.ns_id
+    }
+
+    /// Consumes the guard without deleting the extmark, returning its id.
+    pub fn forget(mut self) -> ExtmarkId {
+        self.forgotten = true;
+        self.id
+    }
+}
+
+impl Drop for ExtmarkGuard {
+    fn drop(&mut self) {
+        if !self.forgotten {
+            // The extmark could have already been deleted, e.g. if the buffer
+            // was wiped out, in which case there's nothing left to do.
+            let _ = self.buffer.del_extmark(self.ns_id, self.id);
+        }
     }
 }
 
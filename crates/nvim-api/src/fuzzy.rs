@@ -0,0 +1,92 @@
+use nvim_types::{conversion::FromObject, Array, Dictionary, Integer, Object};
+
+use crate::opts::MatchFuzzyOpts;
+use crate::Result;
+
+/// Binding to [`matchfuzzypos()`](https://neovim.io/doc/user/builtin.html#matchfuzzypos()).
+///
+/// Fuzzy matches `pattern` against `items`, returning a `(index,
+/// positions, score)` tuple for every matching item, sorted from the best
+/// match to the worst. `index` is the index of the item in `items`, and
+/// `positions` are the byte offsets of the matched characters in it.
+///
+/// An empty pattern matches all the items, in their original order and with
+/// a score of zero, up to the limit set in `opts`.
+pub fn match_fuzzy<S>(
+    items: &[S],
+    pattern: &str,
+    opts: &MatchFuzzyOpts,
+) -> Result<Vec<(usize, Vec<usize>, i64)>>
+where
+    S: AsRef<str>,
+{
+    if pattern.is_empty() {
+        // Like `matchfuzzypos()`, a limit of zero means no limit.
+        let len = match opts.limit {
+            Some(limit) if limit > 0 => items.len().min(limit as usize),
+            _ => items.len(),
+        };
+        return Ok((0..len).map(|idx| (idx, Vec::new(), 0)).collect());
+    }
+
+    // Wrapping the items in dictionaries is the only way to get their
+    // indices back.
+    let list = items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            Dictionary::from_iter([
+                ("text", Object::from(item.as_ref())),
+                ("idx", Object::from(idx as Integer)),
+            ])
+        })
+        .collect::<Array>();
+
+    let mut dict = vec![("key", Object::from("text"))];
+
+    if let Some(limit) = opts.limit {
+        dict.push(("limit", Object::from(limit)));
+    }
+
+    // `matchfuzzypos()` only checks whether the key is present.
+    if opts.matchseq {
+        dict.push(("matchseq", Object::from(1)));
+    }
+
+    let res = crate::call_function::<_, Array>(
+        "matchfuzzypos",
+        (list, pattern, Dictionary::from_iter(dict)),
+    )?;
+
+    let mut res = res.into_iter();
+    let matched =
+        Vec::<Dictionary>::from_object(res.next().unwrap_or_default())?;
+    let positions =
+        Vec::<Vec<usize>>::from_object(res.next().unwrap_or_default())?;
+    let scores = Vec::<i64>::from_object(res.next().unwrap_or_default())?;
+
+    matched
+        .into_iter()
+        .zip(positions)
+        .zip(scores)
+        .map(|((dict, positions), score)| {
+            let idx = usize::from_object(
+                dict.get(&"idx").cloned().unwrap_or_default(),
+            )?;
+
+            // `matchfuzzypos()` returns character positions.
+            let offsets = items[idx]
+                .as_ref()
+                .char_indices()
+                .map(|(offset, _)| offset)
+                .collect::<Vec<_>>();
+
+            let positions = positions
+                .into_iter()
+                .filter_map(|pos| offsets.get(pos).copied())
+                .collect();
+
+            Ok((idx, positions, score))
+        })
+        .collect()
+}
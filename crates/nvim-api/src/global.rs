@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use nvim_types::{
@@ -7,6 +8,7 @@ use nvim_types::{
     Dictionary,
     Integer,
     Object,
+    ObjectKind,
 };
 
 use crate::choose;
@@ -29,6 +31,28 @@ pub fn chan_send(channel_id: u32, data: &str) -> Result<()> {
     choose!(err, ())
 }
 
+/// Binding to [`confirm()`](https://neovim.io/doc/user/builtin.html#confirm()).
+///
+/// Asks the user to pick one of `choices`, returning the one-based index of
+/// the selected choice, or `0` if the dialog was cancelled (e.g. by pressing
+/// `<Esc>`). A `&` in a choice marks the character after it as the shortcut
+/// key for that choice. `default` is the one-based index of the choice
+/// selected by pressing `<CR>`, or `0` to not have one.
+pub fn confirm(
+    msg: &str,
+    choices: &[&str],
+    default: usize,
+    ty: ConfirmType,
+) -> Result<usize> {
+    let choices = choices.join("\n");
+    let ty = nvim::String::from(ty);
+    let choice = crate::call_function::<_, Integer>(
+        "confirm",
+        (msg, choices, default as Integer, ty),
+    )?;
+    Ok(choice.try_into()?)
+}
+
 /// Binding to [`nvim_create_buf`](https://neovim.io/doc/user/api.html#nvim_create_buf()).
 ///
 /// Creates a new, empty, unnamed buffer.
@@ -64,6 +88,23 @@ where
     choose!(err, ())
 }
 
+/// Returns the word under the cursor of the current window, as returned by
+/// [`expand('<cword>')`](https://neovim.io/doc/user/builtin.html#expand()).
+/// What makes up a word is determined by the buffer's `'iskeyword'` option.
+///
+/// Returns `None` if the cursor is on whitespace or on an empty line.
+pub fn cursor_word() -> Result<Option<String>> {
+    expand_under_cursor("<cword>")
+}
+
+/// Like [`cursor_word`], but returns the
+/// [WORD](https://neovim.io/doc/user/motion.html#WORD) under the cursor,
+/// i.e. the sequence of non-blank characters containing it.
+#[allow(non_snake_case)]
+pub fn cursor_WORD() -> Result<Option<String>> {
+    expand_under_cursor("<cWORD>")
+}
+
 /// Binding to [`nvim_del_current_line`](https://neovim.io/doc/user/api.html#nvim_del_current_line()).
 ///
 /// Deletes the current line.
@@ -161,17 +202,26 @@ where
 ///
 /// Writes a message to the Neovim error buffer. Does not append a newline
 /// (`"\n"`); the message gets buffered and won't be displayed until a linefeed
-/// is written.
-pub fn err_write(str: &str) {
-    unsafe { nvim_err_write(nvim::String::from(str).non_owning()) }
+/// is written, so it can be built incrementally over multiple calls.
+///
+/// The message doesn't have to be valid UTF-8, e.g. a `Vec<u8>` can be
+/// written as is.
+pub fn err_write<Msg>(str: Msg)
+where
+    Msg: Into<nvim::String>,
+{
+    unsafe { nvim_err_write(str.into().non_owning()) }
 }
 
 /// Binding to [`nvim_err_writeln`](https://neovim.io/doc/user/api.html#nvim_err_writeln()).
 ///
 /// Writes a message to the Neovim error buffer. Appends a newline (`"\n"`), so
 /// the buffer is flushed and displayed.
-pub fn err_writeln(str: &str) {
-    unsafe { nvim_err_writeln(nvim::String::from(str).non_owning()) }
+pub fn err_writeln<Msg>(str: Msg)
+where
+    Msg: Into<nvim::String>,
+{
+    unsafe { nvim_err_writeln(str.into().non_owning()) }
 }
 
 /// Binding to [`nvim_eval_statusline`](https://neovim.io/doc/user/api.html#nvim_eval_statusline()).
@@ -196,22 +246,73 @@ pub fn feedkeys(keys: &str, mode: Mode, escape_ks: bool) {
     unsafe { nvim_feedkeys(keys.non_owning(), mode.non_owning(), escape_ks) }
 }
 
+/// Same as [`feedkeys`], but makes sure the editor goes back to the mode it
+/// was in before the call once the keys have been processed.
+///
+/// This is done by appending `<C-\><C-n>` to `keys` to get back to Normal
+/// mode, followed by `gi` if the editor was in Insert mode and by `gv` if it
+/// was in Visual or Select mode.
+pub fn feedkeys_restore_mode(
+    keys: &str,
+    mode: Mode,
+    escape_ks: bool,
+) -> Result<()> {
+    let restore = match get_mode()?.mode {
+        Mode::Insert => "<C-\\><C-n>gi",
+        Mode::Visual | Mode::VisualSelect => "<C-\\><C-n>gv",
+        Mode::Select => "<C-\\><C-n>gv<C-g>",
+        _ => "<C-\\><C-n>",
+    };
+
+    feedkeys(keys, mode, escape_ks);
+
+    // The restoring keys have to be executed right away if `keys` were
+    // (i.e. if `mode` contains the `x` flag), or queued after them otherwise.
+    // They're never remapped, or user mappings of `gi` and `gv` would apply.
+    let restore_mode = nvim::String::from(match mode {
+        Mode::Visual => "nx",
+        _ => "n",
+    });
+
+    // The termcodes are already replaced so there's nothing to escape.
+    let restore = replace_termcodes(restore, true, false, true);
+    unsafe {
+        nvim_feedkeys(restore.non_owning(), restore_mode.non_owning(), false)
+    };
+
+    Ok(())
+}
+
 /// Binding to [`nvim_get_all_options_info`](https://neovim.io/doc/user/api.html#nvim_get_all_options_info()).
 ///
-/// Gets the option information for all options.
-pub fn get_all_options_info() -> Result<impl SuperIterator<OptionInfos>> {
+/// Gets the option information for all the global, window-local and
+/// buffer-local options, keyed by the options' full names. The kind of each
+/// option is given by its [`scope`](OptionInfos::scope).
+pub fn get_all_options_info() -> Result<HashMap<String, OptionInfos>> {
     let mut err = nvim::Error::new();
     let infos = unsafe { nvim_get_all_options_info(&mut err) };
     choose!(
         err,
-        Ok({
-            infos
-                .into_iter()
-                .map(|(_, optinf)| OptionInfos::from_object(optinf).unwrap())
-        })
+        infos
+            .into_iter()
+            .map(|(name, optinf)| {
+                let name = name.to_string_lossy().into_owned();
+                Ok((name, OptionInfos::from_object(optinf)?))
+            })
+            .collect()
     )
 }
 
+/// Binding to [`nvim_get_api_info`](https://neovim.io/doc/user/api.html#nvim_get_api_info()).
+///
+/// Returns the API metadata of the running Neovim instance, including its
+/// version and the list of exposed functions.
+pub fn get_api_info() -> Result<ApiInfos> {
+    // `nvim_get_api_info` is only meant to be called over RPC, so we go
+    // through the equivalent `api_info()` Vimscript function instead.
+    crate::call_function("api_info", Array::new())
+}
+
 /// Binding to [`nvim_get_chan_info`](https://neovim.io/doc/user/api.html#nvim_get_chan_info()).
 ///
 /// Gets information about a channel.
@@ -302,6 +403,75 @@ pub fn get_current_win() -> Window {
     unsafe { nvim_get_current_win() }.into()
 }
 
+/// Gets the value of the option `name` which is in effect in `window` for
+/// window-local options, in `buffer` for buffer-local options, or the global
+/// value for global options.
+///
+/// Like Neovim does, the local value of a
+/// [global-local](https://neovim.io/doc/user/options.html#global-local)
+/// option is only used if it's been set, and the global value is returned
+/// otherwise.
+pub fn get_effective_option<Opt>(
+    name: &str,
+    buffer: &Buffer,
+    window: &Window,
+) -> Result<Opt>
+where
+    Opt: FromObject,
+{
+    let get = {
+        let name = name.to_owned();
+        move |()| get_option_value::<Object>(&name, &Default::default())
+    };
+
+    // Evaluating the option with the buffer or window as the current one
+    // lets Neovim resolve the local and global values itself.
+    let obj = match get_option_info(name)?.scope {
+        crate::types::OptionScope::Buffer => buffer.call(get)?,
+        crate::types::OptionScope::Window => window.call(get)?,
+        crate::types::OptionScope::Global => get(())?,
+    };
+
+    Ok(Opt::from_object(obj)?)
+}
+
+/// Returns the definition of the highlight group `name` in the namespace
+/// `ns_id`, with both its RGB and its terminal colors. If the group links to
+/// another one only its [`link`](HlAttrs::link) is set, like
+/// [`nvim_get_hl`](https://neovim.io/doc/user/api.html#nvim_get_hl()) does.
+///
+/// The API of the supported Neovim versions only exposes the highlights of
+/// the global namespace, so passing an `ns_id` other than `0` is an error.
+pub fn get_hl(ns_id: u32, name: &str) -> Result<HlAttrs> {
+    if ns_id != 0 {
+        return Err(Error::custom(
+            "only the highlights of the global namespace can be read",
+        ));
+    }
+
+    let nvim_name = nvim::String::from(name);
+    let mut err = nvim::Error::new();
+
+    let rgb =
+        unsafe { nvim_get_hl_by_name(nvim_name.non_owning(), true, &mut err) };
+    if err.is_err() {
+        return Err(err.into());
+    }
+
+    if let Some(link) = crate::colorscheme::get_link(name)? {
+        return Ok(HlAttrs { link: Some(link), ..Default::default() });
+    }
+
+    let cterm = unsafe {
+        nvim_get_hl_by_name(nvim_name.non_owning(), false, &mut err)
+    };
+    if err.is_err() {
+        return Err(err.into());
+    }
+
+    HlAttrs::from_dicts(rgb, cterm)
+}
+
 /// Binding to [`nvim_get_hl_by_id`](https://neovim.io/doc/user/api.html#nvim_get_hl_by_id()).
 ///
 /// Gets a highlight definition by id.
@@ -460,7 +630,8 @@ pub fn get_runtime_file(
 
 /// Binding to [`nvim_get_var`](https://neovim.io/doc/user/api.html#nvim_get_var()).
 ///
-/// Gets a global (`g:`) variable.
+/// Gets a global (`g:`) variable. Fails with [`Error::VarNotFound`] if the
+/// variable isn't set.
 pub fn get_var<Var>(name: &str) -> Result<Var>
 where
     Var: FromObject,
@@ -468,7 +639,10 @@ where
     let mut err = nvim::Error::new();
     let name = nvim::String::from(name);
     let obj = unsafe { nvim_get_var(name.non_owning(), &mut err) };
-    choose!(err, Ok(Var::from_object(obj)?))
+    if err.is_err() {
+        return Err(Error::from_get_var(err, &name, true));
+    }
+    Ok(Var::from_object(obj)?)
 }
 
 /// Binding to [`nvim_get_vvar`](https://neovim.io/doc/user/api.html#nvim_get_vvar()).
@@ -484,6 +658,27 @@ where
     choose!(err, Ok(Var::from_object(obj)?))
 }
 
+/// Returns `true` if the running Neovim instance is at least
+/// `min_version`, which can be used to feature-detect newer APIs at runtime.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{self, types::NeovimVersion};
+///
+/// if api::has_api(NeovimVersion::new(0, 8, 0)) {
+///     // Safe to use APIs introduced in Neovim 0.8.
+/// }
+/// ```
+pub fn has_api<V>(min_version: V) -> bool
+where
+    V: Into<NeovimVersion>,
+{
+    crate::eval::<NeovimVersion>("api_info().version")
+        .map(|version| version >= min_version.into())
+        .unwrap_or(false)
+}
+
 /// Binding to [`nvim_input`](https://neovim.io/doc/user/api.html#nvim_input()).
 ///
 /// Queues raw user-input. Unlike [`api::feedkeys`](feedkeys) this uses a
@@ -601,6 +796,27 @@ pub fn load_context(ctx: EditorContext) {
     let _ = unsafe { nvim_load_context(ctx.non_owning()) };
 }
 
+/// Returns an iterator over the loaded buffers with unsaved changes.
+///
+/// Only buffers backed by a file are considered, i.e. terminal buffers and
+/// buffers with a special
+/// [`'buftype'`](https://neovim.io/doc/user/options.html#'buftype') (except
+/// for `acwrite`) are never returned, even if their `'modified'` option is
+/// set.
+pub fn modified_buffers() -> impl SuperIterator<Buffer> {
+    list_bufs()
+        .filter(|buf| buf.is_loaded())
+        .filter(|buf| {
+            matches!(
+                buf.get_option::<String>("buftype").as_deref(),
+                Ok("" | "acwrite")
+            )
+        })
+        .filter(|buf| buf.is_modified().unwrap_or(false))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
 /// Binding to [`nvim_notify`](https://neovim.io/doc/user/api.html#nvim_notify()).
 pub fn notify(
     msg: &str,
@@ -643,7 +859,11 @@ pub fn open_term(buffer: &Buffer, opts: &OpenTermOpts) -> Result<u32> {
 /// Binding to [`nvim_out_write`](https://neovim.io/doc/user/api.html#nvim_out_write()).
 ///
 /// Writes a message to the Vim output buffer, without appending a "\n". The
-/// message is buffered and won't be displayed until a linefeed is written.
+/// message is buffered and won't be displayed until a linefeed is written, so
+/// it can be built incrementally over multiple calls.
+///
+/// The message doesn't have to be valid UTF-8, e.g. a `Vec<u8>` can be
+/// written as is.
 pub fn out_write<Msg>(str: Msg)
 where
     Msg: Into<nvim::String>,
@@ -666,6 +886,45 @@ where
     choose!(err, Ok(go_on))
 }
 
+/// Returns the left-hand side of the [`<Plug>`][1] mapping called `name`,
+/// i.e. `<Plug>(name)`.
+///
+/// Characters with a special meaning in a mapping's left-hand side (`<`,
+/// `|`, `\` and whitespace) are escaped using their key notation, so any
+/// string can be used as a name. See also [`set_plug_keymap`].
+///
+/// [1]: https://neovim.io/doc/user/map.html#%3CPlug%3E
+pub fn plug_map(name: &str) -> String {
+    let mut lhs = String::from("<Plug>(");
+
+    for ch in name.chars() {
+        match ch {
+            '<' => lhs.push_str("<lt>"),
+            '|' => lhs.push_str("<Bar>"),
+            '\\' => lhs.push_str("<Bslash>"),
+            ' ' => lhs.push_str("<Space>"),
+            '\t' => lhs.push_str("<Tab>"),
+            '\n' => lhs.push_str("<NL>"),
+            '\r' => lhs.push_str("<CR>"),
+            other => lhs.push(other),
+        }
+    }
+
+    lhs.push(')');
+    lhs
+}
+
+/// Replays the macro stored in `register` `count` times, like typing
+/// `{count}@{register}` in Normal mode. The keys are executed right away,
+/// before the function returns.
+pub fn play_macro(register: char, count: usize) -> Result<()> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    crate::command(&format!("normal! {count}@{register}"))
+}
+
 /// Binding to [`nvim_put`](https://neovim.io/doc/user/api.html#nvim_put()).
 ///
 /// Puts text at cursor, in any mode.
@@ -745,6 +1004,14 @@ pub fn set_current_buf(buf: &Buffer) -> Result<()> {
     choose!(err, ())
 }
 
+/// Like [`set_current_buf`], but lets you switch buffer without triggering
+/// any autocommand. The previous value of
+/// [`'eventignore'`](https://neovim.io/doc/user/options.html#'eventignore')
+/// is restored before returning.
+pub fn set_current_buf_with(buf: &Buffer, opts: &SetBufOpts) -> Result<()> {
+    with_noautocmd(opts.noautocmd, || set_current_buf(buf))
+}
+
 /// Binding to [`nvim_set_current_dir`](https://neovim.io/doc/user/api.html#nvim_set_current_dir()).
 ///
 /// Changes the global working directory.
@@ -788,6 +1055,14 @@ pub fn set_current_win(win: &Window) -> Result<()> {
     choose!(err, ())
 }
 
+/// Like [`set_current_win`], but lets you switch window without triggering
+/// any autocommand. The previous value of
+/// [`'eventignore'`](https://neovim.io/doc/user/options.html#'eventignore')
+/// is restored before returning.
+pub fn set_current_win_with(win: &Window, opts: &SetBufOpts) -> Result<()> {
+    with_noautocmd(opts.noautocmd, || set_current_win(win))
+}
+
 /// Binding to [`nvim_set_hl`](https://neovim.io/doc/user/api.html#nvim_set_hl()).
 ///
 /// Sets a highlight group.
@@ -829,6 +1104,27 @@ pub fn set_keymap(
     choose!(err, ())
 }
 
+/// Stores a macro in `register`, so that it can be replayed via
+/// [`play_macro`] or by typing `@{register}`.
+///
+/// Special keys in `keys` are written in the same notation used by
+/// mappings, e.g. `"Afoo<Esc>j"`, and are converted to their internal
+/// representation via [`replace_termcodes`].
+pub fn set_macro(register: char, keys: &str) -> Result<()> {
+    let keys = replace_termcodes(keys, true, true, true);
+
+    let res = crate::call_function::<_, Integer>(
+        "setreg",
+        (register.to_string(), keys, "c"),
+    )?;
+
+    if res != 0 {
+        return Err(Error::custom(format!("Invalid register {register:?}")));
+    }
+
+    Ok(())
+}
+
 /// Binding to [`nvim_set_option`](https://neovim.io/doc/user/api.html#nvim_set_option()).
 ///
 /// Sets the global value of an option.
@@ -877,6 +1173,36 @@ where
     choose!(err, ())
 }
 
+/// Sets a global `<Plug>` mapping whose left-hand side is
+/// [`plug_map(name)`](plug_map) for the given mode.
+///
+/// `rhs` can either be a string, used as the mapping's right-hand side, or
+/// a function, which is set as the mapping's callback.
+pub fn set_plug_keymap<Rhs>(
+    mode: Mode,
+    name: &str,
+    rhs: Rhs,
+    opts: &SetKeymapOpts,
+) -> Result<()>
+where
+    Rhs: StringOrFunction<(), ()>,
+{
+    let lhs = plug_map(name);
+    let rhs = rhs.to_object();
+
+    match rhs.kind() {
+        ObjectKind::String => {
+            set_keymap(mode, &lhs, &String::from_object(rhs)?, opts)
+        },
+
+        _ => {
+            let mut opts = opts.clone();
+            opts.callback = rhs;
+            set_keymap(mode, &lhs, "", &opts)
+        },
+    }
+}
+
 /// Binding to [`nvim_set_var`](https://neovim.io/doc/user/api.html#nvim_set_var()).
 ///
 /// Sets a global (`g:`) variable.
@@ -915,3 +1241,117 @@ pub fn strwidth(text: &str) -> Result<usize> {
     let width = unsafe { nvim_strwidth(text.non_owning(), &mut err) };
     choose!(err, Ok(width.try_into().expect("always positive")))
 }
+
+/// Changes the working directory to `dir`, calls `fun` and then restores the
+/// previous working directory, even if `fun` panics.
+///
+/// The directory is changed in the same scope the current working directory
+/// was set in, i.e. with [`:lcd`][1] if the current window has a local
+/// directory, [`:tcd`][2] if the current tabpage does and [`:cd`][3]
+/// otherwise. Returns an error without calling `fun` if `dir` is not an
+/// existing directory.
+///
+/// [1]: https://neovim.io/doc/user/editing.html#:lcd
+/// [2]: https://neovim.io/doc/user/editing.html#:tcd
+/// [3]: https://neovim.io/doc/user/editing.html#:cd
+pub fn with_cwd<Dir, F, R>(dir: Dir, fun: F) -> Result<R>
+where
+    Dir: AsRef<Path>,
+    F: FnOnce() -> R,
+{
+    let dir = dir.as_ref();
+
+    if !dir.is_dir() {
+        return Err(Error::custom(format!(
+            "{} is not a directory",
+            dir.display()
+        )));
+    }
+
+    let has_local_dir = |args: Array| {
+        crate::call_function::<_, u8>("haslocaldir", args).map(|n| n == 1)
+    };
+
+    let cd = if has_local_dir(Array::new())? {
+        "lcd"
+    } else if has_local_dir(Array::from((-1, 0)))? {
+        "tcd"
+    } else {
+        "cd"
+    };
+
+    let previous = crate::call_function::<_, String>("getcwd", Array::new())?;
+
+    change_dir(cd, dir)?;
+
+    /// Restores the previous working directory when dropped.
+    struct Restore<'a> {
+        cd: &'static str,
+        previous: &'a Path,
+    }
+
+    impl Drop for Restore<'_> {
+        fn drop(&mut self) {
+            let _ = change_dir(self.cd, self.previous);
+        }
+    }
+
+    let _restore = Restore { cd, previous: Path::new(&previous) };
+
+    Ok(fun())
+}
+
+/// Changes directory by executing `:{cd} {dir}`.
+fn change_dir(cd: &str, dir: &Path) -> Result<()> {
+    let dir = dir.display().to_string();
+    let escaped =
+        crate::call_function::<_, String>("fnameescape", Array::from((dir,)))?;
+    crate::command(&format!("{cd} {escaped}"))
+}
+
+/// Expands `pattern` (either `<cword>` or `<cWORD>`), returning `None` if
+/// the cursor is not on a non-blank character.
+fn expand_under_cursor(pattern: &str) -> Result<Option<String>> {
+    let (_, col) = Window::current().get_cursor()?;
+    let line = get_current_line()?;
+
+    let on_blank = line
+        .get(col..)
+        .and_then(|rest| rest.chars().next())
+        .map_or(true, char::is_whitespace);
+
+    if on_blank {
+        return Ok(None);
+    }
+
+    let word = crate::call_function::<_, String>("expand", (pattern,))?;
+    Ok((!word.is_empty()).then_some(word))
+}
+
+/// Calls `fun` with all autocommands disabled if `noautocmd` is `true`,
+/// restoring the previous value of `'eventignore'` afterwards.
+pub(crate) fn with_noautocmd<F, R>(noautocmd: bool, fun: F) -> Result<R>
+where
+    F: FnOnce() -> Result<R>,
+{
+    if !noautocmd {
+        return fun();
+    }
+
+    let eventignore = get_option::<String>("eventignore")?;
+    set_option("eventignore", "all")?;
+
+    /// Restores the previous value of `'eventignore'` when dropped, even if
+    /// `fun` panics.
+    struct Restore(String);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let _ = set_option("eventignore", self.0.as_str());
+        }
+    }
+
+    let _restore = Restore(eventignore);
+
+    fun()
+}
@@ -0,0 +1,144 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use nvim_types::{self as nvim, Array, Dictionary, Function, Integer, Object};
+
+use crate::types::JobResult;
+use crate::{Error, Result};
+
+/// A job started via [`Job::start`].
+///
+/// The standard output and standard error of the job are collected while
+/// it runs, and can be retrieved together with its exit code via
+/// [`Job::wait`].
+#[derive(Clone, Debug)]
+pub struct Job {
+    id: u32,
+    output: Rc<RefCell<JobOutput>>,
+}
+
+#[derive(Debug, Default)]
+struct JobOutput {
+    stdout: String,
+    stderr: String,
+    exit_code: Option<i32>,
+}
+
+impl Job {
+    /// Binding to [`jobstart()`](https://neovim.io/doc/user/builtin.html#jobstart()).
+    ///
+    /// Starts a job running `cmd`, where the first item is the executable
+    /// and the rest are its arguments. The command is not run through a
+    /// shell.
+    pub fn start<Cmd, Arg>(cmd: Cmd) -> Result<Self>
+    where
+        Cmd: IntoIterator<Item = Arg>,
+        Arg: Into<nvim::String>,
+    {
+        let output = Rc::new(RefCell::new(JobOutput::default()));
+
+        let on_exit = {
+            let output = Rc::clone(&output);
+            Function::from_fn(move |(_, code, _): (Integer, i32, String)| {
+                output.borrow_mut().exit_code = Some(code);
+                Ok::<_, Error>(())
+            })
+        };
+
+        let opts = Dictionary::from_iter([
+            ("on_stdout", collect(&output, |out| &mut out.stdout)),
+            ("on_stderr", collect(&output, |out| &mut out.stderr)),
+            ("on_exit", on_exit.into()),
+        ]);
+
+        let cmd = cmd.into_iter().map(Into::into).collect::<Array>();
+
+        match crate::call_function::<_, Integer>("jobstart", (cmd, opts))? {
+            0 => {
+                Err(Error::custom("Invalid arguments passed to `jobstart()`"))
+            },
+            -1 => Err(Error::custom("The job's command is not executable")),
+            id => Ok(Self { id: id.try_into()?, output }),
+        }
+    }
+
+    /// Returns the id of the job, which is also its channel id.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Binding to [`jobwait()`](https://neovim.io/doc/user/builtin.html#jobwait()).
+    ///
+    /// Blocks until the job exits or until `timeout` has elapsed, processing
+    /// Neovim's events in the meantime. If the timeout is reached the job is
+    /// left running and the returned [`JobResult`] has no exit code, but it
+    /// still contains all the output collected until then.
+    pub fn wait(&self, timeout: Duration) -> Result<JobResult> {
+        let mut code = self.output.borrow().exit_code;
+
+        if code.is_none() {
+            let timeout =
+                Integer::try_from(timeout.as_millis()).unwrap_or(Integer::MAX);
+
+            let codes = crate::call_function::<_, Vec<Integer>>(
+                "jobwait",
+                (Array::from_iter([self.id as Integer]), timeout),
+            )?;
+
+            match codes.first().copied() {
+                Some(-1) => {},
+                Some(-2) => {
+                    return Err(Error::custom("Waiting for job interrupted"))
+                },
+                Some(-3) => {
+                    return Err(Error::custom(format!(
+                        "Invalid job id: {}",
+                        self.id
+                    )))
+                },
+                Some(exit_code) => code = Some(exit_code.try_into()?),
+                None => {},
+            }
+        }
+
+        let output = self.output.borrow();
+
+        Ok(JobResult {
+            code: output.exit_code.or(code),
+            stdout: output.stdout.clone(),
+            stderr: output.stderr.clone(),
+        })
+    }
+}
+
+/// Returns an `on_stdout` or `on_stderr` callback which appends the data it
+/// receives to the `String` returned by `stream`.
+fn collect<F>(output: &Rc<RefCell<JobOutput>>, stream: F) -> Object
+where
+    F: Fn(&mut JobOutput) -> &mut String + 'static,
+{
+    let output = Rc::clone(output);
+
+    Function::from_fn(move |(_, data, _): (Integer, Vec<String>, String)| {
+        let output = &mut *output.borrow_mut();
+        let buf = stream(output);
+
+        // The first item continues the last line of the previous chunk,
+        // every other item starts a new line. See `:h channel-lines`.
+        let mut data = data.into_iter();
+
+        if let Some(first) = data.next() {
+            buf.push_str(&first);
+        }
+
+        for line in data {
+            buf.push('\n');
+            buf.push_str(&line);
+        }
+
+        Ok::<_, Error>(())
+    })
+    .into()
+}
@@ -13,15 +13,26 @@
 
 mod autocmd;
 mod buffer;
+mod colorscheme;
+mod completion;
+mod diff;
 mod error;
 mod extmark;
 mod ffi;
+mod fuzzy;
 mod global;
 pub(crate) mod iterator;
+mod job;
+mod load_file;
+mod observer;
 pub mod opts;
 pub(crate) mod serde_utils;
+#[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+mod substitute;
 mod tabpage;
+mod task;
 mod trait_utils;
+mod transaction;
 pub mod types;
 pub(crate) mod utils;
 mod vimscript;
@@ -30,12 +41,19 @@ mod window;
 
 pub use autocmd::*;
 pub use buffer::*;
+pub use colorscheme::*;
+pub use completion::*;
 pub use error::Error;
 use error::Result;
 pub use extmark::*;
+pub use fuzzy::*;
 pub use global::*;
+pub use job::*;
+pub use observer::*;
 pub use tabpage::*;
+pub use task::*;
 pub use trait_utils::*;
+pub use transaction::*;
 pub use vimscript::*;
 pub use win_config::*;
 pub use window::*;
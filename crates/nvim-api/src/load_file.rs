@@ -0,0 +1,92 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use nvim_types as nvim;
+
+use crate::opts::LoadFileOpts;
+use crate::Buffer;
+use crate::{Error, Result};
+
+/// The number of bytes searched for a NUL byte to tell whether a file is
+/// binary, the same heuristic used by Git.
+const BINARY_CHECK_LEN: usize = 8000;
+
+impl Buffer {
+    /// Replaces the contents of the buffer with the ones of the file at
+    /// `path`, e.g. to show a preview of it.
+    ///
+    /// Unlike `:edit` the buffer isn't associated with the file, and it's
+    /// not marked as modified by the change. Files containing a NUL byte in
+    /// their first few kilobytes are considered binary, and their contents
+    /// are replaced by a single placeholder line.
+    pub fn load_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        opts: &LoadFileOpts,
+    ) -> Result<()> {
+        let path = path.as_ref();
+
+        let read_err = |err: std::io::Error| {
+            Error::custom(format!("Couldn't read {}: {err}", path.display()))
+        };
+
+        let file = File::open(path).map_err(read_err)?;
+        let limit = opts.max_size.map_or(u64::MAX, |max| max as u64);
+
+        let mut contents = Vec::new();
+        file.take(limit).read_to_end(&mut contents).map_err(read_err)?;
+
+        let is_binary =
+            contents.iter().take(BINARY_CHECK_LEN).any(|&byte| byte == 0);
+
+        if is_binary {
+            self.set_lines(.., false, [opts.binary_placeholder.as_str()])?;
+        } else {
+            // A trailing newline terminates the last line, it doesn't start
+            // a new one.
+            if contents.last() == Some(&b'\n') {
+                contents.pop();
+            }
+
+            let lines = contents
+                .split(|&byte| byte == b'\n')
+                .map(|line| nvim::String::from_bytes(line.to_owned()));
+
+            self.set_lines(.., false, lines)?;
+        }
+
+        self.set_option("modified", false)?;
+
+        if opts.filetype {
+            self.detect_filetype(path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the filetype of the buffer as if it was the file at `path`, by
+    /// running the `filetypedetect` autocommands. Does nothing if filetype
+    /// detection is disabled.
+    fn detect_filetype(&self, path: &Path) -> Result<()> {
+        let enabled = crate::call_function::<_, u8>(
+            "exists",
+            ("#filetypedetect#BufRead",),
+        )?;
+
+        if enabled == 0 {
+            return Ok(());
+        }
+
+        let path = crate::call_function::<_, String>(
+            "fnameescape",
+            (path.to_string_lossy().into_owned(),),
+        )?;
+
+        self.call(move |()| {
+            crate::command(&format!(
+                "silent doautocmd filetypedetect BufRead {path}"
+            ))
+        })
+    }
+}
@@ -0,0 +1,144 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::opts::*;
+use crate::types::{BytesChange, LinesChange};
+use crate::{Buffer, Result};
+
+/// A set of callbacks notified of the changes made to a buffer, registered
+/// via [`Buffer::observe`].
+///
+/// Unlike the separate closures passed to [`Buffer::attach`], all the
+/// methods of an observer share its state, which is convenient for e.g.
+/// incremental parsers. Every method does nothing by default.
+#[allow(unused_variables)]
+pub trait BufferObserver: 'static {
+    /// Called when some lines of the buffer change.
+    fn on_lines(&mut self, change: LinesChange) {}
+
+    /// Called when some bytes of the buffer change. This carries more
+    /// granular information than [`on_lines`](Self::on_lines).
+    fn on_bytes(&mut self, change: BytesChange) {}
+
+    /// Called when `b:changedtick` is incremented without the text
+    /// changing.
+    fn on_changedtick(&mut self, buffer: Buffer, changedtick: u32) {}
+
+    /// Called when the buffer is reloaded. Its entire contents should be
+    /// considered changed.
+    fn on_reload(&mut self, buffer: Buffer) {}
+
+    /// Called when Neovim detaches the observer, e.g. because the buffer
+    /// was unloaded. No other method is called after this one.
+    fn on_detach(&mut self, buffer: Buffer) {}
+}
+
+/// A handle to a [`BufferObserver`] registered via [`Buffer::observe`].
+///
+/// The observer is detached when the handle is dropped: from then on none of
+/// its methods are called, and Neovim releases it on the next change to the
+/// buffer.
+#[must_use = "the observer is detached when the handle is dropped"]
+#[derive(Debug)]
+pub struct ObserverHandle {
+    detached: Rc<Cell<bool>>,
+}
+
+impl ObserverHandle {
+    /// Detaches the observer. Same as dropping the handle.
+    #[inline]
+    pub fn detach(self) {}
+
+    /// Returns whether the observer has been detached by Neovim.
+    #[inline]
+    pub fn is_detached(&self) -> bool {
+        self.detached.get()
+    }
+
+    /// Consumes the handle without detaching the observer, which then stays
+    /// attached until Neovim detaches it.
+    #[inline]
+    pub fn leak(self) {
+        std::mem::forget(self)
+    }
+}
+
+impl Drop for ObserverHandle {
+    fn drop(&mut self) {
+        self.detached.set(true);
+    }
+}
+
+impl Buffer {
+    /// Registers `observer` to be notified of the changes made to the buffer
+    /// via [`nvim_buf_attach`](https://neovim.io/doc/user/api.html#nvim_buf_attach()).
+    ///
+    /// The observer stays attached until the returned handle is dropped.
+    pub fn observe<O>(&self, observer: O) -> Result<ObserverHandle>
+    where
+        O: BufferObserver,
+    {
+        let observer = Rc::new(RefCell::new(observer));
+        let detached = Rc::new(Cell::new(false));
+
+        let on_detach = {
+            let detached = Rc::clone(&detached);
+            move |obs: &mut O, args: OnDetachArgs| {
+                detached.set(true);
+                obs.on_detach(args.1)
+            }
+        };
+
+        let opts = BufAttachOpts::builder()
+            .on_lines(forward(
+                &observer,
+                &detached,
+                |obs, args: OnLinesArgs| obs.on_lines(args.into()),
+            ))
+            .on_bytes(forward(
+                &observer,
+                &detached,
+                |obs, args: OnBytesArgs| obs.on_bytes(args.into()),
+            ))
+            .on_changedtick(forward(
+                &observer,
+                &detached,
+                |obs, args: OnChangedtickArgs| {
+                    obs.on_changedtick(args.1, args.2)
+                },
+            ))
+            .on_reload(forward(
+                &observer,
+                &detached,
+                |obs, args: OnReloadArgs| obs.on_reload(args.1),
+            ))
+            .on_detach(forward(&observer, &detached, on_detach))
+            .build();
+
+        self.attach(false, &opts)?;
+
+        Ok(ObserverHandle { detached })
+    }
+}
+
+/// Returns a callback which forwards its arguments to `fun` together with
+/// the observer, or detaches if the observer has been detached.
+fn forward<O, A, F>(
+    observer: &Rc<RefCell<O>>,
+    detached: &Rc<Cell<bool>>,
+    fun: F,
+) -> impl FnMut(A) -> Result<ShouldDetach> + 'static
+where
+    O: BufferObserver,
+    F: Fn(&mut O, A) + 'static,
+{
+    let observer = Rc::clone(observer);
+    let detached = Rc::clone(detached);
+
+    move |args| {
+        if !detached.get() {
+            fun(&mut observer.borrow_mut(), args);
+        }
+        Ok(detached.get())
+    }
+}
@@ -1,5 +1,16 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
 use derive_builder::Builder;
-use nvim_types::{Dictionary, Object};
+use luajit_bindings::{Poppable, Pushable};
+use nvim_types::{
+    conversion::FromObject,
+    BufHandle,
+    Dictionary,
+    Function,
+    Object,
+};
 
 use crate::Buffer;
 use crate::ToFunction;
@@ -189,3 +200,124 @@ impl From<&BufAttachOpts> for Dictionary {
         ])
     }
 }
+
+thread_local! {
+    /// The detach flags of the attachments made via
+    /// [`Buffer::attach`](crate::Buffer::attach), grouped by buffer.
+    static ATTACHMENTS: RefCell<HashMap<BufHandle, Vec<Rc<Cell<bool>>>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// A set of callbacks whose `on_lines` and `on_bytes` are detached once
+/// [`detach_all`] is called on their buffer.
+pub(crate) struct TrackedAttachment {
+    buffer: BufHandle,
+    detached: Rc<Cell<bool>>,
+}
+
+impl TrackedAttachment {
+    pub(crate) fn new(buffer: BufHandle) -> Self {
+        Self { buffer, detached: Rc::new(Cell::new(false)) }
+    }
+
+    /// Converts the options to a `Dictionary`, wrapping the callbacks so
+    /// that they're aware of this attachment's detach flag.
+    pub(crate) fn to_dict(&self, opts: &BufAttachOpts) -> Dictionary {
+        let mut dict = Dictionary::from(opts);
+
+        if let Some(on_lines) = dict.get_mut(&"on_lines") {
+            *on_lines = self.wrap::<OnLinesArgs>(on_lines);
+        }
+
+        if let Some(on_bytes) = dict.get_mut(&"on_bytes") {
+            *on_bytes = self.wrap::<OnBytesArgs>(on_bytes);
+        }
+
+        if let Some(on_detach) = dict.get_mut(&"on_detach") {
+            *on_detach = self.wrap_on_detach(on_detach);
+        }
+
+        dict
+    }
+
+    /// Starts tracking the attachment. This should only be called once
+    /// Neovim has accepted the callbacks.
+    pub(crate) fn register(self) {
+        ATTACHMENTS.with(|attachments| {
+            attachments
+                .borrow_mut()
+                .entry(self.buffer)
+                .or_default()
+                .push(self.detached)
+        })
+    }
+
+    /// Returns a callback which detaches once the flag is set and forwards
+    /// to `fun` otherwise.
+    fn wrap<A>(&self, fun: &Object) -> Object
+    where
+        A: Poppable + Pushable + 'static,
+    {
+        let fun = match Function::<A, ShouldDetach>::from_object(fun.clone()) {
+            Ok(fun) => fun,
+            Err(_) => return fun.clone(),
+        };
+
+        let detached = Rc::clone(&self.detached);
+
+        Function::from_fn(move |args: A| match detached.get() {
+            true => Ok(true),
+            false => fun.call(args),
+        })
+        .into()
+    }
+
+    /// Returns an `on_detach` callback which stops tracking the attachment
+    /// before forwarding to `fun` (if it's set).
+    fn wrap_on_detach(&self, fun: &Object) -> Object {
+        let fun =
+            Function::<OnDetachArgs, ShouldDetach>::from_object(fun.clone())
+                .ok();
+
+        let buffer = self.buffer;
+        let detached = Rc::clone(&self.detached);
+
+        Function::from_fn(move |args: OnDetachArgs| {
+            forget(buffer, &detached);
+
+            match &fun {
+                Some(fun) => fun.call(args),
+                None => Ok(false),
+            }
+        })
+        .into()
+    }
+}
+
+/// Detaches the `on_lines` and `on_bytes` callbacks of all the tracked
+/// attachments of `buffer`. Attachments that have already been detached are
+/// ignored.
+pub(crate) fn detach_all(buffer: BufHandle) {
+    let flags = ATTACHMENTS
+        .with(|attachments| attachments.borrow_mut().remove(&buffer));
+
+    for flag in flags.into_iter().flatten() {
+        flag.set(true);
+    }
+}
+
+/// Stops tracking an attachment, e.g. because Neovim has already detached
+/// it.
+fn forget(buffer: BufHandle, detached: &Rc<Cell<bool>>) {
+    ATTACHMENTS.with(|attachments| {
+        let mut attachments = attachments.borrow_mut();
+
+        if let Some(flags) = attachments.get_mut(&buffer) {
+            flags.retain(|flag| !Rc::ptr_eq(flag, detached));
+
+            if flags.is_empty() {
+                attachments.remove(&buffer);
+            }
+        }
+    })
+}
@@ -43,7 +43,7 @@ pub struct CreateAutocmdOpts {
 
     /// Patterns to match against.
     #[builder(setter(custom))]
-    patterns: Object,
+    pub(crate) patterns: Object,
 }
 
 impl CreateAutocmdOpts {
@@ -0,0 +1,37 @@
+use derive_builder::Builder;
+
+/// Options passed to [`Buffer::apply_diff`](crate::Buffer::apply_diff).
+#[derive(Clone, Debug, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct DiffOpts {
+    /// Maximum number of lines to insert or delete when computing a minimal
+    /// diff. Past this limit the lines between the first and the last
+    /// change are replaced as a whole, which is much faster for buffers
+    /// that changed almost entirely. Defaults to 1000, use `usize::MAX` to
+    /// always compute a minimal diff.
+    pub(crate) max_edits: usize,
+
+    /// Whether to apply the diff without triggering any autocommand (e.g.
+    /// `TextChanged`), like
+    /// [`:noautocmd`](https://neovim.io/doc/user/autocmd.html#:noautocmd).
+    pub(crate) noautocmd: bool,
+}
+
+impl Default for DiffOpts {
+    fn default() -> Self {
+        Self { max_edits: 1000, noautocmd: false }
+    }
+}
+
+impl DiffOpts {
+    #[inline(always)]
+    pub fn builder() -> DiffOptsBuilder {
+        DiffOptsBuilder::default()
+    }
+}
+
+impl DiffOptsBuilder {
+    pub fn build(&mut self) -> DiffOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
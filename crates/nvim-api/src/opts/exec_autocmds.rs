@@ -28,7 +28,7 @@ pub struct ExecAutocmdsOpts {
     modeline: bool,
 
     #[builder(setter(custom))]
-    patterns: Object,
+    pub(crate) patterns: Object,
 }
 
 impl ExecAutocmdsOpts {
@@ -0,0 +1,45 @@
+use derive_builder::Builder;
+
+/// Options passed to [`Buffer::load_file`](crate::Buffer::load_file).
+#[derive(Clone, Debug, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct LoadFileOpts {
+    /// The maximum number of bytes read from the file. Longer files are
+    /// truncated, possibly in the middle of a line.
+    #[builder(setter(strip_option))]
+    pub(crate) max_size: Option<usize>,
+
+    /// Whether to set the
+    /// [`'filetype'`](https://neovim.io/doc/user/options.html#'filetype')
+    /// of the buffer based on the path of the file, using the user's
+    /// filetype detection rules. Defaults to `false`.
+    pub(crate) filetype: bool,
+
+    /// The line shown instead of the contents of binary files. Defaults to
+    /// `"[binary file]"`.
+    #[builder(setter(into))]
+    pub(crate) binary_placeholder: String,
+}
+
+impl Default for LoadFileOpts {
+    fn default() -> Self {
+        Self {
+            max_size: None,
+            filetype: false,
+            binary_placeholder: String::from("[binary file]"),
+        }
+    }
+}
+
+impl LoadFileOpts {
+    #[inline(always)]
+    pub fn builder() -> LoadFileOptsBuilder {
+        LoadFileOptsBuilder::default()
+    }
+}
+
+impl LoadFileOptsBuilder {
+    pub fn build(&mut self) -> LoadFileOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
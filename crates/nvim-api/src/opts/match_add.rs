@@ -0,0 +1,29 @@
+use derive_builder::Builder;
+
+/// Options passed to [`Window::match_add`](crate::Window::match_add).
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct MatchAddOpts {
+    /// Use a specific id for the match instead of letting Neovim pick one.
+    /// Ids 1, 2 and 3 are reserved for `:match`, `:2match` and `:3match`.
+    #[builder(setter(strip_option))]
+    pub(crate) id: Option<u32>,
+
+    /// Character shown in place of the matched text when the `Conceal`
+    /// group is used and `'conceallevel'` is set.
+    #[builder(setter(strip_option))]
+    pub(crate) conceal: Option<char>,
+}
+
+impl MatchAddOpts {
+    #[inline(always)]
+    pub fn builder() -> MatchAddOptsBuilder {
+        MatchAddOptsBuilder::default()
+    }
+}
+
+impl MatchAddOptsBuilder {
+    pub fn build(&mut self) -> MatchAddOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+
+/// Options passed to [`api::match_fuzzy`](crate::match_fuzzy).
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct MatchFuzzyOpts {
+    /// Maximum number of matches to return.
+    #[builder(setter(strip_option))]
+    pub(crate) limit: Option<u32>,
+
+    /// Only match the characters of the pattern in sequence, instead of
+    /// treating the whitespace-separated words in the pattern as separate
+    /// patterns which can match in any order.
+    pub(crate) matchseq: bool,
+}
+
+impl MatchFuzzyOpts {
+    #[inline(always)]
+    pub fn builder() -> MatchFuzzyOptsBuilder {
+        MatchFuzzyOptsBuilder::default()
+    }
+}
+
+impl MatchFuzzyOptsBuilder {
+    pub fn build(&mut self) -> MatchFuzzyOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
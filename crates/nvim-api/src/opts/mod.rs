@@ -9,6 +9,7 @@ mod create_augroup;
 mod create_autocmd;
 mod create_command;
 mod decoration_provider;
+mod diff;
 mod eval_statusline;
 mod exec_autocmds;
 mod get_autocmds;
@@ -19,14 +20,20 @@ mod get_extmarks;
 mod get_mark;
 mod get_option_value;
 mod get_text;
+mod load_file;
+mod match_add;
+mod match_fuzzy;
 mod notify;
 mod open_term;
 #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
 mod parse_cmd;
 mod select_popup_menu_item;
+mod set_buf;
 mod set_extmark;
 mod set_highlight;
 mod set_keymap;
+mod transaction;
+mod wipe;
 
 pub use buf_attach::*;
 pub use buf_delete::*;
@@ -36,6 +43,7 @@ pub use create_augroup::*;
 pub use create_autocmd::*;
 pub use create_command::*;
 pub use decoration_provider::*;
+pub use diff::*;
 pub use eval_statusline::*;
 pub use exec_autocmds::*;
 pub use get_autocmds::*;
@@ -46,11 +54,17 @@ pub use get_extmarks::*;
 pub use get_mark::*;
 pub use get_option_value::*;
 pub use get_text::*;
+pub use load_file::*;
+pub use match_add::*;
+pub use match_fuzzy::*;
 pub use notify::*;
 pub use open_term::*;
 #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
 pub use parse_cmd::*;
 pub use select_popup_menu_item::*;
+pub use set_buf::*;
 pub use set_extmark::*;
 pub use set_highlight::*;
 pub use set_keymap::*;
+pub use transaction::*;
+pub use wipe::*;
@@ -4,7 +4,11 @@ use nvim_types::Dictionary;
 /// Options passed to [`notify`](crate::notify). Currently unused.
 #[derive(Clone, Debug, Default, Builder)]
 #[builder(default, build_fn(private, name = "fallible_build"))]
-pub struct NotifyOpts {}
+pub struct NotifyOpts {
+    /// The fields of the options table, only set by [`Task`](crate::Task).
+    #[builder(setter(skip))]
+    pub(crate) fields: Dictionary,
+}
 
 impl NotifyOpts {
     #[inline(always)]
@@ -20,7 +24,7 @@ impl NotifyOptsBuilder {
 }
 
 impl From<&NotifyOpts> for Dictionary {
-    fn from(_: &NotifyOpts) -> Self {
-        Dictionary::new()
+    fn from(opts: &NotifyOpts) -> Self {
+        opts.fields.clone()
     }
 }
@@ -0,0 +1,26 @@
+use derive_builder::Builder;
+
+/// Options passed to [`set_current_buf_with`](crate::set_current_buf_with)
+/// and [`set_current_win_with`](crate::set_current_win_with).
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct SetBufOpts {
+    /// Whether to switch without triggering any autocommand (e.g.
+    /// `BufEnter`, `BufLeave`, `WinEnter`, etc.), like
+    /// [`:noautocmd`](https://neovim.io/doc/user/autocmd.html#:noautocmd).
+    pub(crate) noautocmd: bool,
+}
+
+impl SetBufOpts {
+    #[inline(always)]
+    /// Creates a new [`SetBufOptsBuilder`].
+    pub fn builder() -> SetBufOptsBuilder {
+        SetBufOptsBuilder::default()
+    }
+}
+
+impl SetBufOptsBuilder {
+    pub fn build(&mut self) -> SetBufOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
@@ -3,7 +3,7 @@ use std::ops::{Deref, DerefMut};
 use nvim_types::{self as nvim, Array, Integer, Object};
 
 use crate::trait_utils::StringOrListOfStrings;
-use crate::types::{ExtmarkHlMode, ExtmarkVirtTextPosition};
+use crate::types::{ExtmarkHlMode, ExtmarkId, ExtmarkVirtTextPosition};
 
 #[derive(Clone, Debug, Default)]
 #[allow(non_camel_case_types)]
@@ -98,8 +98,8 @@ impl SetExtmarkOpts {
     }
 
     #[inline(always)]
-    pub fn set_id(&mut self, id: u32) {
-        self.0.id = id.into();
+    pub fn set_id(&mut self, id: impl Into<ExtmarkId>) {
+        self.0.id = id.into().as_u32().into();
     }
 
     #[inline(always)]
@@ -118,7 +118,7 @@ impl SetExtmarkOpts {
     }
 
     #[inline(always)]
-    pub fn set_right_gravity(&mut self, right_gravity: u32) {
+    pub fn set_right_gravity(&mut self, right_gravity: bool) {
         self.0.right_gravity = right_gravity.into();
     }
 
@@ -132,6 +132,16 @@ impl SetExtmarkOpts {
         self.0.sign_text = nvim::String::from(sign_text).into();
     }
 
+    #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "neovim-0-8", feature = "neovim-nightly")))
+    )]
+    #[inline(always)]
+    pub fn set_spell(&mut self, spell: bool) {
+        self.0.spell = spell.into();
+    }
+
     #[inline(always)]
     pub fn set_strict(&mut self, strict: bool) {
         self.0.strict = strict.into();
@@ -293,9 +303,10 @@ impl SetExtmarkOptsBuilder {
         self
     }
 
-    /// Id of the extmark to edit.
+    /// Id of the extmark to edit, e.g. one returned by
+    /// [`Buffer::set_extmark`](crate::Buffer::set_extmark).
     #[inline(always)]
-    pub fn id(&mut self, id: u32) -> &mut Self {
+    pub fn id(&mut self, id: impl Into<ExtmarkId>) -> &mut Self {
         self.set_id(id);
         self
     }
@@ -325,7 +336,7 @@ impl SetExtmarkOptsBuilder {
     /// Indicates the direction the extmark will be shifted in when new text is
     /// inserted (`true` for right, `false` for left). Defaults to right.
     #[inline(always)]
-    pub fn right_gravity(&mut self, right_gravity: u32) -> &mut Self {
+    pub fn right_gravity(&mut self, right_gravity: bool) -> &mut Self {
         self.set_right_gravity(right_gravity);
         self
     }
@@ -344,6 +355,21 @@ impl SetExtmarkOptsBuilder {
         self
     }
 
+    /// Whether the range of the extmark should be spell checked, overriding
+    /// the [`@Spell`](https://neovim.io/doc/user/syntax.html#%40Spell) and
+    /// [`@NoSpell`](https://neovim.io/doc/user/syntax.html#%40NoSpell)
+    /// syntax clusters.
+    #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "neovim-0-8", feature = "neovim-nightly")))
+    )]
+    #[inline(always)]
+    pub fn spell(&mut self, spell: bool) -> &mut Self {
+        self.set_spell(spell);
+        self
+    }
+
     /// Whether the extmark should not be placed if the line or column value is
     /// past the end of the buffer or end of the line, respectively. Defaults
     /// to `true`.
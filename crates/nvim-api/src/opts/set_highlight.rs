@@ -1,6 +1,8 @@
 use derive_builder::Builder;
 use nvim_types::{self as nvim, NonOwning, Object};
 
+use crate::types::HlAttrs;
+
 /// Options passed to [`nvim_oxi::api::set_hl`](crate::set_hl).
 #[derive(Clone, Debug, Default, PartialEq, Builder)]
 #[builder(default, build_fn(private, name = "fallible_build"))]
@@ -115,6 +117,51 @@ impl SetHighlightOptsBuilder {
     }
 }
 
+/// Turns the definition of a group returned by [`get_hl`](crate::get_hl)
+/// back into the options setting it.
+impl From<&HlAttrs> for SetHighlightOpts {
+    fn from(attrs: &HlAttrs) -> Self {
+        let mut builder = Self::builder();
+
+        if let Some(link) = &attrs.link {
+            return builder.link(link).build();
+        }
+
+        if let Some(fg) = attrs.fg {
+            builder.foreground(&format!("#{fg:06x}"));
+        }
+        if let Some(bg) = attrs.bg {
+            builder.background(&format!("#{bg:06x}"));
+        }
+        if let Some(sp) = attrs.sp {
+            builder.special(&format!("#{sp:06x}"));
+        }
+        if let Some(ctermfg) = attrs.ctermfg {
+            builder.ctermfg(&ctermfg.to_string());
+        }
+        if let Some(ctermbg) = attrs.ctermbg {
+            builder.ctermbg(&ctermbg.to_string());
+        }
+        if let Some(blend) = attrs.blend {
+            builder.blend(blend);
+        }
+
+        builder
+            .bold(attrs.bold)
+            .standout(attrs.standout)
+            .underline(attrs.underline)
+            .undercurl(attrs.undercurl)
+            .underdouble(attrs.underdouble)
+            .underdotted(attrs.underdotted)
+            .underdashed(attrs.underdashed)
+            .strikethrough(attrs.strikethrough)
+            .italic(attrs.italic)
+            .reverse(attrs.reverse)
+            .nocombine(attrs.nocombine)
+            .build()
+    }
+}
+
 // Diff between 0.7.2 and master is:
 //
 // `temp` -> removed
@@ -9,7 +9,7 @@ use crate::ToFunction;
 #[builder(default, build_fn(private, name = "fallible_build"))]
 pub struct SetKeymapOpts {
     #[builder(setter(custom))]
-    callback: Object,
+    pub(crate) callback: Object,
 
     #[builder(setter(custom))]
     desc: Object,
@@ -73,8 +73,11 @@ impl SetKeymapOptsBuilder {
     }
 
     /// A description for the keymap.
-    pub fn desc(&mut self, desc: &str) -> &mut Self {
-        self.desc = Some(nvim::String::from(desc).into());
+    pub fn desc<S>(&mut self, desc: S) -> &mut Self
+    where
+        S: Into<nvim::String>,
+    {
+        self.desc = Some(desc.into().into());
         self
     }
 
@@ -0,0 +1,24 @@
+use derive_builder::Builder;
+
+/// Options passed to [`Buffer::transaction`](crate::Buffer::transaction).
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct TxOpts {
+    /// Whether to apply the edits without triggering any autocommand (e.g.
+    /// `TextChanged`), like
+    /// [`:noautocmd`](https://neovim.io/doc/user/autocmd.html#:noautocmd).
+    pub(crate) noautocmd: bool,
+}
+
+impl TxOpts {
+    #[inline(always)]
+    pub fn builder() -> TxOptsBuilder {
+        TxOptsBuilder::default()
+    }
+}
+
+impl TxOptsBuilder {
+    pub fn build(&mut self) -> TxOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
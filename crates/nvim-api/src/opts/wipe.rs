@@ -0,0 +1,31 @@
+use derive_builder::Builder;
+use nvim_types::Dictionary;
+
+/// Options passed to [`Buffer::wipe`](crate::Buffer::wipe).
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct WipeOpts {
+    /// Force deletion ignoring unsaved changes.
+    #[builder(setter(strip_option))]
+    force: Option<bool>,
+}
+
+impl WipeOpts {
+    /// Creates a new [`WipeOptsBuilder`].
+    #[inline(always)]
+    pub fn builder() -> WipeOptsBuilder {
+        WipeOptsBuilder::default()
+    }
+}
+
+impl WipeOptsBuilder {
+    pub fn build(&mut self) -> WipeOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
+
+impl From<&WipeOpts> for Dictionary {
+    fn from(opts: &WipeOpts) -> Self {
+        Self::from_iter([("force", opts.force), ("unload", Some(false))])
+    }
+}
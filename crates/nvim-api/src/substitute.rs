@@ -0,0 +1,137 @@
+use std::ops::{Bound, RangeBounds};
+
+use nvim_types::Array;
+
+use crate::opts::CmdOpts;
+use crate::types::{CmdInfos, CmdRange};
+use crate::{Buffer, Error, Result};
+
+/// The global variable used to count the substitutions made by
+/// [`Buffer::substitute`].
+const COUNTER: &str = "nvim_oxi_substitute_count";
+
+/// The characters tried, in order, as the delimiter of the pattern.
+const DELIMITERS: &[char] =
+    &['/', '#', '@', '!', ';', ',', ':', '%', '+', '=', '~', '^', '*'];
+
+impl Buffer {
+    /// Replaces the matches of `pattern` with `replacement` in the lines in
+    /// `line_range` via
+    /// [`:substitute`](https://neovim.io/doc/user/change.html#%3Asubstitute),
+    /// returning the number of replacements made. Indexing is zero-based,
+    /// end-exclusive.
+    ///
+    /// `pattern` is a Vim regex, while `replacement` is inserted literally,
+    /// i.e. none of the characters listed in
+    /// [`sub-replace-special`](https://neovim.io/doc/user/change.html#sub-replace-special)
+    /// have a special meaning. `flags` are the
+    /// [`:s_flags`](https://neovim.io/doc/user/change.html#%3As_flags),
+    /// e.g. `"gi"`. Not finding any match isn't an error.
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "neovim-0-8", feature = "neovim-nightly")))
+    )]
+    pub fn substitute<R>(
+        &self,
+        line_range: R,
+        pattern: &str,
+        replacement: &str,
+        flags: &str,
+    ) -> Result<usize>
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = match self.ex_range(line_range)? {
+            Some(range) => range,
+            None => return Ok(0),
+        };
+
+        // Using an expression as the replacement is the only way to both
+        // insert it literally and to count the substitutions.
+        let replacement = format!(
+            "\\=[add(g:{COUNTER}, 0), '{}'][1]",
+            replacement.replace('\'', "''")
+        );
+
+        let delim = pick_delimiter(&[pattern, &replacement])?;
+        let arg =
+            format!("{delim}{pattern}{delim}{replacement}{delim}{flags}e");
+
+        crate::set_var(COUNTER, Array::new())?;
+        let res = self.run_ex("substitute", range, arg);
+        let count = crate::get_var::<Array>(COUNTER).map(|subs| subs.len());
+        crate::del_var(COUNTER)?;
+
+        res.and(count)
+    }
+
+    /// Executes the Ex command `cmd` on the lines in `line_range` matching
+    /// `pattern` via [`:global`](https://neovim.io/doc/user/repeat.html#%3Aglobal).
+    /// Indexing is zero-based, end-exclusive.
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "neovim-0-8", feature = "neovim-nightly")))
+    )]
+    pub fn global<R>(
+        &self,
+        line_range: R,
+        pattern: &str,
+        cmd: &str,
+    ) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = match self.ex_range(line_range)? {
+            Some(range) => range,
+            None => return Ok(()),
+        };
+
+        let delim = pick_delimiter(&[pattern])?;
+        self.run_ex("global", range, format!("{delim}{pattern}{delim}{cmd}"))
+    }
+
+    /// Converts a zero-based, end-exclusive line range into the one-based,
+    /// inclusive range used by Ex commands, or `None` if it's empty.
+    fn ex_range<R>(&self, line_range: R) -> Result<Option<CmdRange>>
+    where
+        R: RangeBounds<usize>,
+    {
+        let first = match line_range.start_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n + 2,
+            Bound::Unbounded => 1,
+        };
+
+        let last = match line_range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.line_count()?,
+        };
+
+        Ok((first <= last).then_some(CmdRange::Double(first, last)))
+    }
+
+    /// Runs the Ex command `cmd` on `range` with `arg` as its only argument,
+    /// with this buffer as the current buffer.
+    fn run_ex(&self, cmd: &str, range: CmdRange, arg: String) -> Result<()> {
+        let infos =
+            CmdInfos::builder().cmd(cmd).range(range).args([arg]).build();
+
+        self.call(move |()| {
+            crate::cmd(&infos, &CmdOpts::default())?;
+            Ok(())
+        })
+    }
+}
+
+/// Returns a delimiter which doesn't appear in any of `parts`, so that the
+/// parts don't have to be escaped.
+fn pick_delimiter(parts: &[&str]) -> Result<char> {
+    DELIMITERS
+        .iter()
+        .copied()
+        .find(|&delim| parts.iter().all(|part| !part.contains(delim)))
+        .ok_or_else(|| {
+            Error::custom("couldn't find a delimiter not used in the pattern")
+        })
+}
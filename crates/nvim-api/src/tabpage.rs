@@ -13,8 +13,8 @@ use serde::{Deserialize, Serialize};
 use crate::choose;
 use crate::ffi::tabpage::*;
 use crate::iterator::SuperIterator;
-use crate::Result;
 use crate::Window;
+use crate::{Error, Result};
 
 /// A wrapper around a Neovim tab handle.
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -95,7 +95,8 @@ impl TabPage {
 
     /// Binding to [`nvim_tabpage_get_var`][1].
     ///
-    /// Gets a tab-scoped (`t:`) variable.
+    /// Gets a tab-scoped (`t:`) variable. Fails with
+    /// [`Error::VarNotFound`] if the variable isn't set.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_tabpage_get_var()
     pub fn get_var<Var>(&self, name: &str) -> Result<Var>
@@ -107,7 +108,10 @@ impl TabPage {
         let obj = unsafe {
             nvim_tabpage_get_var(self.0, name.non_owning(), &mut err)
         };
-        choose!(err, Ok(Var::from_object(obj)?))
+        if err.is_err() {
+            return Err(Error::from_get_var(err, &name, self.is_valid()));
+        }
+        Ok(Var::from_object(obj)?)
     }
 
     /// Binding to [`nvim_tabpage_get_win`](https://neovim.io/doc/user/api.html#nvim_tabpage_get_win()).
@@ -0,0 +1,117 @@
+use luajit_bindings::{self as lua, ffi::*, macros::cstr};
+use nvim_types::{Dictionary, Integer, Object};
+
+use crate::opts::NotifyOpts;
+use crate::types::LogLevel;
+use crate::Result;
+
+/// A long running operation whose progress is reported via
+/// [`notify`](crate::notify).
+///
+/// Every notification emitted by a `Task` passes an options table to
+/// [`vim.notify()`](https://neovim.io/doc/user/lua.html#vim.notify()) with
+/// the following fields, modeled after LSP's progress notifications:
+///
+/// - `id`: the task's token, the same for all its notifications;
+/// - `title`: the title given to [`Task::start`];
+/// - `kind`: one of `"begin"`, `"report"` or `"end"`;
+/// - `percentage`: only set by [`Task::report`];
+/// - `cancelled`: only set if the task is dropped without being finished.
+///
+/// This lets notification frontends group them together.
+#[derive(Debug)]
+pub struct Task {
+    token: u32,
+    title: String,
+    finished: bool,
+}
+
+impl Task {
+    /// Starts a new task, emitting a `"begin"` notification.
+    pub fn start<Title: Into<String>>(title: Title) -> Result<Self> {
+        let token = next_token();
+
+        let task = Self { token, title: title.into(), finished: false };
+        task.notify(&task.title, LogLevel::Info, "begin", [])?;
+        Ok(task)
+    }
+
+    /// Emits a `"end"` notification with the given message.
+    pub fn finish(mut self, msg: &str) -> Result<()> {
+        self.finished = true;
+        self.notify(msg, LogLevel::Info, "end", [])
+    }
+
+    /// Emits a `"report"` notification with the given message. Percentages
+    /// bigger than 100 are clamped.
+    pub fn report(&self, percentage: u8, msg: &str) -> Result<()> {
+        let percentage = percentage.min(100) as Integer;
+        self.notify(
+            msg,
+            LogLevel::Info,
+            "report",
+            [("percentage", percentage.into())],
+        )
+    }
+
+    /// Returns the token shared by all the notifications of the task.
+    #[inline]
+    pub fn token(&self) -> u32 {
+        self.token
+    }
+
+    fn notify<const N: usize>(
+        &self,
+        msg: &str,
+        log_level: LogLevel,
+        kind: &str,
+        extra: [(&str, Object); N],
+    ) -> Result<()> {
+        let fields = [
+            ("id", Object::from(self.token as Integer)),
+            ("title", self.title.as_str().into()),
+            ("kind", kind.into()),
+        ]
+        .into_iter()
+        .chain(extra)
+        .collect::<Dictionary>();
+
+        crate::notify(msg, log_level, &NotifyOpts { fields })
+    }
+}
+
+/// Returns a new task token. The last one is stored in the Lua registry
+/// instead of in a static so that the tasks of different plugins, which
+/// each get their own copy of this crate, don't share the same tokens.
+fn next_token() -> u32 {
+    let key = cstr!("nvim_oxi_task_last_token");
+
+    unsafe {
+        lua::with_state(|lstate| {
+            lua_getfield(lstate, LUA_REGISTRYINDEX, key);
+            let token = (lua_tointeger(lstate, -1) as u32).wrapping_add(1);
+            lua_pop(lstate, 1);
+
+            lua_pushstring(lstate, key);
+            lua_pushinteger(lstate, token as lua_Integer);
+            lua_rawset(lstate, LUA_REGISTRYINDEX);
+
+            token
+        })
+    }
+}
+
+impl Drop for Task {
+    /// Emits an `"end"` notification with `cancelled` set to `true` if the
+    /// task hasn't been finished.
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.notify(
+                "Cancelled",
+                LogLevel::Warn,
+                "end",
+                [("cancelled", true.into())],
+            );
+        }
+    }
+}
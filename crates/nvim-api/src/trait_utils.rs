@@ -1,5 +1,7 @@
 use luajit_bindings::{Poppable, Pushable};
-use nvim_types::{Array, Function, Object};
+use nvim_types::{self as nvim, Array, Function, Object};
+
+use crate::types::AutocmdEvent;
 
 macro_rules! impl_into {
     ($trait:ident, $type:ty) => {
@@ -43,6 +45,51 @@ impl<S: Into<String>> StringOrListOfStrings for Vec<S> {
     }
 }
 
+/// The name of an autocommand event or an [`AutocmdEvent`].
+pub trait StringOrAutocmdEvent {
+    /// Returns the name of the event and, for [`AutocmdEvent::User`], the
+    /// pattern it carries.
+    fn to_event(self) -> (nvim::String, Option<String>);
+}
+
+impl StringOrAutocmdEvent for &str {
+    #[inline]
+    fn to_event(self) -> (nvim::String, Option<String>) {
+        (self.into(), None)
+    }
+}
+
+impl StringOrAutocmdEvent for String {
+    #[inline]
+    fn to_event(self) -> (nvim::String, Option<String>) {
+        (self.into(), None)
+    }
+}
+
+impl StringOrAutocmdEvent for nvim::String {
+    #[inline]
+    fn to_event(self) -> (nvim::String, Option<String>) {
+        (self, None)
+    }
+}
+
+impl StringOrAutocmdEvent for &AutocmdEvent {
+    #[inline]
+    fn to_event(self) -> (nvim::String, Option<String>) {
+        (self.name().into(), self.pattern().map(ToOwned::to_owned))
+    }
+}
+
+impl StringOrAutocmdEvent for AutocmdEvent {
+    #[inline]
+    fn to_event(self) -> (nvim::String, Option<String>) {
+        match self {
+            Self::User(pattern) => ("User".into(), Some(pattern)),
+            event => (event.name().into(), None),
+        }
+    }
+}
+
 /// A Rust closure or a [`Function`].
 pub trait ToFunction<A, R> {
     fn to_object(self) -> Object;
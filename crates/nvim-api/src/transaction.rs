@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+use std::ops::RangeBounds;
+use std::rc::Rc;
+
+use nvim_types as nvim;
+
+use crate::opts::TxOpts;
+use crate::Result;
+use crate::{Buffer, Window};
+
+/// A group of edits applied to a buffer via [`Buffer::transaction`].
+pub struct Tx<'a> {
+    buffer: &'a mut Buffer,
+}
+
+impl Tx<'_> {
+    /// Returns the buffer the transaction is editing, e.g. to call the
+    /// [`Buffer`] methods not exposed by the transaction itself.
+    #[inline]
+    pub fn buffer(&mut self) -> &mut Buffer {
+        self.buffer
+    }
+
+    /// Same as [`Buffer::set_lines`].
+    pub fn set_lines<Line, Lines, R>(
+        &mut self,
+        line_range: R,
+        strict_indexing: bool,
+        replacement: Lines,
+    ) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+        Lines: IntoIterator<Item = Line>,
+        Line: Into<nvim::String>,
+    {
+        self.buffer.set_lines(line_range, strict_indexing, replacement)
+    }
+
+    /// Same as [`Buffer::set_text`].
+    pub fn set_text<Line, Lines, R>(
+        &mut self,
+        line_range: R,
+        start_col: usize,
+        end_col: usize,
+        replacement: Lines,
+    ) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+        Lines: IntoIterator<Item = Line>,
+        Line: Into<nvim::String>,
+    {
+        self.buffer.set_text(line_range, start_col, end_col, replacement)
+    }
+}
+
+impl Buffer {
+    /// Applies all the edits made by `fun` as a single transaction.
+    ///
+    /// `fun` is called with the buffer temporarily set as the current one
+    /// (see [`Buffer::call`]), so that commands like `:normal` apply to it
+    /// even if it's not displayed in the current window.
+    ///
+    /// The edits form a single undo step (see
+    /// [`edit_as_undo_block`](Buffer::edit_as_undo_block)), and the view
+    /// (cursor position and scrolling) of every window displaying the buffer
+    /// is saved before calling `fun` and restored afterwards.
+    ///
+    /// If `fun` fails the edits it has already made are not reverted, but
+    /// the views are still restored and the undo block is still closed, so
+    /// they can be reverted with a single `:undo`.
+    pub fn transaction<F>(&mut self, opts: &TxOpts, fun: F) -> Result<()>
+    where
+        F: FnOnce(&mut Tx<'_>) -> Result<()> + 'static,
+    {
+        let views = crate::list_wins()
+            .filter(|win| matches!(win.get_buf(), Ok(buf) if buf == *self))
+            .map(|win| Ok((win.save_view()?, win)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // The result of `fun` is passed back on the side, or `Buffer::call`
+        // would turn its error into a generic Lua error.
+        let res = Rc::new(RefCell::new(Ok(())));
+
+        let called = self.call({
+            let noautocmd = opts.noautocmd;
+            let mut buffer = self.clone();
+            let res = Rc::clone(&res);
+            move |()| {
+                *res.borrow_mut() =
+                    crate::global::with_noautocmd(noautocmd, || {
+                        buffer.edit_as_undo_block(|buffer| {
+                            fun(&mut Tx { buffer })
+                        })
+                    });
+                Ok(())
+            }
+        });
+
+        let res = called.and_then(|()| res.replace(Ok(())));
+
+        let restored =
+            views.into_iter().try_for_each(|(view, mut win): (_, Window)| {
+                win.restore_view(&view)
+            });
+
+        res.and(restored)
+    }
+}
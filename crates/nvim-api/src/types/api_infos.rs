@@ -0,0 +1,174 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use nvim_types::{
+    conversion::{self, FromObject},
+    serde::Deserializer,
+    Object,
+};
+use serde::Deserialize;
+
+use crate::Error;
+
+/// Metadata about the API exposed by the running Neovim instance, as
+/// returned by [`get_api_info`](crate::get_api_info).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+pub struct ApiInfos {
+    /// The functions exposed by the API.
+    pub functions: Vec<ApiFunctionInfos>,
+
+    /// The version of the running Neovim instance.
+    pub version: NeovimVersion,
+}
+
+/// Informations about a function exposed by the API.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ApiFunctionInfos {
+    /// The API level in which the function was deprecated, if any.
+    pub deprecated_since: Option<u32>,
+
+    /// Whether the function is a method on a buffer, window or tabpage.
+    pub method: bool,
+
+    /// The name of the function, e.g. `nvim_get_current_buf`.
+    pub name: String,
+
+    /// The API level in which the function was introduced.
+    pub since: u32,
+}
+
+/// A Neovim version, e.g. `0.8.0` or `0.9.0-dev`.
+///
+/// Prereleases compare as smaller than the corresponding release, so
+/// `0.9.0-dev` is between `0.8.x` and `0.9.0`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct NeovimVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+
+    /// Whether this is a prerelease version, e.g. a nightly build.
+    pub prerelease: bool,
+}
+
+impl NeovimVersion {
+    /// Creates a new release version.
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch, prerelease: false }
+    }
+}
+
+impl From<(u32, u32, u32)> for NeovimVersion {
+    fn from((major, minor, patch): (u32, u32, u32)) -> Self {
+        Self::new(major, minor, patch)
+    }
+}
+
+impl fmt::Display for NeovimVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if self.prerelease {
+            write!(f, "-dev")?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for NeovimVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| other.prerelease.cmp(&self.prerelease))
+    }
+}
+
+impl PartialOrd for NeovimVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for NeovimVersion {
+    type Err = Error;
+
+    /// Parses strings like `0.8.0`, `v0.8.0` or `v0.9.0-dev-123+g1234abcd`.
+    /// Anything after the patch number marks the version as a prerelease.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::custom(format!("invalid version {s:?}"));
+
+        let s = s.strip_prefix('v').unwrap_or(s);
+
+        let (numbers, prerelease) = match s.find(['-', '+']) {
+            Some(idx) => (&s[..idx], true),
+            None => (s, false),
+        };
+
+        let mut numbers = numbers.split('.').map(u32::from_str);
+
+        let mut next =
+            || numbers.next().ok_or_else(invalid)?.map_err(|_| invalid());
+
+        let major = next()?;
+        let minor = next()?;
+        let patch = next()?;
+
+        if numbers.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(Self { major, minor, patch, prerelease })
+    }
+}
+
+impl FromObject for ApiInfos {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
+
+impl FromObject for NeovimVersion {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_release() {
+        let version = "0.8.1".parse::<NeovimVersion>();
+        assert_eq!(Ok(NeovimVersion::new(0, 8, 1)), version);
+
+        let version = "v0.7.2".parse::<NeovimVersion>();
+        assert_eq!(Ok(NeovimVersion::new(0, 7, 2)), version);
+    }
+
+    #[test]
+    fn parse_nightly() {
+        let version = "v0.9.0-dev-218+g1e4adf4e5".parse::<NeovimVersion>();
+        assert!(version.is_ok(), "{version:?}");
+
+        let version = version.unwrap();
+        assert!(version.prerelease);
+        assert_eq!((0, 9, 0), (version.major, version.minor, version.patch));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("0.8".parse::<NeovimVersion>().is_err());
+        assert!("0.8.0.1".parse::<NeovimVersion>().is_err());
+        assert!("foo".parse::<NeovimVersion>().is_err());
+    }
+
+    #[test]
+    fn prerelease_ordering() {
+        let nightly = "0.9.0-dev".parse::<NeovimVersion>().unwrap();
+        assert!(nightly > NeovimVersion::new(0, 8, 3));
+        assert!(nightly < NeovimVersion::new(0, 9, 0));
+    }
+}
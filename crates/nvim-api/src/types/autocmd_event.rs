@@ -0,0 +1,296 @@
+use nvim_types as nvim;
+
+macro_rules! autocmd_events {
+    ($($(#[$attr:meta])* $event:ident,)*) => {
+        /// An event that can trigger an autocommand, as listed in
+        /// [`:h autocmd-events`](https://neovim.io/doc/user/autocmd.html#autocmd-events).
+        #[non_exhaustive]
+        #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+        pub enum AutocmdEvent {
+            $($(#[$attr])* $event,)*
+
+            /// Never triggered by Neovim itself, only by
+            /// [`exec_autocmds`](crate::exec_autocmds). Carries the pattern
+            /// the autocommand matches on, see [`AutocmdEvent::pattern`].
+            User(String),
+        }
+
+        impl AutocmdEvent {
+            /// Returns the name of the event, e.g. `"BufEnter"`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$event => stringify!($event),)*
+                    Self::User(_) => "User",
+                }
+            }
+        }
+    };
+}
+
+autocmd_events! {
+    /// After adding a new buffer to the buffer list.
+    BufAdd,
+    /// Before deleting a buffer from the buffer list.
+    BufDelete,
+    /// After entering a buffer.
+    BufEnter,
+    /// After changing the name of the current buffer.
+    BufFilePost,
+    /// Before changing the name of the current buffer.
+    BufFilePre,
+    /// Before a buffer becomes hidden.
+    BufHidden,
+    /// Before leaving to another buffer.
+    BufLeave,
+    /// After the `modified` value of a buffer has been changed.
+    BufModifiedSet,
+    /// After creating a new buffer or renaming an existing one.
+    BufNew,
+    /// When starting to edit a file that doesn't exist.
+    BufNewFile,
+    /// When starting to edit a new buffer, after reading the file.
+    BufReadPost,
+    /// Before starting to edit a new buffer, should read the file into it.
+    BufReadCmd,
+    /// When starting to edit a new buffer, before reading the file.
+    BufReadPre,
+    /// Before unloading a buffer.
+    BufUnload,
+    /// After a buffer is displayed in a window.
+    BufWinEnter,
+    /// Before a buffer is removed from a window.
+    BufWinLeave,
+    /// Before completely deleting a buffer.
+    BufWipeout,
+    /// Before writing the whole buffer to a file.
+    BufWritePre,
+    /// Before writing the whole buffer, should do the writing itself.
+    BufWriteCmd,
+    /// After writing the whole buffer to a file.
+    BufWritePost,
+    /// State of a channel changed.
+    ChanInfo,
+    /// Just after a channel was opened.
+    ChanOpen,
+    /// When a user command is used but it isn't defined.
+    CmdUndefined,
+    /// After a change was made to the text inside the command line.
+    CmdlineChanged,
+    /// After entering the command line.
+    CmdlineEnter,
+    /// Before leaving the command line.
+    CmdlineLeave,
+    /// After entering the command-line window.
+    CmdwinEnter,
+    /// Before leaving the command-line window.
+    CmdwinLeave,
+    /// After loading a color scheme.
+    ColorScheme,
+    /// Before loading a color scheme.
+    ColorSchemePre,
+    /// After each time the Insert mode completion menu changed.
+    CompleteChanged,
+    /// After Insert mode completion is done, before clearing the info.
+    CompleteDonePre,
+    /// After Insert mode completion is done.
+    CompleteDone,
+    /// When the user doesn't press a key for the time specified with
+    /// `updatetime`.
+    CursorHold,
+    /// Like `CursorHold`, but in Insert mode.
+    CursorHoldI,
+    /// After the cursor was moved in Normal or Visual mode.
+    CursorMoved,
+    /// After the cursor was moved in Insert mode.
+    CursorMovedI,
+    /// After the diagnostics of a buffer have changed.
+    DiagnosticChanged,
+    /// After diffs have been updated.
+    DiffUpdated,
+    /// After the current directory was changed.
+    DirChanged,
+    /// When the current directory is going to be changed.
+    DirChangedPre,
+    /// When using `:quit` or `:wq` in a way that makes Neovim exit.
+    ExitPre,
+    /// Before appending to a file, should do the appending itself.
+    FileAppendCmd,
+    /// After appending to a file.
+    FileAppendPost,
+    /// Before appending to a file.
+    FileAppendPre,
+    /// Before making the first change to a read-only file.
+    FileChangedRO,
+    /// When Neovim notices that a file changed since editing started.
+    FileChangedShell,
+    /// After handling a file that was changed outside of Neovim.
+    FileChangedShellPost,
+    /// Before reading a file with `:read`, should do the reading itself.
+    FileReadCmd,
+    /// After reading a file with `:read`.
+    FileReadPost,
+    /// Before reading a file with `:read`.
+    FileReadPre,
+    /// When the `filetype` option has been set.
+    FileType,
+    /// Before writing to a file when not writing the whole buffer, should
+    /// do the writing itself.
+    FileWriteCmd,
+    /// After writing to a file when not writing the whole buffer.
+    FileWritePost,
+    /// Before writing to a file when not writing the whole buffer.
+    FileWritePre,
+    /// After reading a file from a filter command.
+    FilterReadPost,
+    /// Before reading a file from a filter command.
+    FilterReadPre,
+    /// After writing a file for a filter command.
+    FilterWritePost,
+    /// Before writing a file for a filter command.
+    FilterWritePre,
+    /// When Neovim got input focus.
+    FocusGained,
+    /// When Neovim lost input focus.
+    FocusLost,
+    /// When a user function is used but it isn't defined.
+    FuncUndefined,
+    /// When typing `<Insert>` while in Insert or Replace mode.
+    InsertChange,
+    /// When a character is typed in Insert mode, before inserting it.
+    InsertCharPre,
+    /// Just before starting Insert mode.
+    InsertEnter,
+    /// Just before leaving Insert mode.
+    InsertLeavePre,
+    /// Just after leaving Insert mode.
+    InsertLeave,
+    /// After an LSP client attaches to a buffer.
+    LspAttach,
+    /// Just before an LSP client detaches from a buffer.
+    LspDetach,
+    /// Just before showing the popup menu.
+    MenuPopup,
+    /// After changing the mode.
+    ModeChanged,
+    /// After setting an option.
+    OptionSet,
+    /// Before a quickfix command is run.
+    QuickFixCmdPre,
+    /// After a quickfix command is run.
+    QuickFixCmdPost,
+    /// When using `:quit`, `:wq` or `:qall`, before deciding whether it
+    /// closes the current window or quits Neovim.
+    QuitPre,
+    /// When starting to record a macro.
+    RecordingEnter,
+    /// When stopping to record a macro.
+    RecordingLeave,
+    /// When a reply from a Neovim that functions as a server was received.
+    RemoteReply,
+    /// After loading a session file.
+    SessionLoadPost,
+    /// After executing a shell command.
+    ShellCmdPost,
+    /// After executing a shell command with a filter.
+    ShellFilterPost,
+    /// After Neovim receives a signal.
+    Signal,
+    /// When sourcing a Vim script, should do the sourcing itself.
+    SourceCmd,
+    /// After sourcing a Vim script.
+    SourcePost,
+    /// Before sourcing a Vim script.
+    SourcePre,
+    /// When trying to load a spell file and it can't be found.
+    SpellFileMissing,
+    /// After reading from stdin into the buffer.
+    StdinReadPost,
+    /// Before reading from stdin into the buffer.
+    StdinReadPre,
+    /// When an existing swap file is found when starting to edit a file.
+    SwapExists,
+    /// When the `syntax` option has been set.
+    Syntax,
+    /// After closing a tab page.
+    TabClosed,
+    /// Just after entering a tab page.
+    TabEnter,
+    /// Just before leaving a tab page.
+    TabLeave,
+    /// When creating a new tab page.
+    TabNew,
+    /// After entering a new tab page.
+    TabNewEntered,
+    /// When a terminal job ends.
+    TermClose,
+    /// After entering Terminal mode.
+    TermEnter,
+    /// After leaving Terminal mode.
+    TermLeave,
+    /// When a terminal job is starting.
+    TermOpen,
+    /// After the response to `t_RV` is received from the terminal.
+    TermResponse,
+    /// After a change was made to the text of the current buffer in Normal
+    /// mode.
+    TextChanged,
+    /// After a change was made to the text of the current buffer in Insert
+    /// mode, when the popup menu is not visible.
+    TextChangedI,
+    /// After a change was made to the text of the current buffer in Insert
+    /// mode, when the popup menu is visible.
+    TextChangedP,
+    /// Just after a yank or deleting command.
+    TextYankPost,
+    /// After a UI connects.
+    UIEnter,
+    /// After a UI disconnects.
+    UILeave,
+    /// After doing all the startup stuff.
+    VimEnter,
+    /// Before exiting Neovim, just after writing the shada file.
+    VimLeave,
+    /// Before exiting Neovim, just before writing the shada file.
+    VimLeavePre,
+    /// After the Neovim window was resized.
+    VimResized,
+    /// After Neovim resumes from being suspended.
+    VimResume,
+    /// Before Neovim is suspended.
+    VimSuspend,
+    /// When closing a window, just before it's removed from the layout.
+    WinClosed,
+    /// After entering another window.
+    WinEnter,
+    /// Before leaving a window.
+    WinLeave,
+    /// When a new window was created.
+    WinNew,
+    /// After any window in the current tab page scrolled or changed size.
+    WinScrolled,
+}
+
+impl AutocmdEvent {
+    /// Returns the pattern carried by [`AutocmdEvent::User`], which
+    /// [`create_autocmd`](crate::create_autocmd) and
+    /// [`exec_autocmds`](crate::exec_autocmds) use as the `patterns` of
+    /// their options.
+    pub fn pattern(&self) -> Option<&str> {
+        match self {
+            Self::User(pattern) => Some(pattern),
+            _ => None,
+        }
+    }
+}
+
+impl From<AutocmdEvent> for nvim::String {
+    fn from(event: AutocmdEvent) -> Self {
+        event.name().into()
+    }
+}
+
+impl From<&AutocmdEvent> for nvim::String {
+    fn from(event: &AutocmdEvent) -> Self {
+        event.name().into()
+    }
+}
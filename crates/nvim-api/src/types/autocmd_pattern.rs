@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use crate::Result;
+
+/// A file pattern like the ones given to
+/// [`create_autocmd`](crate::create_autocmd), compiled to be matched against
+/// paths from Rust, e.g. to further filter the events received by a callback.
+///
+/// The pattern is converted to a regular expression by Neovim's
+/// [`glob2regpat()`][1], so wildcards, `**` and `{a,b}` alternatives behave
+/// exactly like in autocommands. As described in [`:h autocmd-pattern`][2],
+/// patterns without a `/` are only matched against the tail of the path.
+/// Matching is always case-sensitive, regardless of `'ignorecase'`.
+///
+/// [1]: https://neovim.io/doc/user/builtin.html#glob2regpat()
+/// [2]: https://neovim.io/doc/user/autocmd.html#autocmd-pattern
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AutocmdPattern {
+    pattern: String,
+    regex: String,
+}
+
+impl AutocmdPattern {
+    /// Compiles the given pattern.
+    pub fn new(pattern: impl Into<String>) -> Result<Self> {
+        let pattern = pattern.into();
+        let regex = crate::call_function::<_, String>(
+            "glob2regpat",
+            (pattern.as_str(),),
+        )?;
+        // Without `\C` the regex would be matched according to the user's
+        // `'ignorecase'` and `'smartcase'`.
+        Ok(Self { pattern, regex: format!("\\C{regex}") })
+    }
+
+    /// Returns the pattern the `AutocmdPattern` was compiled from.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns whether `path` matches the pattern. Paths that aren't valid
+    /// UTF-8 are matched lossily.
+    pub fn matches<P: AsRef<Path>>(&self, path: P) -> Result<bool> {
+        let path = path.as_ref();
+
+        let subject = if self.pattern.contains('/') {
+            path.as_os_str()
+        } else {
+            match path.file_name() {
+                Some(tail) => tail,
+                None => return Ok(false),
+            }
+        };
+
+        let idx = crate::call_function::<_, i64>(
+            "match",
+            (subject.to_string_lossy().into_owned(), self.regex.as_str()),
+        )?;
+
+        Ok(idx != -1)
+    }
+}
@@ -0,0 +1,43 @@
+use nvim_types::{
+    conversion::{self, FromObject},
+    serde::Deserializer,
+    Object,
+};
+use serde::Deserialize;
+
+/// Internal buffer statistics returned by
+/// [`Buffer::stats`](crate::Buffer::stats).
+///
+/// These come from an internal Neovim function meant for debugging, so the
+/// available fields may change between Neovim versions.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct BufStats {
+    /// The line number of the line currently cached by the memline.
+    pub current_lnum: usize,
+
+    /// Number of deleted bytes not yet reported to `on_bytes` callbacks.
+    /// Should always be zero when API functions are called.
+    pub dirty_bytes: usize,
+
+    /// Like [`dirty_bytes`](BufStats::dirty_bytes), but for the second
+    /// counter Neovim uses to track deletions.
+    pub dirty_bytes2: usize,
+
+    /// Number of times the cached line was flushed to the memline.
+    pub flush_count: usize,
+
+    /// Whether the cached line has changes that haven't been flushed yet.
+    pub line_dirty: bool,
+
+    /// Number of extmark entries (saved extmark positions and text splices)
+    /// stored in the current undo header, if there is one.
+    #[serde(default)]
+    pub uhp_extmark_size: Option<usize>,
+}
+
+impl FromObject for BufStats {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
@@ -0,0 +1,127 @@
+use crate::opts::{OnBytesArgs, OnLinesArgs};
+use crate::Buffer;
+
+/// A change to the lines of a buffer, decoded from the arguments of an
+/// [`on_lines`](crate::opts::BufAttachOptsBuilder::on_lines) callback.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinesChange {
+    /// The buffer that changed.
+    pub buffer: Buffer,
+
+    /// The value of the buffer-local `b:changedtick` variable.
+    pub changedtick: u32,
+
+    /// The first row that changed (0-indexed).
+    pub first_row: usize,
+
+    /// The last row that was changed, exclusive.
+    pub last_row: usize,
+
+    /// The last row in the updated range, exclusive.
+    pub new_last_row: usize,
+
+    /// The byte count of the previous contents.
+    pub prev_byte_count: usize,
+}
+
+impl From<OnLinesArgs> for LinesChange {
+    fn from(args: OnLinesArgs) -> Self {
+        let (
+            _,
+            buffer,
+            changedtick,
+            first_row,
+            last_row,
+            new_last_row,
+            prev_byte_count,
+            _,
+            _,
+        ) = args;
+
+        Self {
+            buffer,
+            changedtick,
+            first_row,
+            last_row,
+            new_last_row,
+            prev_byte_count,
+        }
+    }
+}
+
+/// A change to the bytes of a buffer, decoded from the arguments of an
+/// [`on_bytes`](crate::opts::BufAttachOptsBuilder::on_bytes) callback.
+///
+/// The deleted and added extents are relative to the start of the change,
+/// i.e. a change within a single line has both `old_end_row` and
+/// `new_end_row` set to zero.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BytesChange {
+    /// The buffer that changed.
+    pub buffer: Buffer,
+
+    /// The value of the buffer-local `b:changedtick` variable.
+    pub changedtick: u32,
+
+    /// The start row of the changed text (0-indexed).
+    pub start_row: usize,
+
+    /// The start column of the changed text.
+    pub start_col: usize,
+
+    /// The byte offset of the changed text from the start of the buffer.
+    pub start_byte: usize,
+
+    /// The number of rows deleted.
+    pub old_end_row: usize,
+
+    /// The number of columns deleted.
+    pub old_end_col: usize,
+
+    /// The number of bytes deleted.
+    pub old_end_byte: usize,
+
+    /// The number of rows added.
+    pub new_end_row: usize,
+
+    /// The number of columns added.
+    pub new_end_col: usize,
+
+    /// The number of bytes added.
+    pub new_end_byte: usize,
+}
+
+impl From<OnBytesArgs> for BytesChange {
+    fn from(args: OnBytesArgs) -> Self {
+        let (
+            _,
+            buffer,
+            changedtick,
+            start_row,
+            start_col,
+            start_byte,
+            old_end_row,
+            old_end_col,
+            old_end_byte,
+            new_end_row,
+            new_end_col,
+            new_end_byte,
+        ) = args;
+
+        Self {
+            buffer,
+            changedtick,
+            start_row,
+            start_col,
+            start_byte,
+            old_end_row,
+            old_end_col,
+            old_end_byte,
+            new_end_row,
+            new_end_col,
+            new_end_byte,
+        }
+    }
+}
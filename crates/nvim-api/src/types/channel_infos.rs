@@ -41,6 +41,9 @@ pub struct ChannelInfos {
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ChannelStream {
+    /// Channel of a terminal opened with [`open_term`](crate::open_term),
+    /// which isn't connected to any process.
+    Internal,
     Job,
     Socket,
     StdErr,
@@ -0,0 +1,71 @@
+use derive_builder::Builder;
+use nvim_types::{Dictionary, Integer, Object};
+
+/// A match returned by a completion function registered via
+/// [`register_completion_func`](crate::register_completion_func), as
+/// described in `:h complete-items`.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct CompletionItem {
+    /// The text that will be inserted.
+    #[builder(setter(into))]
+    pub word: String,
+
+    /// An abbreviation of `word` displayed in the popup menu instead of it.
+    #[builder(setter(into, strip_option))]
+    pub abbr: Option<String>,
+
+    /// Extra text displayed in the popup menu after `word` or `abbr`.
+    #[builder(setter(into, strip_option))]
+    pub menu: Option<String>,
+
+    /// Extra information about the item, displayed in the preview window.
+    #[builder(setter(into, strip_option))]
+    pub info: Option<String>,
+
+    /// A single letter indicating the type of the completion.
+    #[builder(setter(strip_option))]
+    pub kind: Option<char>,
+
+    /// Whether case should be ignored when comparing the item with other
+    /// items.
+    pub icase: bool,
+
+    /// Whether the item should be added even if an item with the same
+    /// `word` is already present.
+    pub dup: bool,
+
+    /// Whether the item should be added even if `word` is empty.
+    pub empty: bool,
+}
+
+impl CompletionItem {
+    #[inline(always)]
+    /// Creates a new [`CompletionItemBuilder`].
+    pub fn builder() -> CompletionItemBuilder {
+        CompletionItemBuilder::default()
+    }
+}
+
+impl CompletionItemBuilder {
+    pub fn build(&mut self) -> CompletionItem {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
+
+impl From<CompletionItem> for Object {
+    fn from(item: CompletionItem) -> Self {
+        Dictionary::from_iter([
+            ("word", Object::from(item.word)),
+            ("abbr", item.abbr.into()),
+            ("menu", item.menu.into()),
+            ("info", item.info.into()),
+            ("kind", item.kind.map(String::from).into()),
+            ("icase", (item.icase as Integer).into()),
+            ("dup", (item.dup as Integer).into()),
+            ("empty", (item.empty as Integer).into()),
+        ])
+        .into()
+    }
+}
@@ -0,0 +1,20 @@
+use crate::Buffer;
+
+/// The arguments passed to a completion function registered via
+/// [`register_completion_func`](crate::register_completion_func).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompletionRequest {
+    /// The buffer in which the completion was triggered.
+    pub buffer: Buffer,
+
+    /// The 1-indexed line of the cursor.
+    pub row: usize,
+
+    /// The 0-indexed byte column where the completed text starts.
+    pub col: usize,
+
+    /// The text between `col` and the cursor that the matches should
+    /// complete. Can be empty.
+    pub base: String,
+}
@@ -0,0 +1,24 @@
+use nvim_types::{self as nvim, conversion::FromObject, serde::Serializer};
+use serde::Serialize;
+
+/// The type of dialog shown by [`api::confirm`](crate::confirm), which only
+/// changes its icon in the GUIs that support it.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+pub enum ConfirmType {
+    Error,
+    Question,
+    Info,
+    Warning,
+    Generic,
+}
+
+impl From<ConfirmType> for nvim::String {
+    fn from(ty: ConfirmType) -> Self {
+        nvim::String::from_object(
+            ty.serialize(Serializer::new())
+                .expect("`ConfirmType` is serializable"),
+        )
+        .expect("`ConfirmType` is serialized into a string")
+    }
+}
@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+/// The id of an extmark set via
+/// [`Buffer::set_extmark`](crate::Buffer::set_extmark). Ids are only unique
+/// within the namespace the extmark was set in.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ExtmarkId(pub(crate) u32);
+
+impl ExtmarkId {
+    /// Returns the raw id of the extmark.
+    #[inline]
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Creates an `ExtmarkId` from a raw id, e.g. one stored by Lua code. The id
+/// isn't checked, so the extmark may not exist.
+impl From<u32> for ExtmarkId {
+    #[inline]
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
@@ -1,6 +1,8 @@
 use nvim_types::{Array, Integer, Object};
 use serde::Deserialize;
 
+use super::ExtmarkId;
+
 #[non_exhaustive]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub enum ExtmarkPosition {
@@ -9,7 +11,7 @@ pub enum ExtmarkPosition {
     ByTuple((usize, usize)),
 
     /// Defines the extmark's position in the buffer by its id.
-    ById(u32),
+    ById(ExtmarkId),
 }
 
 impl From<ExtmarkPosition> for Object {
@@ -20,7 +22,7 @@ impl From<ExtmarkPosition> for Object {
             ByTuple((row, col)) => {
                 Array::from_iter([row as Integer, col as Integer]).into()
             },
-            ById(extmark_id) => extmark_id.into(),
+            ById(extmark_id) => extmark_id.0.into(),
         }
     }
 }
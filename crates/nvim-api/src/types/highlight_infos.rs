@@ -7,7 +7,7 @@ use serde::Deserialize;
 
 /// Attributes related to a highlight group.
 #[non_exhaustive]
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
 pub struct HighlightInfos {
     pub background: Option<u32>,
     pub bg_indexed: Option<bool>,
@@ -16,6 +16,10 @@ pub struct HighlightInfos {
     pub fg_indexed: Option<bool>,
     pub foreground: Option<u32>,
     pub italic: Option<bool>,
+    /// The group this group links to. Only set by
+    /// [`export_colorscheme`](crate::export_colorscheme) when links are
+    /// preserved.
+    pub link: Option<String>,
     pub reverse: Option<bool>,
     pub special: Option<u32>,
     pub standout: Option<bool>,
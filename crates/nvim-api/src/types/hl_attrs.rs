@@ -0,0 +1,121 @@
+use nvim_types::{conversion::FromObject, Dictionary};
+
+use crate::Result;
+
+/// The definition of a highlight group, returned by
+/// [`get_hl`](crate::get_hl). It can be turned back into a
+/// [`SetHighlightOpts`](crate::opts::SetHighlightOpts) to pass to
+/// [`set_hl`](crate::set_hl).
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HlAttrs {
+    /// The RGB foreground color.
+    pub fg: Option<u32>,
+
+    /// The RGB background color.
+    pub bg: Option<u32>,
+
+    /// The RGB color of underlines and undercurls.
+    pub sp: Option<u32>,
+
+    /// The foreground color in terminals without true colors.
+    pub ctermfg: Option<u8>,
+
+    /// The background color in terminals without true colors.
+    pub ctermbg: Option<u8>,
+
+    /// The blend level of floating windows and popup menus, between 0 and
+    /// 100.
+    pub blend: Option<u8>,
+
+    pub bold: bool,
+    pub standout: bool,
+    pub underline: bool,
+    pub undercurl: bool,
+    pub underdouble: bool,
+    pub underdotted: bool,
+    pub underdashed: bool,
+    pub strikethrough: bool,
+    pub italic: bool,
+    pub reverse: bool,
+    pub nocombine: bool,
+
+    /// The group this group links to. If set all the other fields are
+    /// left empty.
+    pub link: Option<String>,
+}
+
+impl HlAttrs {
+    /// Builds the attributes of a group from the dictionaries returned by
+    /// `nvim_get_hl_by_name` with `rgb` set to `true` and `false`.
+    pub(crate) fn from_dicts(
+        rgb: Dictionary,
+        cterm: Dictionary,
+    ) -> Result<Self> {
+        let mut attrs = Self::default();
+
+        for (key, value) in rgb {
+            let color = match key.as_bytes() {
+                b"foreground" => &mut attrs.fg,
+                b"background" => &mut attrs.bg,
+                b"special" => &mut attrs.sp,
+
+                b"blend" => {
+                    attrs.blend = Some(u8::from_object(value)?);
+                    continue;
+                },
+
+                _ => {
+                    if let Some(flag) = attrs.flag_mut(key.as_bytes()) {
+                        *flag = bool::from_object(value)?;
+                    }
+                    continue;
+                },
+            };
+
+            *color = Some(u32::from_object(value)?);
+        }
+
+        for (key, value) in cterm {
+            let color = match key.as_bytes() {
+                b"foreground" => &mut attrs.ctermfg,
+                b"background" => &mut attrs.ctermbg,
+                _ => continue,
+            };
+
+            *color = Some(u8::from_object(value)?);
+        }
+
+        Ok(attrs)
+    }
+
+    /// Returns the flag stored under `key`, accounting for the keys renamed
+    /// between Neovim versions.
+    fn flag_mut(&mut self, key: &[u8]) -> Option<&mut bool> {
+        let flag = match key {
+            b"bold" => &mut self.bold,
+            b"standout" => &mut self.standout,
+            b"underline" => &mut self.underline,
+            b"undercurl" => &mut self.undercurl,
+            #[cfg(feature = "neovim-0-7")]
+            b"underlineline" => &mut self.underdouble,
+            #[cfg(feature = "neovim-0-7")]
+            b"underdot" => &mut self.underdotted,
+            #[cfg(feature = "neovim-0-7")]
+            b"underdash" => &mut self.underdashed,
+            #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+            b"underdouble" => &mut self.underdouble,
+            #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+            b"underdotted" => &mut self.underdotted,
+            #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+            b"underdashed" => &mut self.underdashed,
+            b"strikethrough" => &mut self.strikethrough,
+            b"italic" => &mut self.italic,
+            b"reverse" => &mut self.reverse,
+            b"nocombine" => &mut self.nocombine,
+            _ => return None,
+        };
+
+        Some(flag)
+    }
+}
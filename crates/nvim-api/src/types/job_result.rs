@@ -0,0 +1,23 @@
+/// The result of waiting for a [`Job`](crate::Job) via
+/// [`Job::wait`](crate::Job::wait).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct JobResult {
+    /// The exit code of the job, or `None` if it was still running when the
+    /// timeout was reached.
+    pub code: Option<i32>,
+
+    /// Everything the job has written to its standard output.
+    pub stdout: String,
+
+    /// Everything the job has written to its standard error.
+    pub stderr: String,
+}
+
+impl JobResult {
+    /// Returns `true` if the job didn't exit before the timeout.
+    #[inline]
+    pub fn timed_out(&self) -> bool {
+        self.code.is_none()
+    }
+}
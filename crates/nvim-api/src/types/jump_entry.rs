@@ -0,0 +1,35 @@
+use nvim_types::{
+    conversion::{self, FromObject},
+    serde::Deserializer,
+    Object,
+};
+use serde::Deserialize;
+
+use crate::Buffer;
+
+/// An entry of a window's jumplist, as returned by
+/// [`Window::jumplist`](crate::Window::jumplist).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct JumpEntry {
+    /// The buffer the jump points to.
+    #[serde(rename = "bufnr")]
+    pub buffer: Buffer,
+
+    /// The 1-indexed line of the jump.
+    pub lnum: usize,
+
+    /// The 0-indexed byte column of the jump.
+    pub col: usize,
+
+    /// The offset in screen cells from `col` when
+    /// [`virtualedit`](https://neovim.io/doc/user/options.html#'virtualedit')
+    /// is active.
+    pub coladd: usize,
+}
+
+impl FromObject for JumpEntry {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
@@ -19,6 +19,10 @@ pub struct KeymapInfos {
     /// Optional callback triggered by the keymap.
     pub callback: Option<Function<(), ()>>,
 
+    /// The description of the keymap, if it has one.
+    #[serde(default)]
+    pub desc: Option<String>,
+
     /// Whether the keymap argument is an expression.
     #[serde(deserialize_with = "utils::bool_from_int")]
     pub expr: bool,
@@ -0,0 +1,13 @@
+/// The id of a match added to a window via
+/// [`Window::match_add`](crate::Window::match_add), used to delete it with
+/// [`Window::match_delete`](crate::Window::match_delete).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MatchId(pub(crate) u32);
+
+impl MatchId {
+    /// Returns the raw id of the match.
+    #[inline]
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+}
@@ -1,7 +1,13 @@
 //! Contains various types given to and returned from Neovim API functions.
 
+mod api_infos;
 mod autocmd_callback_args;
+mod autocmd_event;
 mod autocmd_infos;
+mod autocmd_pattern;
+#[cfg(feature = "unstable")]
+mod buf_stats;
+mod buffer_change;
 mod channel_infos;
 mod client_infos;
 mod cmd_infos;
@@ -14,16 +20,24 @@ mod command_infos;
 mod command_modifiers;
 mod command_nargs;
 mod command_range;
+mod completion_item;
+mod completion_request;
+mod confirm_type;
 mod context_type;
 mod editor_context;
 mod extmark_hl_mode;
+mod extmark_id;
 mod extmark_infos;
 mod extmark_position;
 mod extmark_virt_text_position;
 mod got_mode;
 mod highlight_infos;
+mod hl_attrs;
+mod job_result;
+mod jump_entry;
 mod keymap_infos;
 mod log_level;
+mod match_id;
 mod mode;
 mod mouse_action;
 mod mouse_button;
@@ -31,11 +45,16 @@ mod option_infos;
 mod parsed_viml_expression;
 mod paste_phase;
 mod proc_infos;
+mod region;
 mod register_type;
+mod screen_pos;
+mod split_direction;
 mod split_modifier;
 mod statusline_highlight_infos;
 mod statusline_infos;
+mod tag_stack;
 mod ui_infos;
+mod view;
 mod viml_ast_node;
 mod window_anchor;
 mod window_border;
@@ -44,8 +63,14 @@ mod window_config;
 mod window_relative_to;
 mod window_style;
 
+pub use api_infos::*;
 pub use autocmd_callback_args::*;
+pub use autocmd_event::*;
 pub use autocmd_infos::*;
+pub use autocmd_pattern::*;
+#[cfg(feature = "unstable")]
+pub use buf_stats::*;
+pub use buffer_change::*;
 pub use channel_infos::*;
 pub use client_infos::*;
 pub use cmd_infos::*;
@@ -58,16 +83,24 @@ pub use command_infos::*;
 pub use command_modifiers::*;
 pub use command_nargs::*;
 pub use command_range::*;
+pub use completion_item::*;
+pub use completion_request::*;
+pub use confirm_type::*;
 pub use context_type::*;
 pub use editor_context::*;
 pub use extmark_hl_mode::*;
+pub use extmark_id::*;
 pub use extmark_infos::*;
 pub use extmark_position::*;
 pub use extmark_virt_text_position::*;
 pub use got_mode::*;
 pub use highlight_infos::*;
+pub use hl_attrs::*;
+pub use job_result::*;
+pub use jump_entry::*;
 pub use keymap_infos::*;
 pub use log_level::*;
+pub use match_id::*;
 pub use mode::*;
 pub use mouse_action::*;
 pub use mouse_button::*;
@@ -75,11 +108,16 @@ pub use option_infos::*;
 pub use parsed_viml_expression::*;
 pub use paste_phase::*;
 pub use proc_infos::*;
+pub use region::*;
 pub use register_type::*;
+pub use screen_pos::*;
+pub use split_direction::*;
 pub use split_modifier::*;
 pub use statusline_highlight_infos::*;
 pub use statusline_infos::*;
+pub use tag_stack::*;
 pub use ui_infos::*;
+pub use view::*;
 pub use viml_ast_node::*;
 pub use window_anchor::*;
 pub use window_border::*;
@@ -3,37 +3,52 @@ use serde::Deserialize;
 
 #[non_exhaustive]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+/// The modes a keymap can be defined for. See
+/// [`map-modes`](https://neovim.io/doc/user/map.html#map-modes) for details.
 pub enum Mode {
+    /// Command-line mode, `c`.
     #[serde(rename = "c")]
     CmdLine,
 
+    /// Insert mode, `i`.
     #[serde(rename = "i")]
     Insert,
 
+    /// Insert and Command-line mode, `!`.
     #[serde(rename = "!")]
     InsertCmdLine,
 
+    /// Insert, Command-line and Lang-Arg mode, `l`.
     #[serde(rename = "l")]
     Langmap,
 
+    /// Normal, Visual, Select and Operator-pending mode, i.e. what
+    /// `:map` applies to.
     #[serde(rename(deserialize = " "))]
     NormalVisualOperator,
 
+    /// Normal mode, `n`.
     #[serde(rename = "n")]
     Normal,
 
+    /// Operator-pending mode, `o`.
     #[serde(rename = "o")]
     OperatorPending,
 
+    /// Select mode, `s`.
     #[serde(rename = "s")]
     Select,
 
+    /// Terminal mode, `t`.
     #[serde(rename = "t")]
     Terminal,
 
+    /// Visual mode, `x`. This covers the characterwise, linewise and
+    /// blockwise Visual modes, which can't be mapped separately.
     #[serde(rename = "x")]
     Visual,
 
+    /// Visual and Select mode, `v`.
     #[serde(rename = "v")]
     VisualSelect,
 }
@@ -1,6 +1,3 @@
-use std::cmp::Ordering;
-use std::collections::BTreeSet;
-
 use nvim_types::{
     conversion::{self, FromObject},
     serde::Deserializer,
@@ -51,9 +48,10 @@ pub struct ParseExpressionError {
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
 #[serde(from = "DeserializedVimLExpressionAST")]
 pub struct VimLExpressionAst {
-    /// A tree of child nodes.
+    /// The child nodes, in the order in which they appear in the
+    /// expression.
     #[serde(default)]
-    pub children: BTreeSet<VimLExpressionAst>,
+    pub children: Vec<VimLExpressionAst>,
 
     /// Length of the node.
     pub len: usize,
@@ -61,7 +59,7 @@ pub struct VimLExpressionAst {
     /// A `(line, column)` tuple describing where the the node is started.
     pub start: (usize, usize),
 
-    /// Error message in printf format. Contains exactly one `"%.*s"` block.
+    /// The type of the node.
     pub ty: VimLAstNode,
 }
 
@@ -85,7 +83,7 @@ struct DeserializedVimLExpressionAST {
     ccs_strategy: Option<ExprCaseCompareStrategy>,
 
     #[serde(default)]
-    children: BTreeSet<VimLExpressionAst>,
+    children: Vec<VimLExpressionAst>,
 
     #[serde(default)]
     cmp_type: Option<ExprComparisonType>,
@@ -211,23 +209,6 @@ impl From<DeserializedVimLExpressionAST> for VimLExpressionAst {
     }
 }
 
-impl Ord for VimLExpressionAst {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match (self.children.len(), other.children.len()) {
-            (l1, l2) if l1 < l2 => Ordering::Less,
-            (l1, l2) if l1 == l2 => Ordering::Equal,
-            (l1, l2) if l1 > l2 => Ordering::Greater,
-            _ => unreachable!(),
-        }
-    }
-}
-
-impl PartialOrd for VimLExpressionAst {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 impl FromObject for ParsedVimLExpression {
     fn from_object(obj: Object) -> Result<Self, conversion::Error> {
         Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
@@ -0,0 +1,50 @@
+/// The unit in which the column of a [`Position`] is expressed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ColumnUnit {
+    /// The column is a byte offset from the start of the line.
+    Byte,
+
+    /// The column is the number of characters (i.e. Unicode scalar values)
+    /// preceding the position on its line.
+    Char,
+}
+
+/// A 0-indexed position in a buffer.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+    pub unit: ColumnUnit,
+}
+
+impl Position {
+    /// Creates a new position whose column is a byte offset.
+    #[inline]
+    pub fn bytes(row: usize, col: usize) -> Self {
+        Self { row, col, unit: ColumnUnit::Byte }
+    }
+
+    /// Creates a new position whose column is a character offset.
+    #[inline]
+    pub fn chars(row: usize, col: usize) -> Self {
+        Self { row, col, unit: ColumnUnit::Char }
+    }
+}
+
+/// A region of a buffer going from `start` (inclusive) to `end`
+/// (exclusive), used by [`Buffer::get_region`](crate::Buffer::get_region)
+/// and [`Buffer::set_region`](crate::Buffer::set_region).
+///
+/// The two positions can use different [`ColumnUnit`]s.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Region {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Region {
+    #[inline]
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+}
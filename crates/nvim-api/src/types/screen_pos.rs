@@ -0,0 +1,34 @@
+use nvim_types::{
+    conversion::{self, FromObject},
+    serde::Deserializer,
+    Object,
+};
+use serde::Deserialize;
+
+/// The screen position of a buffer character, as returned by
+/// [`Window::screen_pos`](crate::Window::screen_pos).
+///
+/// All the values are 1-indexed screen cells.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ScreenPos {
+    /// The screen row of the character.
+    pub row: u32,
+
+    /// The first screen column of the character.
+    pub col: u32,
+
+    /// The last screen column of the character. Differs from
+    /// [`col`](ScreenPos::col) for wide characters and tabs.
+    pub endcol: u32,
+
+    /// The screen column where the cursor would be placed when on the
+    /// character.
+    pub curscol: u32,
+}
+
+impl FromObject for ScreenPos {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
@@ -0,0 +1,26 @@
+use nvim_types::Object;
+use serde::Deserialize;
+
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Specifies where a split window is opened relative to the window being
+/// split.
+pub enum SplitDirection {
+    Left,
+    Right,
+    Above,
+    Below,
+}
+
+impl From<SplitDirection> for Object {
+    fn from(direction: SplitDirection) -> Self {
+        use SplitDirection::*;
+        Self::from(match direction {
+            Left => "left",
+            Right => "right",
+            Above => "above",
+            Below => "below",
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use nvim_types::{
+    conversion::{self, FromObject},
+    serde::Deserializer,
+    Object,
+};
+use serde::Deserialize;
+
+use crate::Buffer;
+
+/// A window's tag stack, as returned by
+/// [`Window::tagstack`](crate::Window::tagstack).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct TagStack {
+    /// The 1-indexed position of the current entry in
+    /// [`items`](TagStack::items). It's one past the last item when at the
+    /// top of the stack.
+    #[serde(rename = "curidx")]
+    pub current: usize,
+
+    /// The entries of the stack, from the bottom to the top.
+    pub items: Vec<TagStackItem>,
+}
+
+/// An entry of a [`TagStack`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct TagStackItem {
+    /// The name of the tag that was jumped to.
+    pub tagname: String,
+
+    /// The buffer containing the tag.
+    #[serde(rename = "bufnr")]
+    pub buffer: Buffer,
+
+    /// The 1-indexed number of the current match for the tag, when there
+    /// were multiple matches.
+    pub matchnr: usize,
+
+    /// The position the tag was jumped from.
+    pub from: TagOrigin,
+}
+
+/// The position a tag was jumped from.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(from = "(Buffer, usize, usize, usize)")]
+pub struct TagOrigin {
+    /// The buffer the tag was jumped from.
+    pub buffer: Buffer,
+
+    /// The 1-indexed line the tag was jumped from.
+    pub lnum: usize,
+
+    /// The 0-indexed byte column the tag was jumped from.
+    pub col: usize,
+}
+
+impl From<(Buffer, usize, usize, usize)> for TagOrigin {
+    fn from((buffer, lnum, col, _off): (Buffer, usize, usize, usize)) -> Self {
+        // The column is 1-indexed, like the ones returned by `getpos()`.
+        Self { buffer, lnum, col: col.saturating_sub(1) }
+    }
+}
+
+impl FromObject for TagStack {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
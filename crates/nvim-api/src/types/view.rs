@@ -0,0 +1,58 @@
+use nvim_types::{
+    conversion::{self, FromObject},
+    serde::Deserializer,
+    Dictionary,
+    Integer,
+    Object,
+};
+use serde::Deserialize;
+
+/// The view of a window, as returned by
+/// [`Window::save_view`](crate::Window::save_view).
+///
+/// Besides the cursor position it also captures how the window is scrolled,
+/// so passing it to [`Window::restore_view`](crate::Window::restore_view)
+/// brings the window back to exactly the same state.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct View {
+    /// The 1-indexed line of the cursor.
+    pub lnum: usize,
+
+    /// The 0-indexed byte column of the cursor.
+    pub col: usize,
+
+    /// The cursor's offset in screen cells from `col` when
+    /// [`virtualedit`](https://neovim.io/doc/user/options.html#'virtualedit')
+    /// is active.
+    pub coladd: usize,
+
+    /// The preferred screen column of the cursor for vertical movements.
+    pub curswant: usize,
+
+    /// The first line displayed in the window.
+    pub topline: usize,
+
+    /// The first screen column displayed in the window. Only relevant when
+    /// [`wrap`](https://neovim.io/doc/user/options.html#'wrap') is off.
+    pub leftcol: usize,
+}
+
+impl FromObject for View {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
+
+impl From<&View> for Dictionary {
+    fn from(view: &View) -> Self {
+        Dictionary::from_iter([
+            ("lnum", view.lnum as Integer),
+            ("col", view.col as Integer),
+            ("coladd", view.coladd as Integer),
+            ("curswant", view.curswant as Integer),
+            ("topline", view.topline as Integer),
+            ("leftcol", view.leftcol as Integer),
+        ])
+    }
+}
@@ -9,7 +9,14 @@ use nvim_types::{
 };
 use serde::Deserialize;
 
-use super::{WindowAnchor, WindowBorder, WindowRelativeTo, WindowStyle};
+use super::{
+    SplitDirection,
+    WindowAnchor,
+    WindowBorder,
+    WindowRelativeTo,
+    WindowStyle,
+};
+use crate::Window;
 
 #[non_exhaustive]
 #[derive(Clone, Debug, Default, PartialEq, Builder, Deserialize)]
@@ -62,6 +69,16 @@ pub struct WindowConfig {
     #[builder(setter(into, strip_option))]
     pub row: Option<Float>,
 
+    /// Opens a split window instead of a floating one, placed in this
+    /// direction relative to [`win`](WindowConfigBuilder::win). Can't be
+    /// used together with [`relative`](WindowConfigBuilder::relative). The
+    /// size of the split is given by [`width`](WindowConfigBuilder::width)
+    /// for vertical splits and by [`height`](WindowConfigBuilder::height)
+    /// for horizontal ones. Only used by [`open_win`](crate::open_win).
+    #[builder(setter(strip_option))]
+    #[serde(skip)]
+    pub split: Option<SplitDirection>,
+
     /// Configures the appearance of the window.
     #[builder(setter(strip_option))]
     pub style: Option<WindowStyle>,
@@ -70,6 +87,12 @@ pub struct WindowConfig {
     #[builder(setter(strip_option))]
     pub width: Option<u32>,
 
+    /// The window to split when [`split`](WindowConfigBuilder::split) is
+    /// set. Defaults to the current window.
+    #[builder(setter(strip_option))]
+    #[serde(skip)]
+    pub win: Option<Window>,
+
     /// Stacking order. Windows with higher `zindex` go in front of windows
     /// with lower indices.
     #[builder(setter(strip_option))]
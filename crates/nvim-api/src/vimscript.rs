@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use nvim_types::{self as nvim, conversion::FromObject, Array, Object};
 
 use crate::choose;
@@ -119,6 +121,29 @@ pub fn exec(src: &str, output: bool) -> Result<Option<String>> {
     })
 }
 
+/// Binding to the [`expand()`](https://neovim.io/doc/user/builtin.html#expand())
+/// Vimscript function.
+///
+/// Expands wildcards and special keywords like `%`, `#` and `<cfile>`,
+/// optionally followed by filename modifiers like `:p` or `:h`. A leading
+/// `~` is expanded to the home directory.
+pub fn expand(expr: &str) -> Result<String> {
+    call_function("expand", (expr,))
+}
+
+/// Binding to the
+/// [`fnamemodify()`](https://neovim.io/doc/user/builtin.html#fnamemodify())
+/// Vimscript function.
+///
+/// Applies the filename modifiers `mods` (e.g. `":p:h"`) to `path`.
+pub fn fnamemodify<P>(path: P, mods: &str) -> Result<String>
+where
+    P: AsRef<Path>,
+{
+    let path = nvim::String::from(path.as_ref().to_owned());
+    call_function("fnamemodify", (path, mods))
+}
+
 /// Binding to [`nvim_parse_cmd`](https://neovim.io/doc/user/api.html#nvim_parse_cmd()).
 ///
 /// Parses the command line.
@@ -1,25 +1,190 @@
-use nvim_types::{self as nvim, conversion::FromObject};
+use nvim_types::{self as nvim, conversion::FromObject, Float};
 
 use crate::choose;
 use crate::ffi::win_config::*;
 use crate::types::*;
-use crate::Result;
 use crate::{Buffer, Window};
+use crate::{Error, Result};
 
 /// Binding to [`nvim_open_win`](https://neovim.io/doc/user/api.html#nvim_open_win()).
 ///
-/// Opens a new floating or external window.
+/// Opens a new floating or external window, or a split window if the
+/// [`split`](WindowConfig::split) field of `config` is set.
 pub fn open_win(
     buf: &Buffer,
     enter: bool,
     config: &WindowConfig,
 ) -> Result<Window> {
+    if let Some(direction) = config.split {
+        return open_split(buf, enter, config, direction);
+    }
+
     let mut err = nvim::Error::new();
     let handle =
         unsafe { nvim_open_win(buf.0, enter, &config.into(), &mut err) };
     choose!(err, Ok(handle.into()))
 }
 
+/// Opens a split window via
+/// [`:split`](https://neovim.io/doc/user/windows.html#%3Asplit) or
+/// [`:vsplit`](https://neovim.io/doc/user/windows.html#%3Avsplit), since
+/// `nvim_open_win` can only open floating windows before Neovim 0.10.
+fn open_split(
+    buf: &Buffer,
+    enter: bool,
+    config: &WindowConfig,
+    direction: SplitDirection,
+) -> Result<Window> {
+    if config.relative.is_some() {
+        return Err(Error::custom(
+            "`relative` can't be set when opening a split window",
+        ));
+    }
+
+    let (modifier, size, split) = match direction {
+        SplitDirection::Left => ("aboveleft", config.width, "vsplit"),
+        SplitDirection::Right => ("belowright", config.width, "vsplit"),
+        SplitDirection::Above => ("aboveleft", config.height, "split"),
+        SplitDirection::Below => ("belowright", config.height, "split"),
+    };
+
+    let size = size.map(|size| size.to_string()).unwrap_or_default();
+    let command = format!("{modifier} {size}{split}");
+    let parent = config.win.clone().unwrap_or_else(Window::current);
+
+    crate::global::with_noautocmd(config.noautocmd.unwrap_or(false), || {
+        // The new window becomes the current one until `nvim_win_call`
+        // switches back to the window that was current before.
+        let mut win = parent.call(move |()| {
+            crate::command(&command)?;
+            Ok(Window::current())
+        })?;
+
+        win.set_buf(buf)?;
+
+        if enter {
+            crate::set_current_win(&win)?;
+        }
+
+        Ok(win)
+    })
+}
+
+/// A builder for floating windows, validating the configuration before
+/// opening them via [`open_win`].
+///
+/// Unlike a bare [`WindowConfig`], which Neovim rejects if `relative`,
+/// `width` or `height` are missing, [`open`](Self::open) fails early with a
+/// descriptive error listing the missing fields.
+#[derive(Clone, Debug, Default)]
+pub struct FloatWinBuilder {
+    config: WindowConfig,
+    enter: bool,
+}
+
+impl FloatWinBuilder {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decides which corner of the window to place at `(row, col)`.
+    pub fn anchor(&mut self, anchor: WindowAnchor) -> &mut Self {
+        self.config.anchor = Some(anchor);
+        self
+    }
+
+    /// Style of the window border.
+    pub fn border(&mut self, border: WindowBorder) -> &mut Self {
+        self.config.border = Some(border);
+        self
+    }
+
+    /// Column position in units of screen cell width.
+    pub fn col<C: Into<Float>>(&mut self, col: C) -> &mut Self {
+        self.config.col = Some(col.into());
+        self
+    }
+
+    /// Whether the window should become the current window once opened.
+    /// Defaults to `false`.
+    pub fn enter(&mut self, enter: bool) -> &mut Self {
+        self.enter = enter;
+        self
+    }
+
+    /// Whether the window can be focused by user actions.
+    pub fn focusable(&mut self, focusable: bool) -> &mut Self {
+        self.config.focusable = Some(focusable);
+        self
+    }
+
+    /// Window height in character cells. Required.
+    pub fn height(&mut self, height: u32) -> &mut Self {
+        self.config.height = Some(height);
+        self
+    }
+
+    /// What the window is positioned relative to. Required.
+    pub fn relative(&mut self, relative: WindowRelativeTo) -> &mut Self {
+        self.config.relative = Some(relative);
+        self
+    }
+
+    /// Row position in units of screen cell height.
+    pub fn row<R: Into<Float>>(&mut self, row: R) -> &mut Self {
+        self.config.row = Some(row.into());
+        self
+    }
+
+    /// Configures the appearance of the window.
+    pub fn style(&mut self, style: WindowStyle) -> &mut Self {
+        self.config.style = Some(style);
+        self
+    }
+
+    /// Window width in character cells. Required.
+    pub fn width(&mut self, width: u32) -> &mut Self {
+        self.config.width = Some(width);
+        self
+    }
+
+    /// Stacking order. Windows with higher `zindex` go in front of windows
+    /// with lower indices.
+    pub fn zindex(&mut self, zindex: u32) -> &mut Self {
+        self.config.zindex = Some(zindex);
+        self
+    }
+
+    /// Returns the configuration built so far.
+    #[inline]
+    pub fn config(&self) -> &WindowConfig {
+        &self.config
+    }
+
+    /// Opens a floating window displaying `buf`, failing without calling
+    /// Neovim if any of `relative`, `width` or `height` is missing.
+    pub fn open(&self, buf: &Buffer) -> Result<Window> {
+        let missing = [
+            ("relative", self.config.relative.is_none()),
+            ("width", self.config.width.is_none()),
+            ("height", self.config.height.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(field, is_missing)| is_missing.then_some(field))
+        .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            return Err(Error::custom(format!(
+                "Floating window is missing required fields: {}",
+                missing.join(", ")
+            )));
+        }
+
+        open_win(buf, self.enter, &self.config)
+    }
+}
+
 impl Window {
     /// Binding to [`nvim_win_get_config`](https://neovim.io/doc/user/api.html#nvim_win_get_config()).
     ///
@@ -46,4 +211,21 @@ impl Window {
         unsafe { nvim_win_set_config(self.0, &config.into(), &mut err) };
         choose!(err, ())
     }
+
+    /// Updates the window configuration in place.
+    ///
+    /// Gets the current configuration with [`get_config`](Self::get_config),
+    /// passes it to `fun` to be modified and then sets it back with
+    /// [`set_config`](Self::set_config). The fields that `fun` doesn't touch
+    /// keep their current values, which makes this convenient when only a
+    /// couple of fields change at a time, e.g. when animating a floating
+    /// window by moving it a bit on every frame.
+    pub fn update_config<F>(&mut self, fun: F) -> Result<()>
+    where
+        F: FnOnce(&mut WindowConfig),
+    {
+        let mut config = self.get_config()?;
+        fun(&mut config);
+        self.set_config(&config)
+    }
 }
@@ -5,7 +5,9 @@ use luajit_bindings::{self as lua, Poppable, Pushable};
 use nvim_types::{
     self as nvim,
     conversion::{self, FromObject, ToObject},
+    serde::Deserializer,
     Array,
+    Dictionary,
     Function,
     Integer,
     Object,
@@ -15,9 +17,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::choose;
 use crate::ffi::window::*;
-use crate::Result;
+use crate::opts::MatchAddOpts;
+use crate::types::{JumpEntry, MatchId, ScreenPos, TagStack, View};
 use crate::LUA_INTERNAL_CALL;
 use crate::{Buffer, TabPage};
+use crate::{Error, Result};
 
 /// A wrapper around a Neovim window handle.
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -204,7 +208,8 @@ impl Window {
 
     /// Binding to [`nvim_win_get_var`](https://neovim.io/doc/user/api.html#nvim_win_get_var()).
     ///
-    /// Gets a window-scoped (`w:`) variable.
+    /// Gets a window-scoped (`w:`) variable. Fails with
+    /// [`Error::VarNotFound`] if the variable isn't set.
     pub fn get_var<Var>(&self, name: &str) -> Result<Var>
     where
         Var: FromObject,
@@ -213,7 +218,10 @@ impl Window {
         let name = nvim::String::from(name);
         let obj =
             unsafe { nvim_win_get_var(self.0, name.non_owning(), &mut err) };
-        choose!(err, Ok(Var::from_object(obj)?))
+        if err.is_err() {
+            return Err(Error::from_get_var(err, &name, self.is_valid()));
+        }
+        Ok(Var::from_object(obj)?)
     }
 
     /// Binding to [`nvim_win_get_width`](https://neovim.io/doc/user/api.html#nvim_win_get_width()).
@@ -234,6 +242,17 @@ impl Window {
         choose!(err, ())
     }
 
+    /// Returns whether the window-scoped (`w:`) variable `name` is set.
+    /// Unlike [`get_var`](Self::get_var), this doesn't fail if the variable
+    /// doesn't exist.
+    pub fn has_var(&self, name: &str) -> bool {
+        let mut err = nvim::Error::new();
+        let name = nvim::String::from(name);
+        let _ =
+            unsafe { nvim_win_get_var(self.0, name.non_owning(), &mut err) };
+        !err.is_err()
+    }
+
     /// Binding to [`nvim_win_is_valid`](https://neovim.io/doc/user/api.html#nvim_win_is_valid()).
     ///
     /// Checks if the window is valid.
@@ -241,6 +260,111 @@ impl Window {
         unsafe { nvim_win_is_valid(self.0) }
     }
 
+    /// Binding to [`getjumplist()`](https://neovim.io/doc/user/builtin.html#getjumplist()).
+    ///
+    /// Returns the entries of the window's jumplist, from the oldest to the
+    /// newest, together with the index of the current position in it. The
+    /// index is equal to the number of entries when not jumping back through
+    /// the list.
+    pub fn jumplist(&self) -> Result<(Vec<JumpEntry>, usize)> {
+        let list =
+            crate::call_function::<_, Object>("getjumplist", (self.clone(),))?;
+        Ok(<(Vec<JumpEntry>, usize)>::deserialize(Deserializer::new(list))
+            .map_err(conversion::Error::from)?)
+    }
+
+    /// Binding to [`matchadd()`](https://neovim.io/doc/user/builtin.html#matchadd()).
+    ///
+    /// Highlights every occurrence of the `pattern` regex in the window with
+    /// the `group` highlight group. Matches with a higher `priority` are
+    /// drawn on top of the ones with a lower priority, and the search
+    /// highlighting has priority 0.
+    pub fn match_add(
+        &mut self,
+        group: &str,
+        pattern: &str,
+        priority: u32,
+        opts: &MatchAddOpts,
+    ) -> Result<MatchId> {
+        let id = opts.id.map_or(-1, Integer::from);
+        // `matchadd()` expects `conceal` to be a string if it's given.
+        let conceal = opts.conceal.map(|ch| ("conceal", Object::from(ch)));
+        let dict = [("window", Object::from(self.clone()))]
+            .into_iter()
+            .chain(conceal)
+            .collect::<Dictionary>();
+
+        let id = crate::call_function::<_, Integer>(
+            "matchadd",
+            (group, pattern, priority as Integer, id, dict),
+        )?;
+
+        match id {
+            -1 => Err(Error::custom("Couldn't add the match")),
+            id => Ok(MatchId(id.try_into()?)),
+        }
+    }
+
+    /// Binding to [`matchdelete()`](https://neovim.io/doc/user/builtin.html#matchdelete()).
+    ///
+    /// Deletes a match previously added with
+    /// [`match_add`](Window::match_add).
+    pub fn match_delete(&mut self, id: MatchId) -> Result<()> {
+        match crate::call_function::<_, Integer>(
+            "matchdelete",
+            (id.0 as Integer, self.clone()),
+        )? {
+            0 => Ok(()),
+            _ => Err(Error::custom(format!(
+                "Couldn't delete match with id {}",
+                id.0
+            ))),
+        }
+    }
+
+    /// Binding to [`winrestview()`](https://neovim.io/doc/user/builtin.html#winrestview()).
+    ///
+    /// Restores a view previously saved with
+    /// [`save_view`](Window::save_view), including both the cursor position
+    /// and how the window was scrolled.
+    pub fn restore_view(&mut self, view: &View) -> Result<()> {
+        let view = Dictionary::from(view);
+        self.call(move |()| {
+            crate::call_function::<_, Integer>("winrestview", (view,))
+                .map(|_| ())
+        })
+    }
+
+    /// Binding to [`winsaveview()`](https://neovim.io/doc/user/builtin.html#winsaveview()).
+    ///
+    /// Saves the view of the window, which can later be restored with
+    /// [`restore_view`](Window::restore_view).
+    pub fn save_view(&self) -> Result<View> {
+        let view = self.call(|()| {
+            crate::call_function::<_, Object>("winsaveview", Array::new())
+        })?;
+        Ok(View::from_object(view)?)
+    }
+
+    /// Binding to [`screenpos()`](https://neovim.io/doc/user/builtin.html#screenpos()).
+    ///
+    /// Returns the screen position of the character at the given
+    /// (1,0)-indexed buffer position, or `None` if it's not currently
+    /// visible in the window (e.g. it's scrolled out of view or inside a
+    /// closed fold). Wrapped lines, folds and concealed text are all taken
+    /// into account by Neovim.
+    pub fn screen_pos(
+        &self,
+        line: usize,
+        col: usize,
+    ) -> Result<Option<ScreenPos>> {
+        let pos = crate::call_function::<_, ScreenPos>(
+            "screenpos",
+            (self.clone(), line as Integer, col as Integer + 1),
+        )?;
+        Ok((pos.row != 0).then_some(pos))
+    }
+
     /// Binding to [`nvim_win_set_buf`](https://neovim.io/doc/user/api.html#nvim_win_set_buf()).
     ///
     /// Sets `buffer` as the current buffer in the window.
@@ -261,6 +385,39 @@ impl Window {
         choose!(err, ())
     }
 
+    /// Sets the cursor to the 1-indexed `line` and the 0-indexed display
+    /// column `virtcol`, which is converted to the corresponding byte column
+    /// taking multibyte characters and tabs into account.
+    ///
+    /// If `virtcol` is past the end of the line the cursor is only placed
+    /// there if allowed by
+    /// [`'virtualedit'`](https://neovim.io/doc/user/options.html#'virtualedit'),
+    /// otherwise it's clamped to the last character.
+    pub fn set_cursor_virtual(
+        &mut self,
+        line: usize,
+        virtcol: usize,
+    ) -> Result<()> {
+        self.set_cursor(line, 0)?;
+        // The `|` motion moves to a screen column and honors 'virtualedit'.
+        self.call(move |()| {
+            crate::command(&format!("normal! {}|", virtcol + 1))
+        })
+    }
+
+    /// Like [`set_cursor`](Window::set_cursor), but also opens the folds
+    /// enclosing the new cursor position so that it's visible. Behaves
+    /// exactly like `set_cursor` if the line is not inside a closed fold.
+    pub fn set_cursor_open_folds(
+        &mut self,
+        line: usize,
+        col: usize,
+    ) -> Result<()> {
+        self.set_cursor(line, col)?;
+        // `zv` opens just enough folds to make the cursor line visible.
+        self.call(|()| crate::command("normal! zv"))
+    }
+
     /// Binding to [`nvim_win_set_height`](https://neovim.io/doc/user/api.html#nvim_win_set_height()).
     ///
     /// Sets the window height.
@@ -320,4 +477,22 @@ impl Window {
         unsafe { nvim_win_set_width(self.0, width.into(), &mut err) };
         choose!(err, ())
     }
+
+    /// Binding to [`gettagstack()`](https://neovim.io/doc/user/builtin.html#gettagstack()).
+    ///
+    /// Returns the window's tag stack.
+    pub fn tagstack(&self) -> Result<TagStack> {
+        crate::call_function("gettagstack", (self.clone(),))
+    }
+
+    /// Binding to [`virtcol()`](https://neovim.io/doc/user/builtin.html#virtcol()).
+    ///
+    /// Returns the 1-indexed display column of the last screen cell occupied
+    /// by the character at the given (1,0)-indexed buffer position in this
+    /// window. Unlike byte columns it takes into account tabs (expanded
+    /// according to the buffer's `tabstop`) and double-width characters.
+    pub fn virtcol(&self, line: usize, col: usize) -> Result<usize> {
+        let pos = Array::from_iter([line as Integer, col as Integer + 1]);
+        self.call(move |()| crate::call_function("virtcol", (pos,)))
+    }
 }
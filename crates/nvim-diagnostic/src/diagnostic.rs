@@ -1,32 +1,129 @@
-use std::error::Error;
+use std::ffi::{c_char, c_int};
 
-use luajit_bindings::{self as lua, ffi::*, macros::cstr, Pushable};
-use nvim_api::Buffer;
+use luajit_bindings::{self as lua, ffi::*, macros::cstr, Poppable, Pushable};
+use nvim_api::{Buffer, Window};
+use nvim_types::{Array, Dictionary};
+
+use crate::opts::*;
+use crate::types::*;
+use crate::Result;
 
 /// Binding to [`vim.diagnostic.enable`][1].
 ///
 /// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.enable()
-pub fn enable(
+pub fn enable(buffer: &Buffer, namespace: Option<u32>) -> Result<()> {
+    call(cstr!("enable"), (buffer.clone(), namespace), 1)
+}
+
+/// Binding to [`vim.diagnostic.get`][1].
+///
+/// Returns the diagnostics of `buffer`, or of all the buffers if it's
+/// `None`.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.get()
+pub fn get(
+    buffer: Option<&Buffer>,
+    opts: &GetOpts,
+) -> Result<Vec<Diagnostic>> {
+    call(cstr!("get"), (buffer.cloned(), Dictionary::from(opts)), 1)
+}
+
+/// Binding to [`vim.diagnostic.goto_next`][1].
+///
+/// Moves the cursor to the next diagnostic.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.goto_next()
+pub fn goto_next(opts: &GotoOpts) -> Result<()> {
+    call(cstr!("goto_next"), Dictionary::from(opts), 1)
+}
+
+/// Binding to [`vim.diagnostic.goto_prev`][1].
+///
+/// Moves the cursor to the previous diagnostic.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.goto_prev()
+pub fn goto_prev(opts: &GotoOpts) -> Result<()> {
+    call(cstr!("goto_prev"), Dictionary::from(opts), 1)
+}
+
+/// Binding to [`vim.diagnostic.open_float`][1].
+///
+/// Shows the diagnostics in a floating window, returning the window or `None`
+/// if there were no diagnostics to show.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.open_float()
+pub fn open_float(opts: &OpenFloatOpts) -> Result<Option<Window>> {
+    call::<_, (Option<Buffer>, Option<Window>)>(
+        cstr!("open_float"),
+        Dictionary::from(opts),
+        2,
+    )
+    .map(|(_, window)| window)
+}
+
+/// Binding to [`vim.diagnostic.reset`][1].
+///
+/// Removes the diagnostics of `buffer` in the given namespace, clearing
+/// their signs, underlines and virtual text.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.reset()
+pub fn reset(namespace: u32, buffer: &Buffer) -> Result<()> {
+    call(cstr!("reset"), (namespace, buffer.clone()), 1)
+}
+
+/// Binding to [`vim.diagnostic.set`][1].
+///
+/// Sets the diagnostics of `buffer` for the given namespace, replacing the
+/// existing ones.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.set()
+pub fn set<D>(
+    namespace: u32,
     buffer: &Buffer,
-    namespace: Option<u32>,
-) -> Result<(), Box<dyn Error> /* TODO: actual error */> {
-    let res = unsafe {
-        lua::with_state(|lua_state| {
-            lua_getglobal(lua_state, cstr!("vim"));
-            lua_getfield(lua_state, -1, cstr!("diagnostic"));
-            lua_getfield(lua_state, -1, cstr!("enable"));
-            let _ = buffer.clone().push(lua_state);
-            let _ = namespace.push(lua_state);
-            lua_pcall(lua_state, 2, 0, 0)
-        })
-    };
+    diagnostics: D,
+    opts: &DiagnosticOpts,
+) -> Result<()>
+where
+    D: IntoIterator<Item = Diagnostic>,
+{
+    let diagnostics = diagnostics.into_iter().collect::<Array>();
+    let opts = Dictionary::from(opts);
+    call(cstr!("set"), (namespace, buffer.clone(), diagnostics, opts), 1)
+}
+
+/// Calls `vim.diagnostic.{fun}` with the given arguments, popping the first
+/// `nresults` values it returns.
+fn call<A, R>(fun: *const c_char, args: A, nresults: c_int) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+
+            lua_getglobal(lstate, cstr!("vim"));
+            lua_getfield(lstate, -1, cstr!("diagnostic"));
+            lua_getfield(lstate, -1, fun);
+
+            let res = match args.push(lstate) {
+                Ok(nargs) => match lua_pcall(lstate, nargs, nresults, 0) {
+                    LUA_OK => R::pop(lstate),
 
-    match res {
-        LUA_OK => Ok(()),
+                    _err_code => {
+                        let msg = lua::utils::error_message(lstate, -1);
 
-        _err_code => {
-            // TODO: error handling
-            todo!()
-        },
+                        Err(lua::Error::RuntimeError(msg))
+                    },
+                },
+
+                Err(err) => Err(err),
+            };
+
+            // Pop `vim`, `vim.diagnostic` and anything that was left over.
+            lua_settop(lstate, top);
+
+            res
+        })
     }
 }
@@ -1,5 +1,12 @@
+//! Bindings to Neovim's [diagnostic framework][diagnostic], exposed in Lua
+//! through the `vim.diagnostic` table.
+//!
+//! [diagnostic]: https://neovim.io/doc/user/diagnostic.html
+
 mod diagnostic;
-mod opts;
+pub mod opts;
+pub mod types;
 
 pub use diagnostic::*;
-pub use opts::*;
+
+type Result<T> = std::result::Result<T, luajit_bindings::Error>;
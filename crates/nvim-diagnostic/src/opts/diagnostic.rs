@@ -0,0 +1,56 @@
+use derive_builder::Builder;
+use nvim_types::Dictionary;
+
+/// Options passed to [`set`](crate::set), configuring how the diagnostics
+/// are displayed. See `:h vim.diagnostic.config()` for the defaults.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct DiagnosticOpts {
+    /// Whether to show the diagnostics in the sign column.
+    #[builder(setter(strip_option))]
+    signs: Option<bool>,
+
+    /// Whether to sort the diagnostics by severity, showing the most severe
+    /// ones first.
+    #[builder(setter(strip_option))]
+    severity_sort: Option<bool>,
+
+    /// Whether to underline the text the diagnostics refer to.
+    #[builder(setter(strip_option))]
+    underline: Option<bool>,
+
+    /// Whether to update the diagnostics in Insert mode, instead of waiting
+    /// for the `InsertLeave` event.
+    #[builder(setter(strip_option))]
+    update_in_insert: Option<bool>,
+
+    /// Whether to show the diagnostics' messages as virtual text.
+    #[builder(setter(strip_option))]
+    virtual_text: Option<bool>,
+}
+
+impl DiagnosticOpts {
+    #[inline(always)]
+    /// Creates a new [`DiagnosticOptsBuilder`].
+    pub fn builder() -> DiagnosticOptsBuilder {
+        DiagnosticOptsBuilder::default()
+    }
+}
+
+impl DiagnosticOptsBuilder {
+    pub fn build(&mut self) -> DiagnosticOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
+
+impl From<&DiagnosticOpts> for Dictionary {
+    fn from(opts: &DiagnosticOpts) -> Self {
+        Self::from_iter([
+            ("signs", opts.signs),
+            ("severity_sort", opts.severity_sort),
+            ("underline", opts.underline),
+            ("update_in_insert", opts.update_in_insert),
+            ("virtual_text", opts.virtual_text),
+        ])
+    }
+}
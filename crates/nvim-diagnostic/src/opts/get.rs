@@ -0,0 +1,57 @@
+use derive_builder::Builder;
+use nvim_types::{Dictionary, Object};
+
+use crate::types::DiagnosticSeverity;
+
+/// Options passed to [`get`](crate::get).
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct GetOpts {
+    /// Only get the diagnostics on this line (0-indexed).
+    #[builder(setter(strip_option))]
+    lnum: Option<usize>,
+
+    /// Only get the diagnostics of this namespace.
+    #[builder(setter(strip_option))]
+    namespace: Option<u32>,
+
+    #[builder(setter(custom))]
+    severity: Object,
+}
+
+impl GetOpts {
+    #[inline(always)]
+    /// Creates a new [`GetOptsBuilder`].
+    pub fn builder() -> GetOptsBuilder {
+        GetOptsBuilder::default()
+    }
+}
+
+impl GetOptsBuilder {
+    /// Only get the diagnostics with the given severity.
+    pub fn severity(&mut self, severity: DiagnosticSeverity) -> &mut Self {
+        self.severity = Some(severity.into());
+        self
+    }
+
+    /// Only get the diagnostics at least as severe as `severity`.
+    pub fn min_severity(&mut self, severity: DiagnosticSeverity) -> &mut Self {
+        self.severity =
+            Some(Dictionary::from_iter([("min", severity)]).into());
+        self
+    }
+
+    pub fn build(&mut self) -> GetOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
+
+impl From<&GetOpts> for Dictionary {
+    fn from(opts: &GetOpts) -> Self {
+        Self::from_iter([
+            ("lnum", opts.lnum.map(|n| n as i64).into()),
+            ("namespace", opts.namespace.into()),
+            ("severity", opts.severity.clone()),
+        ])
+    }
+}
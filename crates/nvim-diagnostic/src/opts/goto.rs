@@ -0,0 +1,77 @@
+use derive_builder::Builder;
+use nvim_api::Window;
+use nvim_types::{Dictionary, Object};
+
+use crate::types::DiagnosticSeverity;
+
+/// Options passed to [`goto_next`](crate::goto_next) and
+/// [`goto_prev`](crate::goto_prev).
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct GotoOpts {
+    /// Whether to open a floating window showing the diagnostic after moving
+    /// to it. Defaults to `true`.
+    #[builder(setter(strip_option))]
+    float: Option<bool>,
+
+    /// Only consider the diagnostics of this namespace.
+    #[builder(setter(strip_option))]
+    namespace: Option<u32>,
+
+    #[builder(setter(custom))]
+    severity: Object,
+
+    #[builder(setter(custom))]
+    win_id: Object,
+
+    /// Whether to wrap around the start or the end of the buffer when there
+    /// are no more diagnostics in the search direction. Defaults to `true`.
+    #[builder(setter(strip_option))]
+    wrap: Option<bool>,
+}
+
+impl GotoOpts {
+    #[inline(always)]
+    /// Creates a new [`GotoOptsBuilder`].
+    pub fn builder() -> GotoOptsBuilder {
+        GotoOptsBuilder::default()
+    }
+}
+
+impl GotoOptsBuilder {
+    /// Only jump to the diagnostics with the given severity.
+    pub fn severity(&mut self, severity: DiagnosticSeverity) -> &mut Self {
+        self.severity = Some(severity.into());
+        self
+    }
+
+    /// Only jump to the diagnostics at least as severe as `severity`.
+    pub fn min_severity(&mut self, severity: DiagnosticSeverity) -> &mut Self {
+        self.severity =
+            Some(Dictionary::from_iter([("min", severity)]).into());
+        self
+    }
+
+    /// The window in which to move the cursor. Defaults to the current
+    /// window.
+    pub fn win_id(&mut self, window: Window) -> &mut Self {
+        self.win_id = Some(window.into());
+        self
+    }
+
+    pub fn build(&mut self) -> GotoOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
+
+impl From<&GotoOpts> for Dictionary {
+    fn from(opts: &GotoOpts) -> Self {
+        Self::from_iter([
+            ("float", opts.float.into()),
+            ("namespace", opts.namespace.into()),
+            ("severity", opts.severity.clone()),
+            ("win_id", opts.win_id.clone()),
+            ("wrap", opts.wrap.into()),
+        ])
+    }
+}
@@ -0,0 +1,11 @@
+//! Contains the option structs passed to the `vim.diagnostic` functions.
+
+mod diagnostic;
+mod get;
+mod goto;
+mod open_float;
+
+pub use diagnostic::*;
+pub use get::*;
+pub use goto::*;
+pub use open_float::*;
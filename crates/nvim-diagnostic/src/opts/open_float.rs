@@ -0,0 +1,105 @@
+use derive_builder::Builder;
+use nvim_api::Buffer;
+use nvim_types::{Dictionary, Object};
+
+use crate::types::DiagnosticSeverity;
+
+/// Which diagnostics to show in the floating window opened by
+/// [`open_float`](crate::open_float).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FloatScope {
+    /// All the diagnostics in the buffer.
+    Buffer,
+
+    /// The diagnostics under the cursor.
+    Cursor,
+
+    /// The diagnostics on the cursor line.
+    Line,
+}
+
+impl From<FloatScope> for Object {
+    fn from(scope: FloatScope) -> Self {
+        match scope {
+            FloatScope::Buffer => "buffer",
+            FloatScope::Cursor => "cursor",
+            FloatScope::Line => "line",
+        }
+        .into()
+    }
+}
+
+/// Options passed to [`open_float`](crate::open_float).
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct OpenFloatOpts {
+    #[builder(setter(custom))]
+    bufnr: Object,
+
+    /// Whether the floating window should be focused. Defaults to `true`.
+    #[builder(setter(strip_option))]
+    focus: Option<bool>,
+
+    /// Header shown at the top of the floating window. An empty string hides
+    /// the header.
+    #[builder(setter(into, strip_option))]
+    header: Option<String>,
+
+    /// Only show the diagnostics of this namespace.
+    #[builder(setter(strip_option))]
+    namespace: Option<u32>,
+
+    /// Which diagnostics to show. Defaults to [`FloatScope::Line`].
+    #[builder(setter(strip_option))]
+    scope: Option<FloatScope>,
+
+    #[builder(setter(custom))]
+    severity: Object,
+}
+
+impl OpenFloatOpts {
+    #[inline(always)]
+    /// Creates a new [`OpenFloatOptsBuilder`].
+    pub fn builder() -> OpenFloatOptsBuilder {
+        OpenFloatOptsBuilder::default()
+    }
+}
+
+impl OpenFloatOptsBuilder {
+    /// The buffer whose diagnostics should be shown. Defaults to the current
+    /// buffer.
+    pub fn bufnr(&mut self, buffer: Buffer) -> &mut Self {
+        self.bufnr = Some(buffer.into());
+        self
+    }
+
+    /// Only show the diagnostics with the given severity.
+    pub fn severity(&mut self, severity: DiagnosticSeverity) -> &mut Self {
+        self.severity = Some(severity.into());
+        self
+    }
+
+    /// Only show the diagnostics at least as severe as `severity`.
+    pub fn min_severity(&mut self, severity: DiagnosticSeverity) -> &mut Self {
+        self.severity =
+            Some(Dictionary::from_iter([("min", severity)]).into());
+        self
+    }
+
+    pub fn build(&mut self) -> OpenFloatOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
+
+impl From<&OpenFloatOpts> for Dictionary {
+    fn from(opts: &OpenFloatOpts) -> Self {
+        Self::from_iter([
+            ("bufnr", opts.bufnr.clone()),
+            ("focus", opts.focus.into()),
+            ("header", opts.header.clone().into()),
+            ("namespace", opts.namespace.into()),
+            ("scope", opts.scope.into()),
+            ("severity", opts.severity.clone()),
+        ])
+    }
+}
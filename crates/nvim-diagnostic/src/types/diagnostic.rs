@@ -0,0 +1,114 @@
+use derive_builder::Builder;
+use luajit_bindings::{self as lua, ffi::lua_State, Poppable};
+use nvim_types::{
+    conversion::{self, FromObject},
+    Dictionary,
+    Object,
+};
+
+use super::DiagnosticSeverity;
+
+/// A diagnostic, as described in `:h diagnostic-structure`.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct Diagnostic {
+    /// The starting line of the diagnostic (0-indexed).
+    pub lnum: usize,
+
+    /// The starting column of the diagnostic (0-indexed).
+    pub col: usize,
+
+    /// The final line of the diagnostic (0-indexed).
+    #[builder(setter(strip_option))]
+    pub end_lnum: Option<usize>,
+
+    /// The final column of the diagnostic (0-indexed).
+    #[builder(setter(strip_option))]
+    pub end_col: Option<usize>,
+
+    /// The severity of the diagnostic. Defaults to
+    /// [`DiagnosticSeverity::Error`] if not set.
+    #[builder(setter(strip_option))]
+    pub severity: Option<DiagnosticSeverity>,
+
+    /// The diagnostic text.
+    #[builder(setter(into))]
+    pub message: String,
+
+    /// The source of the diagnostic, e.g. the name of a linter.
+    #[builder(setter(into, strip_option))]
+    pub source: Option<String>,
+
+    /// The code of the diagnostic, e.g. the name of the lint that produced
+    /// it.
+    #[builder(setter(into, strip_option))]
+    pub code: Option<Object>,
+
+    /// The namespace of the diagnostic. It's set by Neovim on the
+    /// diagnostics returned by [`get`](crate::get), and ignored when
+    /// setting them since they're set in the namespace given to
+    /// [`set`](crate::set).
+    #[builder(setter(skip))]
+    pub namespace: u32,
+}
+
+impl Diagnostic {
+    #[inline(always)]
+    /// Creates a new [`DiagnosticBuilder`].
+    pub fn builder() -> DiagnosticBuilder {
+        DiagnosticBuilder::default()
+    }
+}
+
+impl DiagnosticBuilder {
+    pub fn build(&mut self) -> Diagnostic {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
+
+impl From<Diagnostic> for Object {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Dictionary::from_iter([
+            ("lnum", Object::from(diagnostic.lnum as i64)),
+            ("col", (diagnostic.col as i64).into()),
+            ("end_lnum", diagnostic.end_lnum.map(|n| n as i64).into()),
+            ("end_col", diagnostic.end_col.map(|n| n as i64).into()),
+            ("severity", diagnostic.severity.into()),
+            ("message", diagnostic.message.into()),
+            ("source", diagnostic.source.into()),
+            ("code", diagnostic.code.into()),
+        ])
+        .into()
+    }
+}
+
+impl FromObject for Diagnostic {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        let mut dict = Dictionary::from_object(obj)?;
+
+        let mut take = |key: &str| {
+            dict.get_mut(&key).map(std::mem::take).unwrap_or_default()
+        };
+
+        Ok(Self {
+            lnum: usize::from_object(take("lnum"))?,
+            col: usize::from_object(take("col"))?,
+            end_lnum: FromObject::from_object(take("end_lnum"))?,
+            end_col: FromObject::from_object(take("end_col"))?,
+            severity: FromObject::from_object(take("severity"))?,
+            message: String::from_object(take("message"))?,
+            source: FromObject::from_object(take("source"))?,
+            code: FromObject::from_object(take("code"))?,
+            namespace: FromObject::from_object(take("namespace"))?,
+        })
+    }
+}
+
+impl Poppable for Diagnostic {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self, lua::Error> {
+        let obj = Object::pop(lstate)?;
+        Self::from_object(obj)
+            .map_err(lua::Error::pop_error_from_err::<Self, _>)
+    }
+}
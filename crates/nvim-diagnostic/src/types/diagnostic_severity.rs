@@ -0,0 +1,33 @@
+use nvim_types::{
+    conversion::{self, FromObject},
+    Integer,
+    Object,
+};
+
+/// Severity of a diagnostic, see `:h diagnostic-severity`.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DiagnosticSeverity {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Hint = 4,
+}
+
+impl From<DiagnosticSeverity> for Object {
+    fn from(severity: DiagnosticSeverity) -> Self {
+        (severity as Integer).into()
+    }
+}
+
+impl FromObject for DiagnosticSeverity {
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        match Integer::from_object(obj)? {
+            1 => Ok(Self::Error),
+            2 => Ok(Self::Warn),
+            3 => Ok(Self::Info),
+            4 => Ok(Self::Hint),
+            other => Err(conversion::Error::UnknownVariant(other.to_string())),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! Contains the types given to and returned from the `vim.diagnostic`
+//! functions.
+
+mod diagnostic;
+mod diagnostic_severity;
+
+pub use diagnostic::*;
+pub use diagnostic_severity::*;
@@ -0,0 +1,176 @@
+use std::ffi::{c_char, c_int};
+
+use luajit_bindings::{self as lua, ffi::*, macros::cstr, Poppable, Pushable};
+use nvim_types::{self as nvim, Function, Object};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::lsp::pcall;
+use crate::types::ClientId;
+use crate::{Error, Result};
+
+/// An active LSP client, returned by [`get_client`](crate::get_client).
+///
+/// Every method fails with [`Error::ClientNotFound`] once the client has
+/// been stopped.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LspClient {
+    id: ClientId,
+}
+
+/// The error object of a response, as described in the [LSP
+/// specification][1].
+///
+/// [1]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#responseMessage
+#[derive(Deserialize)]
+struct ResponseError {
+    code: i64,
+    message: String,
+}
+
+impl LspClient {
+    #[inline]
+    pub(crate) fn new(id: ClientId) -> Self {
+        Self { id }
+    }
+
+    /// Returns the id of the client.
+    #[inline]
+    pub fn id(&self) -> ClientId {
+        self.id
+    }
+
+    /// Binding to `client.notify`.
+    ///
+    /// Sends a notification to the server.
+    pub fn notify<P>(&self, method: &str, params: P) -> Result<()>
+    where
+        P: Serialize,
+    {
+        let params = nvim::serde::to_object(&params)?;
+
+        if self.call::<_, bool>(cstr!("notify"), (method, params), 1)? {
+            Ok(())
+        } else {
+            Err(Error::ClientNotFound(self.id.0))
+        }
+    }
+
+    /// Binding to `client.request`.
+    ///
+    /// Sends a request to the server, calling `callback` with its response
+    /// once it arrives. Errors sent by the server are passed to the callback
+    /// as [`Error::Response`]s.
+    pub fn request<P, R, F>(
+        &self,
+        method: &str,
+        params: P,
+        callback: F,
+    ) -> Result<()>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+        F: FnOnce(Result<R>) + 'static,
+    {
+        let params = nvim::serde::to_object(&params)?;
+
+        // The handler is called with `(err, result, ctx, config)`.
+        let handler = Function::from_fn_once(
+            move |(err, result, _, _): (Object, Object, Object, Object)| {
+                callback(response(err, result));
+                Ok::<_, Error>(())
+            },
+        );
+
+        let (sent, _) = self.call::<_, (bool, Object)>(
+            cstr!("request"),
+            (method, params, handler),
+            2,
+        )?;
+
+        if sent {
+            Ok(())
+        } else {
+            Err(Error::ClientNotFound(self.id.0))
+        }
+    }
+
+    /// Binding to `client.stop`.
+    ///
+    /// Stops the client, asking the server to shut down.
+    pub fn stop(&self) -> Result<()> {
+        self.call(cstr!("stop"), (), 1)
+    }
+
+    /// Calls `client.{method}` with the given arguments, popping the first
+    /// `nresults` values it returns.
+    fn call<A, R>(
+        &self,
+        method: *const c_char,
+        args: A,
+        nresults: c_int,
+    ) -> Result<R>
+    where
+        A: Pushable,
+        R: Poppable,
+    {
+        unsafe {
+            lua::with_state(move |lstate| {
+                let top = lua_gettop(lstate);
+
+                let res = match push_client(lstate, self.id) {
+                    Ok(true) => {
+                        lua_getfield(lstate, -1, method);
+                        pcall(lstate, args, nresults)
+                            .and_then(|()| R::pop(lstate).map_err(Into::into))
+                    },
+
+                    Ok(false) => Err(Error::ClientNotFound(self.id.0)),
+
+                    Err(err) => Err(err),
+                };
+
+                // Pop `vim`, `vim.lsp`, the client and anything that was
+                // left over.
+                lua_settop(lstate, top);
+
+                res
+            })
+        }
+    }
+}
+
+/// Returns whether the client with the given id exists.
+pub(crate) fn exists(id: ClientId) -> Result<bool> {
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+            let res = push_client(lstate, id);
+            lua_settop(lstate, top);
+            res
+        })
+    }
+}
+
+/// Pushes `vim`, `vim.lsp` and the result of `vim.lsp.get_client_by_id(id)`
+/// on the stack, returning whether the latter is a client.
+unsafe fn push_client(lstate: *mut lua_State, id: ClientId) -> Result<bool> {
+    lua_getglobal(lstate, cstr!("vim"));
+    lua_getfield(lstate, -1, cstr!("lsp"));
+    lua_getfield(lstate, -1, cstr!("get_client_by_id"));
+    pcall(lstate, id, 1)?;
+    Ok(lua_type(lstate, -1) == LUA_TTABLE)
+}
+
+/// Converts the error and the result passed to a request handler into the
+/// result given to the callback.
+fn response<R>(err: Object, result: Object) -> Result<R>
+where
+    R: DeserializeOwned,
+{
+    if !err.is_nil() {
+        let ResponseError { code, message } = nvim::serde::from_object(err)?;
+        return Err(Error::Response { code, message });
+    }
+
+    Ok(nvim::serde::from_object(result)?)
+}
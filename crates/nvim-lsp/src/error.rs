@@ -0,0 +1,25 @@
+use thiserror::Error as ThisError;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Debug, ThisError, Eq, PartialEq)]
+pub enum Error {
+    #[error(transparent)]
+    Lua(#[from] luajit_bindings::Error),
+
+    #[error(transparent)]
+    Serde(#[from] nvim_types::serde::Error),
+
+    /// Returned when there's no active client with the given id.
+    #[error("No LSP client with id {0}")]
+    ClientNotFound(u32),
+
+    /// Returned when `vim.lsp.start_client` fails to start the client with
+    /// the given name.
+    #[error("Couldn't start the LSP client {0:?}")]
+    StartFailed(String),
+
+    /// The error sent by a server in response to a request.
+    #[error("LSP error {code}: {message}")]
+    Response { code: i64, message: String },
+}
@@ -0,0 +1,13 @@
+//! Bindings to Neovim's [built-in LSP client][lsp], exposed in Lua through
+//! the `vim.lsp` table.
+//!
+//! [lsp]: https://neovim.io/doc/user/lsp.html
+
+mod client;
+mod error;
+mod lsp;
+pub mod types;
+
+pub use client::LspClient;
+pub use error::{Error, Result};
+pub use lsp::*;
@@ -0,0 +1,85 @@
+use std::ffi::{c_char, c_int};
+
+use luajit_bindings::{self as lua, ffi::*, macros::cstr, Poppable, Pushable};
+use nvim_types::Dictionary;
+
+use crate::client;
+use crate::types::*;
+use crate::{Error, LspClient, Result};
+
+/// Binding to [`vim.lsp.start_client`][1].
+///
+/// Starts a new LSP client and its language server, returning the id of the
+/// client. The client isn't attached to any buffer.
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.start_client()
+pub fn start_client(config: LspClientConfig) -> Result<ClientId> {
+    let name = config.name.clone();
+
+    call::<_, Option<u32>>(cstr!("start_client"), Dictionary::from(config))?
+        .map(ClientId)
+        .ok_or(Error::StartFailed(name))
+}
+
+/// Binding to [`vim.lsp.get_client_by_id`][1].
+///
+/// Returns the client with the given id, failing if it doesn't exist or if
+/// it has already been stopped.
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.get_client_by_id()
+pub fn get_client(id: ClientId) -> Result<LspClient> {
+    if client::exists(id)? {
+        Ok(LspClient::new(id))
+    } else {
+        Err(Error::ClientNotFound(id.0))
+    }
+}
+
+/// Calls `vim.lsp.{fun}` with the given arguments, popping the value it
+/// returns.
+fn call<A, R>(fun: *const c_char, args: A) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    unsafe {
+        lua::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+
+            lua_getglobal(lstate, cstr!("vim"));
+            lua_getfield(lstate, -1, cstr!("lsp"));
+            lua_getfield(lstate, -1, fun);
+
+            let res = pcall(lstate, args, 1)
+                .and_then(|()| R::pop(lstate).map_err(Into::into));
+
+            // Pop `vim`, `vim.lsp` and anything that was left over.
+            lua_settop(lstate, top);
+
+            res
+        })
+    }
+}
+
+/// Calls the function at the top of the stack with the given arguments,
+/// leaving the first `nresults` values it returns on the stack.
+pub(crate) unsafe fn pcall<A>(
+    lstate: *mut lua_State,
+    args: A,
+    nresults: c_int,
+) -> Result<()>
+where
+    A: Pushable,
+{
+    let nargs = args.push(lstate)?;
+
+    match lua_pcall(lstate, nargs, nresults, 0) {
+        LUA_OK => Ok(()),
+
+        _err_code => {
+            let msg = lua::utils::error_message(lstate, -1);
+
+            Err(lua::Error::RuntimeError(msg).into())
+        },
+    }
+}
@@ -0,0 +1,32 @@
+use std::ffi::c_int;
+
+use luajit_bindings::{self as lua, ffi::lua_State, Pushable};
+
+/// The id of an LSP client, returned by
+/// [`start_client`](crate::start_client).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ClientId(pub(crate) u32);
+
+impl ClientId {
+    /// Returns the raw id of the client.
+    #[inline]
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Creates a `ClientId` from a raw id, e.g. one received from Lua. The id
+/// isn't checked, use [`get_client`](crate::get_client) to know if a client
+/// with that id exists.
+impl From<u32> for ClientId {
+    #[inline]
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl Pushable for ClientId {
+    unsafe fn push(self, lstate: *mut lua_State) -> Result<c_int, lua::Error> {
+        self.0.push(lstate)
+    }
+}
@@ -0,0 +1,67 @@
+use derive_builder::Builder;
+use nvim_types::{Array, Dictionary, Object};
+
+/// The configuration of an LSP client, passed to
+/// [`start_client`](crate::start_client). See `:h vim.lsp.start_client()`
+/// for the meaning of each field.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct LspClientConfig {
+    /// The name of the client, used in logs and messages.
+    #[builder(setter(into))]
+    pub name: String,
+
+    /// The command used to start the language server, followed by its
+    /// arguments.
+    #[builder(setter(custom))]
+    pub cmd: Vec<String>,
+
+    /// The directory the server is started in and which is sent to it as
+    /// the workspace root.
+    #[builder(setter(into, strip_option))]
+    pub root_dir: Option<String>,
+
+    /// The capabilities advertised to the server. If set they replace the
+    /// default ones returned by `vim.lsp.protocol.make_client_capabilities`
+    /// instead of being merged with them, so they should usually be built
+    /// starting from those.
+    #[builder(setter(strip_option))]
+    pub capabilities: Option<Dictionary>,
+}
+
+impl LspClientConfig {
+    #[inline(always)]
+    /// Creates a new [`LspClientConfigBuilder`].
+    pub fn builder() -> LspClientConfigBuilder {
+        LspClientConfigBuilder::default()
+    }
+}
+
+impl LspClientConfigBuilder {
+    /// The command used to start the language server, followed by its
+    /// arguments.
+    pub fn cmd<I, S>(&mut self, cmd: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.cmd = Some(cmd.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn build(&mut self) -> LspClientConfig {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
+
+impl From<LspClientConfig> for Dictionary {
+    fn from(config: LspClientConfig) -> Self {
+        Dictionary::from_iter([
+            ("name", Object::from(config.name)),
+            ("cmd", config.cmd.into_iter().collect::<Array>().into()),
+            ("root_dir", config.root_dir.into()),
+            ("capabilities", config.capabilities.into()),
+        ])
+    }
+}
@@ -0,0 +1,7 @@
+//! Contains the types given to and returned from the `vim.lsp` functions.
+
+mod client_id;
+mod lsp_client_config;
+
+pub use client_id::*;
+pub use lsp_client_config::*;
@@ -7,7 +7,7 @@ use luajit_bindings::{self as lua, ffi::lua_State, Pushable};
 /// The entrypoint of the plugin.
 ///
 /// Initializes the Lua state, executes the entrypoint function and pushes the
-/// result on the stack.
+/// result on the stack. Both errors and panics are reported as Lua errors.
 #[doc(hidden)]
 pub unsafe fn entrypoint<R, E>(
     lua_state: *mut lua_State,
@@ -20,10 +20,32 @@ where
     lua::init(lua_state);
 
     #[cfg(feature = "libuv")]
-    libuv_bindings::init(lua_state);
-
-    match body() {
-        Ok(api) => api.push(lua_state).unwrap(),
-        Err(err) => lua::utils::handle_error(lua_state, &err),
+    {
+        libuv_bindings::init(lua_state);
+        register_libuv_shutdown();
     }
+
+    // Panics are turned into Lua errors instead of unwinding into Neovim.
+    lua::pcall_fn(lua_state, |lua_state| {
+        body().map(|api| api.push(lua_state).unwrap())
+    })
+}
+
+/// Makes sure the libuv handles created by the plugin are stopped when Neovim
+/// is about to exit.
+#[cfg(feature = "libuv")]
+fn register_libuv_shutdown() {
+    use nvim_api::opts::CreateAutocmdOpts;
+
+    let opts = CreateAutocmdOpts::builder()
+        .once(true)
+        .callback(|_| {
+            libuv_bindings::shutdown();
+            Ok::<_, nvim_api::Error>(true)
+        })
+        .build();
+
+    // If this fails the handles are simply not stopped on exit, which isn't
+    // worth failing the whole plugin for.
+    let _ = nvim_api::create_autocmd(["VimLeavePre"], &opts);
 }
@@ -1,3 +1,6 @@
+use std::error::Error as StdError;
+use std::fmt;
+
 use thiserror::Error as ThisError;
 
 /// `nvim-oxi`'s result type.
@@ -26,7 +29,125 @@ pub enum Error {
     #[error(transparent)]
     Libuv(#[from] libuv_bindings::Error),
 
+    #[cfg(feature = "lsp")]
+    #[error(transparent)]
+    Lsp(#[from] nvim_lsp::Error),
+
+    #[cfg(feature = "treesitter")]
+    #[error(transparent)]
+    Treesitter(#[from] nvim_treesitter::Error),
+
     #[cfg(feature = "mlua")]
     #[error(transparent)]
     Mlua(#[from] mlua::Error),
+
+    #[error(transparent)]
+    Context(#[from] ContextError),
+}
+
+impl Error {
+    /// Wraps the error with some context describing what was being done
+    /// when it happened. See [`ResultExt`] for how it's displayed.
+    pub fn with_context<C>(self, context: C) -> Self
+    where
+        C: fmt::Display,
+    {
+        Self::Context(ContextError {
+            context: context.to_string(),
+            source: Box::new(self),
+        })
+    }
+}
+
+/// An [`Error`] together with some context added via [`ResultExt`] or
+/// [`Error::with_context`].
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "mlua"), derive(Eq, PartialEq))]
+pub struct ContextError {
+    context: String,
+    source: Box<Error>,
+}
+
+impl ContextError {
+    /// Returns the context added to the error.
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+
+    /// Returns the error the context was added to.
+    pub fn inner(&self) -> &Error {
+        &self.source
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.context)?;
+
+        if f.alternate() {
+            write!(f, ": {:#}", self.source)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StdError for ContextError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// Extension trait to add context to the errors of a
+/// [`Result`](std::result::Result), similar to
+/// [`anyhow::Context`](https://docs.rs/anyhow/latest/anyhow/trait.Context.html).
+///
+/// Like with `anyhow`, formatting the error with `{}` only prints the
+/// outermost context, while `{:#}` prints the whole chain separated by
+/// colons, e.g. `processing Buffer(1): Variable not found: name`. The
+/// underlying errors are also available via
+/// [`source`](std::error::Error::source).
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::{api, ResultExt};
+///
+/// let buf = api::get_current_buf();
+/// let name = buf
+///     .get_var::<String>("name")
+///     .with_context(|| format!("processing {buf}"))?;
+/// ```
+pub trait ResultExt<T> {
+    /// Wraps the error with `context`.
+    fn context<C>(self, context: C) -> Result<T>
+    where
+        C: fmt::Display;
+
+    /// Wraps the error with the context returned by `fun`, which is only
+    /// called if there's an error.
+    fn with_context<C, F>(self, fun: F) -> Result<T>
+    where
+        C: fmt::Display,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> ResultExt<T> for std::result::Result<T, E>
+where
+    E: Into<Error>,
+{
+    fn context<C>(self, context: C) -> Result<T>
+    where
+        C: fmt::Display,
+    {
+        self.map_err(|err| err.into().with_context(context))
+    }
+
+    fn with_context<C, F>(self, fun: F) -> Result<T>
+    where
+        C: fmt::Display,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|err| err.into().with_context(fun()))
+    }
 }
@@ -34,6 +34,13 @@ pub mod libuv {
     pub use libuv_bindings::*;
 }
 
+#[cfg(feature = "lsp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lsp")))]
+pub mod lsp {
+    #[doc(inline)]
+    pub use nvim_lsp::*;
+}
+
 pub mod lua {
     //! Low-level Rust bindings to [LuaJIT], the Lua version used by Neovim.
     //!
@@ -79,6 +86,8 @@ pub mod mlua {
     }
 }
 
+pub mod profile;
+
 #[cfg(feature = "diagnostic")]
 #[cfg_attr(docsrs, doc(cfg(feature = "diagnostic")))]
 pub mod diagnostic {
@@ -86,11 +95,19 @@ pub mod diagnostic {
     pub use nvim_diagnostic::*;
 }
 
+#[cfg(feature = "treesitter")]
+#[cfg_attr(docsrs, doc(cfg(feature = "treesitter")))]
+pub mod treesitter {
+    #[doc(inline)]
+    pub use nvim_treesitter::*;
+}
+
 #[doc(hidden)]
 pub use entrypoint::entrypoint;
-pub use error::{Error, Result};
+pub use error::{ContextError, Error, Result, ResultExt};
 pub use luajit_bindings::{dbg, print};
 pub use nvim_types::*;
+pub use oxi_derive::{FromObject, ToObject};
 pub use oxi_module::oxi_module as module;
 #[cfg(feature = "test")]
 #[cfg_attr(docsrs, doc(cfg(feature = "test")))]
@@ -0,0 +1,136 @@
+//! Measures how long the work done by a plugin on Neovim's main thread
+//! takes, e.g. to find slow autocommand or decoration provider callbacks.
+//!
+//! Profiling is disabled by default, in which case creating a [`scope`]
+//! costs a single thread-local read and nothing is recorded.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use nvim_oxi::profile;
+//!
+//! profile::enable();
+//!
+//! fn on_buf_enter() {
+//!     let _scope = profile::scope("on_buf_enter");
+//!     // ...
+//! }
+//!
+//! on_buf_enter();
+//!
+//! let stats = profile::stats("on_buf_enter").unwrap();
+//! assert_eq!(1, stats.count);
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static ENABLED: Cell<bool> = Cell::new(false);
+
+    static STATS: RefCell<HashMap<&'static str, Stats>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The aggregated timings of all the [`Scope`]s with the same name.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Stats {
+    /// How many scopes have been recorded.
+    pub count: u64,
+
+    /// The sum of the time spent in each scope.
+    pub total: Duration,
+
+    /// The time spent in the slowest scope.
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Returns the average time spent in a scope.
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        let nanos = self.total.as_nanos() / u128::from(self.count);
+        Duration::from_nanos(nanos as u64)
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+}
+
+/// A guard returned by [`scope`] which records the time elapsed since its
+/// creation when it's dropped.
+#[must_use = "the scope is recorded when it's dropped"]
+pub struct Scope {
+    start: Option<(&'static str, Instant)>,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if let Some((name, start)) = self.start.take() {
+            let elapsed = start.elapsed();
+            STATS.with(|stats| {
+                stats.borrow_mut().entry(name).or_default().record(elapsed)
+            });
+        }
+    }
+}
+
+/// Starts measuring a piece of work, which ends when the returned guard is
+/// dropped. Scopes created while profiling is disabled aren't recorded.
+///
+/// Stats are kept per thread, so scopes should be created on Neovim's main
+/// thread to be returned by [`stats`].
+#[inline]
+pub fn scope(name: &'static str) -> Scope {
+    let start = is_enabled().then(|| (name, Instant::now()));
+    Scope { start }
+}
+
+/// Starts recording scopes.
+pub fn enable() {
+    ENABLED.with(|enabled| enabled.set(true));
+}
+
+/// Stops recording scopes. The stats recorded so far are kept.
+pub fn disable() {
+    ENABLED.with(|enabled| enabled.set(false));
+}
+
+/// Returns whether scopes are being recorded.
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.with(Cell::get)
+}
+
+/// Returns the stats of the scopes called `name`, or `None` if none has been
+/// recorded.
+pub fn stats(name: &str) -> Option<Stats> {
+    STATS.with(|stats| stats.borrow().get(name).copied())
+}
+
+/// Returns the stats of all the recorded scopes, sorted by the total time
+/// spent in them, slowest first.
+pub fn all_stats() -> Vec<(&'static str, Stats)> {
+    let mut all = STATS.with(|stats| {
+        stats
+            .borrow()
+            .iter()
+            .map(|(&name, &stats)| (name, stats))
+            .collect::<Vec<_>>()
+    });
+    all.sort_by_key(|(_, stats)| Reverse(stats.total));
+    all
+}
+
+/// Clears all the recorded stats.
+pub fn reset() {
+    STATS.with(|stats| stats.borrow_mut().clear());
+}
@@ -1,4 +1,18 @@
+#[cfg(feature = "libuv")]
+use std::cell::{Cell, RefCell};
+#[cfg(feature = "libuv")]
+use std::rc::{Rc, Weak};
+#[cfg(feature = "libuv")]
+use std::time::Duration;
+
+#[cfg(feature = "libuv")]
+use libuv_bindings::TimerHandle;
 use luajit_bindings::{self as lua, ffi::*, macros::cstr};
+#[cfg(feature = "libuv")]
+use nvim_api::{
+    opts::{CreateAutocmdOptsBuilder, GetAutocmdsOpts},
+    types::AutocmdCallbackArgs,
+};
 use nvim_types::Function;
 
 use crate::Result;
@@ -37,3 +51,198 @@ where
         })
     };
 }
+
+/// Creates an autocommand whose `callback` is debounced, i.e. only called
+/// once `delay` has elapsed since the last time one of `events` was
+/// triggered, with the arguments of that last trigger.
+///
+/// Every trigger restarts the same timer, so rapid bursts of events (e.g.
+/// `CursorMoved`) result in a single call. If the autocommand is deleted
+/// before the timer fires `callback` is not called, which is also why `once`
+/// shouldn't be set on `opts`. Returning `true` from `callback` deletes the
+/// autocommand.
+///
+/// The timer is closed once the autocommand is deleted, either by returning
+/// `true` from `callback` or, if it's deleted by other means, the next time
+/// the timer fires.
+///
+/// Any `callback` or `command` previously set on `opts` is replaced.
+/// Returns the id of the autocommand.
+#[cfg(feature = "libuv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "libuv")))]
+pub fn create_autocmd_debounced<'a, I, C>(
+    events: I,
+    opts: &mut CreateAutocmdOptsBuilder,
+    delay: Duration,
+    callback: C,
+) -> Result<u32>
+where
+    I: IntoIterator<Item = &'a str>,
+    C: FnMut(AutocmdCallbackArgs) -> Result<bool> + 'static,
+{
+    let events = events.into_iter().map(String::from).collect::<Vec<_>>();
+    let callback = Rc::new(RefCell::new(callback));
+    let pending = Rc::new(RefCell::new(None::<AutocmdCallbackArgs>));
+    let timer = Rc::new(RefCell::new(None::<TimerHandle>));
+    let id = Rc::new(Cell::new(0));
+
+    let on_timer = {
+        let events = events.clone();
+        let pending = Rc::clone(&pending);
+        // The timer's callback is owned by the timer itself.
+        let timer = Rc::downgrade(&timer);
+        let id = Rc::clone(&id);
+
+        move |handle: &mut TimerHandle| {
+            handle.stop()?;
+
+            let callback = Rc::clone(&callback);
+            let events = events.clone();
+            let pending = Rc::clone(&pending);
+            let timer = Weak::clone(&timer);
+            let id = id.get();
+
+            // The API can't be called from inside a libuv callback.
+            schedule(move |()| {
+                let args = match pending.borrow_mut().take() {
+                    Some(args) => args,
+                    None => return Ok(()),
+                };
+
+                if autocmd_exists(id, &events)? {
+                    if !(callback.borrow_mut())(args)? {
+                        return Ok(());
+                    }
+                    nvim_api::del_autocmd(id)?;
+                }
+
+                if let Some(timer) = timer.upgrade() {
+                    if let Some(timer) = timer.borrow_mut().take() {
+                        timer.close();
+                    }
+                }
+
+                Ok(())
+            });
+
+            Ok::<_, crate::Error>(())
+        }
+    };
+
+    // The timer is stopped right away and then restarted by every trigger
+    // with `again`, which uses the repeat interval as the timeout. It stops
+    // itself when it fires, so it never actually repeats.
+    let mut handle = TimerHandle::start(delay, delay, on_timer)?;
+    handle.stop()?;
+    *timer.borrow_mut() = Some(handle);
+
+    let debounced = Function::from_fn_mut({
+        let timer = Rc::clone(&timer);
+        move |args: AutocmdCallbackArgs| {
+            *pending.borrow_mut() = Some(args);
+
+            if let Some(timer) = &mut *timer.borrow_mut() {
+                timer.again()?;
+            }
+
+            Ok::<_, crate::Error>(false)
+        }
+    });
+
+    let opts = opts.callback(debounced).build();
+
+    let autocmd_id = match nvim_api::create_autocmd(
+        events.iter().map(String::as_str),
+        &opts,
+    ) {
+        Ok(autocmd_id) => autocmd_id,
+
+        Err(err) => {
+            if let Some(timer) = timer.borrow_mut().take() {
+                timer.close();
+            }
+            return Err(err.into());
+        },
+    };
+
+    id.set(autocmd_id);
+
+    Ok(autocmd_id)
+}
+
+/// Creates an autocommand that waits for one of `events` to be triggered for
+/// at most `timeout`.
+///
+/// If one of the events is triggered in time `callback` is called and the
+/// timeout is cancelled. If not, the autocommand is deleted and `on_timeout`
+/// is called instead. Either way, at most one of the two callbacks is called.
+///
+/// The autocommand is always created with `once` set to `true`, and any
+/// `callback` or `command` previously set on `opts` is replaced by
+/// `callback`. Returns the id of the autocommand.
+#[cfg(feature = "libuv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "libuv")))]
+pub fn create_autocmd_with_timeout<'a, I, C, T>(
+    events: I,
+    opts: &mut CreateAutocmdOptsBuilder,
+    timeout: Duration,
+    callback: C,
+    on_timeout: T,
+) -> Result<u32>
+where
+    I: IntoIterator<Item = &'a str>,
+    C: FnOnce(AutocmdCallbackArgs) -> Result<bool> + 'static,
+    T: FnOnce() -> Result<()> + 'static,
+{
+    let timer = Rc::new(RefCell::new(None::<TimerHandle>));
+
+    let callback = Function::from_fn_once({
+        let timer = Rc::clone(&timer);
+        move |args| {
+            if let Some(timer) = timer.borrow_mut().take() {
+                timer.close();
+            }
+            callback(args)
+        }
+    });
+
+    let opts = opts.once(true).callback(callback).build();
+    let id = nvim_api::create_autocmd(events, &opts)?;
+
+    let handle = TimerHandle::once(timeout, {
+        let timer = Rc::clone(&timer);
+        move || {
+            if let Some(timer) = timer.borrow_mut().take() {
+                timer.close();
+            }
+
+            // The API can't be called from inside a libuv callback.
+            schedule(move |()| {
+                // Deleting the autocommand only fails if it has already been
+                // triggered (and deleted since it's `once`) after the timer
+                // fired but before this function got scheduled.
+                match nvim_api::del_autocmd(id) {
+                    Ok(()) => on_timeout(),
+                    Err(_) => Ok(()),
+                }
+            });
+
+            Ok::<_, crate::Error>(())
+        }
+    })?;
+
+    *timer.borrow_mut() = Some(handle);
+
+    Ok(id)
+}
+
+/// Returns whether the autocommand with the given `id`, listening for one of
+/// `events`, still exists.
+#[cfg(feature = "libuv")]
+fn autocmd_exists(id: u32, events: &[String]) -> Result<bool> {
+    let opts = GetAutocmdsOpts::builder()
+        .events(events.iter().map(String::as_str))
+        .build();
+
+    Ok(nvim_api::get_autocmds(&opts)?.any(|infos| infos.id == Some(id)))
+}
@@ -0,0 +1,12 @@
+use thiserror::Error as ThisError;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Debug, ThisError, Eq, PartialEq)]
+pub enum Error {
+    #[error(transparent)]
+    Lua(#[from] luajit_bindings::Error),
+
+    #[error(transparent)]
+    Api(#[from] nvim_api::Error),
+}
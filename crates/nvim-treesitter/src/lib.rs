@@ -0,0 +1,15 @@
+//! Bindings to Neovim's [treesitter integration][treesitter], exposed in
+//! Lua through the `vim.treesitter` table.
+//!
+//! [treesitter]: https://neovim.io/doc/user/treesitter.html
+
+mod error;
+mod lua_ref;
+mod node;
+mod parser;
+mod tree;
+
+pub use error::{Error, Result};
+pub use node::Node;
+pub use parser::{get_parser, Parser};
+pub use tree::Tree;
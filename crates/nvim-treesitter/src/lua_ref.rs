@@ -0,0 +1,104 @@
+use std::ffi::{c_char, c_int};
+
+use luajit_bindings::{self as lua, ffi::*, Poppable, Pushable, StackGuard};
+
+/// A Lua value, like a parser or a node, stored in the Lua registry. The
+/// value is released when the reference is dropped.
+pub(crate) struct LuaRef(c_int);
+
+impl LuaRef {
+    /// Calls `value:{method}(args)`, popping the first `nresults` values it
+    /// returns.
+    pub(crate) fn call_method<A, R>(
+        &self,
+        method: *const c_char,
+        args: A,
+        nresults: c_int,
+    ) -> Result<R, lua::Error>
+    where
+        A: Pushable,
+        R: Poppable,
+    {
+        unsafe {
+            lua::with_state(move |lstate| {
+                let _guard = StackGuard::new(lstate);
+
+                lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+                lua_getfield(lstate, -1, method);
+                // The value itself is the method's `self` argument.
+                lua_pushvalue(lstate, -2);
+
+                let nargs = args.push(lstate)?;
+                pcall(lstate, nargs + 1, nresults)
+            })
+        }
+    }
+
+    /// Returns the value at index `n` of the referenced table.
+    pub(crate) fn get_index(&self, n: c_int) -> Result<Self, lua::Error> {
+        unsafe {
+            lua::with_state(|lstate| {
+                let _guard = StackGuard::new(lstate);
+                lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+                lua_rawgeti(lstate, -1, n);
+                Self::pop(lstate)
+            })
+        }
+    }
+}
+
+impl Clone for LuaRef {
+    fn clone(&self) -> Self {
+        unsafe {
+            lua::with_state(|lstate| {
+                lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+                Self(luaL_ref(lstate, LUA_REGISTRYINDEX))
+            })
+        }
+    }
+}
+
+impl Drop for LuaRef {
+    fn drop(&mut self) {
+        unsafe {
+            lua::with_state(|lstate| {
+                luaL_unref(lstate, LUA_REGISTRYINDEX, self.0)
+            })
+        }
+    }
+}
+
+impl Poppable for LuaRef {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self, lua::Error> {
+        match lua_type(lstate, -1) {
+            LUA_TNONE | LUA_TNIL => Err(lua::Error::pop_error(
+                "LuaRef",
+                "expected a value, found nil",
+            )),
+
+            // `luaL_ref` pops the value off the stack.
+            _ => Ok(Self(luaL_ref(lstate, LUA_REGISTRYINDEX))),
+        }
+    }
+}
+
+/// Calls the function placed on the stack below its `nargs` arguments,
+/// popping the first `nresults` values it returns.
+pub(crate) unsafe fn pcall<R>(
+    lstate: *mut lua_State,
+    nargs: c_int,
+    nresults: c_int,
+) -> Result<R, lua::Error>
+where
+    R: Poppable,
+{
+    match lua_pcall(lstate, nargs, nresults, 0) {
+        LUA_OK => R::pop(lstate),
+
+        _err_code => {
+            let msg = lua::utils::error_message(lstate, -1);
+
+            Err(lua::Error::RuntimeError(msg))
+        },
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+
+use luajit_bindings::{self as lua, macros::cstr};
+use nvim_api::Buffer;
+use nvim_types as nvim;
+
+use crate::lua_ref::LuaRef;
+use crate::Result;
+
+/// A node of a syntax [`Tree`](crate::Tree).
+///
+/// Positions are 0-indexed `(row, col)` tuples, with the column counted in
+/// bytes.
+#[derive(Clone)]
+pub struct Node {
+    node: LuaRef,
+    kind: String,
+    start: (usize, usize),
+    end: (usize, usize),
+    named_child_count: usize,
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("kind", &self.kind)
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+
+impl Node {
+    pub(crate) fn new(node: LuaRef) -> std::result::Result<Self, lua::Error> {
+        let kind = node.call_method(cstr!("type"), (), 1)?;
+
+        let (start_row, start_col, end_row, end_col) =
+            node.call_method(cstr!("range"), (), 4)?;
+
+        let named_child_count =
+            node.call_method(cstr!("named_child_count"), (), 1)?;
+
+        Ok(Self {
+            node,
+            kind,
+            start: (start_row, start_col),
+            end: (end_row, end_col),
+            named_child_count,
+        })
+    }
+
+    /// Returns the `i`-th child of the node, counting both named and
+    /// anonymous nodes, or `None` if it has less than `i + 1` children.
+    pub fn child(&self, i: usize) -> Result<Option<Node>> {
+        let child = self.node.call_method::<_, Option<LuaRef>>(
+            cstr!("child"),
+            i,
+            1,
+        )?;
+
+        Ok(child.map(Node::new).transpose()?)
+    }
+
+    /// Returns the end position of the node. The column is exclusive.
+    pub fn end_position(&self) -> (usize, usize) {
+        self.end
+    }
+
+    /// Returns the type of the node, e.g. `"function_definition"`.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Returns the number of named children of the node.
+    pub fn named_child_count(&self) -> usize {
+        self.named_child_count
+    }
+
+    /// Returns the start position of the node.
+    pub fn start_position(&self) -> (usize, usize) {
+        self.start
+    }
+
+    /// Returns the text of the node in `buffer`, with lines joined by
+    /// newlines.
+    pub fn text(&self, buffer: &Buffer) -> Result<nvim::String> {
+        let (start_row, start_col) = self.start;
+        let (mut end_row, mut end_col) = self.end;
+
+        // Nodes spanning up to the end of the buffer, like the root node of
+        // most files, end at the start of the line past the last one.
+        if end_col == 0 && end_row > start_row {
+            let line_count = buffer.line_count()?;
+
+            if end_row == line_count {
+                end_row -= 1;
+                end_col = buffer
+                    .get_lines(end_row..=end_row, true)?
+                    .next()
+                    .map_or(0, |line| line.as_bytes().len());
+            }
+        }
+
+        let lines = buffer.get_text(
+            start_row..=end_row,
+            start_col,
+            end_col,
+            &Default::default(),
+        )?;
+
+        let mut text = Vec::new();
+
+        for (i, line) in lines.enumerate() {
+            if i > 0 {
+                text.push(b'\n');
+            }
+            text.extend_from_slice(line.as_bytes());
+        }
+
+        Ok(nvim::String::from_bytes(text))
+    }
+}
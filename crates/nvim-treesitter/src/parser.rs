@@ -0,0 +1,47 @@
+use luajit_bindings::{self as lua, ffi::*, macros::cstr, StackGuard};
+use nvim_api::Buffer;
+
+use crate::lua_ref::{self, LuaRef};
+use crate::{Result, Tree};
+
+/// Binding to [`vim.treesitter.get_parser`][1].
+///
+/// Returns the parser for `lang` attached to `buffer`, creating it if it
+/// doesn't already exist. Fails if no parser for `lang` is installed.
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.get_parser()
+pub fn get_parser(buffer: Buffer, lang: &str) -> Result<Parser> {
+    let parser = unsafe {
+        lua::with_state(move |lstate| {
+            let _guard = StackGuard::new(lstate);
+
+            lua_getglobal(lstate, cstr!("vim"));
+            lua_getfield(lstate, -1, cstr!("treesitter"));
+            lua_getfield(lstate, -1, cstr!("get_parser"));
+
+            let nargs = lua::Pushable::push((buffer, lang), lstate)?;
+            lua_ref::pcall::<LuaRef>(lstate, nargs, 1)
+        })
+    }?;
+
+    Ok(Parser(parser))
+}
+
+/// A [`LanguageTree`][1] parsing the contents of a buffer, returned by
+/// [`get_parser`].
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#lua-treesitter-languagetree
+#[derive(Clone)]
+pub struct Parser(LuaRef);
+
+impl Parser {
+    /// Parses the buffer, returning the syntax tree of its contents.
+    ///
+    /// Only the tree of the parser's own language is returned, the ones of
+    /// injected languages are ignored.
+    pub fn parse(&self) -> Result<Tree> {
+        let trees = self.0.call_method::<_, LuaRef>(cstr!("parse"), (), 1)?;
+        let tree = trees.get_index(1)?;
+        Ok(Tree::new(tree)?)
+    }
+}
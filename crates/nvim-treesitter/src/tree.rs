@@ -0,0 +1,22 @@
+use luajit_bindings::{self as lua, macros::cstr};
+
+use crate::lua_ref::LuaRef;
+use crate::Node;
+
+/// A syntax tree, returned by [`Parser::parse`](crate::Parser::parse).
+#[derive(Clone, Debug)]
+pub struct Tree {
+    root: Node,
+}
+
+impl Tree {
+    pub(crate) fn new(tree: LuaRef) -> Result<Self, lua::Error> {
+        let root = tree.call_method::<_, LuaRef>(cstr!("root"), (), 1)?;
+        Ok(Self { root: Node::new(root)? })
+    }
+
+    /// Returns the root node of the tree.
+    pub fn root_node(&self) -> Node {
+        self.root.clone()
+    }
+}
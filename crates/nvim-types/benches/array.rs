@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use nvim_types::{Array, Object};
+
+const LEN: usize = 10_000;
+
+fn build_array(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_array");
+
+    group.bench_function("without_capacity", |b| {
+        b.iter(|| {
+            let mut arr = Array::new();
+            for i in 0..LEN {
+                arr.push(Object::from(black_box(i as i64)));
+            }
+            // Dropping a `Vec` also frees the allocation.
+            Vec::from(arr)
+        })
+    });
+
+    group.bench_function("with_capacity", |b| {
+        b.iter(|| {
+            let mut arr = Array::with_capacity(LEN);
+            for i in 0..LEN {
+                arr.push(Object::from(black_box(i as i64)));
+            }
+            // Dropping a `Vec` also frees the allocation.
+            Vec::from(arr)
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, build_array);
+criterion_main!(benches);
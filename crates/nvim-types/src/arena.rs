@@ -0,0 +1,133 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+use crate::Object;
+
+/// An arena that deduplicates structurally equal [`Object`]s.
+///
+/// Interning an object returns a reference-counted handle to it. If an equal
+/// object had already been interned the new one is dropped and a handle to
+/// the existing one is returned instead, so that all the copies share the
+/// same storage. Since the handles are [`Rc`]s, shared objects can't be
+/// mutated.
+///
+/// Objects are compared as a whole, so to also share the storage of nested
+/// values (e.g. a dictionary that appears inside many different arrays) those
+/// have to be interned separately.
+#[derive(Default)]
+pub struct ObjectArena {
+    buckets: HashMap<u64, Vec<Rc<Object>>>,
+}
+
+impl ObjectArena {
+    /// Creates a new empty arena.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interns an object, returning a handle to it or to a previously
+    /// interned object equal to it.
+    pub fn intern(&mut self, obj: Object) -> Rc<Object> {
+        let mut hasher = DefaultHasher::new();
+        obj.hash_structure(&mut hasher);
+
+        let bucket = self.buckets.entry(hasher.finish()).or_default();
+
+        match bucket.iter().find(|interned| ***interned == obj) {
+            Some(interned) => Rc::clone(interned),
+
+            None => {
+                let obj = Rc::new(obj);
+                bucket.push(Rc::clone(&obj));
+                obj
+            },
+        }
+    }
+
+    /// Returns the number of distinct objects in the arena.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buckets.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if the arena doesn't contain any objects.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bytes allocated on the heap by all the objects
+    /// in the arena. See [`Object::heap_size`] for details.
+    pub fn heap_size(&self) -> usize {
+        self.buckets.values().flatten().map(|obj| obj.heap_size()).sum()
+    }
+
+    /// Removes all the objects that are no longer referenced outside of the
+    /// arena.
+    pub fn collect(&mut self) {
+        self.buckets.retain(|_, bucket| {
+            bucket.retain(|obj| Rc::strong_count(obj) > 1);
+            !bucket.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Array, Dictionary};
+
+    fn cached() -> Object {
+        Dictionary::from_iter([
+            ("name", Object::from("foo")),
+            ("lines", Array::from_iter(["bar", "baz"]).into()),
+        ])
+        .into()
+    }
+
+    #[test]
+    fn interning_equal_objects_shares_storage() {
+        let mut arena = ObjectArena::new();
+
+        let size = cached().heap_size();
+        assert!(size > 0);
+
+        let first = arena.intern(cached());
+        let second = arena.intern(cached());
+
+        assert!(Rc::ptr_eq(&first, &second));
+        assert_eq!(1, arena.len());
+        assert_eq!(size, arena.heap_size());
+        assert!(arena.heap_size() < 2 * size);
+    }
+
+    #[test]
+    fn interning_different_objects() {
+        let mut arena = ObjectArena::new();
+
+        let foo = arena.intern(Object::from("foo"));
+        let bar = arena.intern(Object::from("bar"));
+        let zero = arena.intern(Object::from(0.0));
+        let neg_zero = arena.intern(Object::from(-0.0));
+
+        assert!(!Rc::ptr_eq(&foo, &bar));
+        assert!(Rc::ptr_eq(&zero, &neg_zero));
+        assert_eq!(3, arena.len());
+    }
+
+    #[test]
+    fn collect() {
+        let mut arena = ObjectArena::new();
+
+        let foo = arena.intern(Object::from("foo"));
+        drop(arena.intern(Object::from("bar")));
+
+        arena.collect();
+
+        assert_eq!(1, arena.len());
+        assert!(Rc::ptr_eq(&foo, &arena.intern(Object::from("foo"))));
+    }
+}
@@ -5,11 +5,16 @@ use std::ptr;
 
 use luajit_bindings::{self as lua, ffi::lua_State};
 
+use super::conversion::{self, ToObject};
 use super::{KVec, Object};
 
 // https://github.com/neovim/neovim/blob/master/src/nvim/api/private/defs.h#L89
 //
 /// A vector of Neovim [`Object`](Object)s.
+///
+/// Arrays dereference to `[Object]`, so all the slice methods like
+/// [`chunks`](slice::chunks) and [`windows`](slice::windows) can be called
+/// on them directly.
 pub type Array = KVec<Object>;
 
 impl Debug for Array {
@@ -24,6 +29,27 @@ impl Display for Array {
     }
 }
 
+impl Array {
+    /// Creates an array from an iterator of values which can be converted
+    /// into [`Object`]s, failing on the first value which can't. Like
+    /// [`FromIterator`], nil objects are skipped.
+    pub fn from_typed_iter<T, I>(iter: I) -> Result<Self, conversion::Error>
+    where
+        T: ToObject,
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let mut array = Self::with_capacity(iter.size_hint().0);
+        for item in iter {
+            let obj = item.to_object()?;
+            if obj.is_some() {
+                array.push(obj);
+            }
+        }
+        Ok(array)
+    }
+}
+
 impl lua::Pushable for Array {
     unsafe fn push(self, state: *mut lua_State) -> Result<c_int, lua::Error> {
         <Vec<Object>>::from(self).push(state)
@@ -155,6 +181,22 @@ from_tuple!(A B C D E F G H I J K L M N);
 from_tuple!(A B C D E F G H I J K L M N O);
 from_tuple!(A B C D E F G H I J K L M N O P);
 
+#[cfg(feature = "serde")]
+mod serde {
+    use serde::ser::{Serialize, Serializer};
+
+    use super::Array;
+
+    impl Serialize for Array {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            crate::object::serde::serialize_array(self, false, serializer)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,4 +237,115 @@ mod tests {
         let arr = Array::from_iter([Array::from((1, 2, 3))]);
         assert_eq!(String::from("[[1, 2, 3]]"), format!("{arr}"));
     }
+
+    #[test]
+    fn with_capacity() {
+        let mut arr = Array::with_capacity(3);
+        assert!(arr.is_empty());
+        assert!(arr.capacity() >= 3);
+
+        let ptr = arr.items;
+        arr.push(Object::from("foo"));
+        arr.push(Object::from(1));
+        arr.push(Object::from(true));
+
+        // No reallocations should've happened.
+        assert_eq!(ptr, arr.items);
+        assert_eq!(Array::from(("foo", 1, true)), arr);
+
+        assert!(Array::with_capacity(0).items.is_null());
+    }
+
+    #[test]
+    fn reserve() {
+        let mut arr = Array::from_iter(["foo"]);
+        arr.reserve(10);
+        assert!(arr.capacity() >= 11);
+        assert_eq!(Array::from_iter(["foo"]), arr);
+
+        let mut arr = Array::new();
+        for i in 0..100 {
+            arr.push(Object::from(i));
+        }
+        assert_eq!(Array::from_iter(0..100), arr);
+    }
+
+    #[test]
+    fn into_vec() {
+        let arr = Array::from_iter(["foo", "bar"]);
+        let vec = Vec::from(arr);
+        assert_eq!(vec![Object::from("foo"), Object::from("bar")], vec);
+    }
+
+    #[test]
+    fn clear() {
+        let mut arr = Array::from(("foo", 1, true));
+        let (ptr, capacity) = (arr.items, arr.capacity());
+
+        arr.clear();
+        assert!(arr.is_empty());
+        assert_eq!(capacity, arr.capacity());
+
+        arr.push(Object::from("bar"));
+        arr.push(Object::from(2));
+        assert_eq!(ptr, arr.items);
+        assert_eq!(Array::from(("bar", 2)), arr);
+    }
+
+    #[test]
+    fn pop() {
+        let mut arr = Array::from(("foo", 1));
+        assert_eq!(Some(Object::from(1)), arr.pop());
+        assert_eq!(Some(Object::from("foo")), arr.pop());
+        assert_eq!(None, arr.pop());
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn retain() {
+        let mut arr = Array::from_iter(0..10);
+        arr.retain(|obj| obj == &Object::from(3) || obj == &Object::from(7));
+        assert_eq!(Array::from((3, 7)), arr);
+
+        arr.push(Object::from(8));
+        assert_eq!(Array::from((3, 7, 8)), arr);
+    }
+
+    #[test]
+    fn dedup() {
+        let mut arr = Array::from((1, 1, "a", "a", 1, true, true));
+        arr.dedup();
+        assert_eq!(Array::from((1, "a", 1, true)), arr);
+    }
+
+    #[test]
+    fn from_typed_iter() {
+        let arr = Array::from_typed_iter([1u64, 2, 3]).unwrap();
+        assert_eq!(Array::from((1, 2, 3)), arr);
+
+        assert!(Array::from_typed_iter([1, u64::MAX]).is_err());
+    }
+
+    #[test]
+    fn chunks_and_windows() {
+        let arr = Array::from((255, 0, 0, 0, 255, 0));
+
+        let rgb = arr.chunks(3).collect::<Vec<_>>();
+        assert_eq!(2, rgb.len());
+        assert_eq!(&Array::from((0, 255, 0))[..], rgb[1]);
+
+        let pairs = arr.chunks(2).collect::<Vec<_>>();
+        assert_eq!(3, pairs.len());
+        assert_eq!(&Array::from((0, 0))[..], pairs[1]);
+
+        let windows = arr.windows(5).collect::<Vec<_>>();
+        assert_eq!(2, windows.len());
+        assert_eq!(&arr[1..], windows[1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_sized_chunks() {
+        let _ = Array::from((1, 2)).chunks(0);
+    }
 }
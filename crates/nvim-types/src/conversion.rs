@@ -1,6 +1,8 @@
 //! Traits for converting between Neovim [`Object`]s and Rust types.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::ops::{Range, RangeInclusive};
 
 use thiserror::Error as ThisError;
 
@@ -26,6 +28,15 @@ pub enum Error {
     #[error(transparent)]
     FromUtf8(#[from] std::string::FromUtf8Error),
 
+    #[error("Unknown flag \"{0}\"")]
+    UnknownFlag(std::string::String),
+
+    #[error("Unknown variant \"{0}\"")]
+    UnknownVariant(std::string::String),
+
+    #[error("Invalid range: {0}")]
+    InvalidRange(&'static str),
+
     #[cfg(feature = "serde")]
     #[error(transparent)]
     Serde(#[from] crate::serde::Error),
@@ -121,7 +132,7 @@ impl FromObject for Array {
             ObjectKind::Array => Ok(unsafe { obj.into_array_unchecked() }),
 
             other => Err(Error::FromWrongType {
-                expected: "string",
+                expected: "array",
                 actual: other.as_static(),
             }),
         }
@@ -134,7 +145,7 @@ impl FromObject for Dictionary {
             ObjectKind::Dictionary => Ok(unsafe { obj.into_dict_unchecked() }),
 
             other => Err(Error::FromWrongType {
-                expected: "string",
+                expected: "dictionary",
                 actual: other.as_static(),
             }),
         }
@@ -204,6 +215,18 @@ impl FromObject for String {
     }
 }
 
+impl FromObject for Cow<'_, str> {
+    fn from_object(obj: Object) -> Result<Self, Error> {
+        String::from_object(obj).map(Cow::Owned)
+    }
+}
+
+impl FromObject for Box<str> {
+    fn from_object(obj: Object) -> Result<Self, Error> {
+        String::from_object(obj).map(String::into_boxed_str)
+    }
+}
+
 impl<T> FromObject for Option<T>
 where
     T: FromObject,
@@ -276,3 +299,489 @@ where
             .map(Into::into)
     }
 }
+
+/// Implements `ToObject` and `FromObject` for ranges of an integer type.
+///
+/// Ranges are converted into `[start, end]` arrays where `end` is always
+/// exclusive, like the line ranges taken by Neovim's API, so `2..5` and
+/// `2..=4` are both converted into `[2, 5]` and converted back into `2..5`
+/// or `2..=4`. Ranges whose start is greater than their end are rejected.
+macro_rules! range_impls {
+    ($($type:ty),*) => {
+        $(
+            impl ToObject for Range<$type> {
+                fn to_object(self) -> Result<Object, Error> {
+                    range_to_object(self.start, self.end)
+                }
+            }
+
+            impl ToObject for RangeInclusive<$type> {
+                fn to_object(self) -> Result<Object, Error> {
+                    let (start, end) = self.into_inner();
+                    let end = end.checked_add(1).ok_or(Error::InvalidRange(
+                        "end is too large to be made exclusive",
+                    ))?;
+                    range_to_object(start, end)
+                }
+            }
+
+            impl FromObject for Range<$type> {
+                fn from_object(obj: Object) -> Result<Self, Error> {
+                    range_from_object(obj).map(|(start, end)| start..end)
+                }
+            }
+
+            impl FromObject for RangeInclusive<$type> {
+                fn from_object(obj: Object) -> Result<Self, Error> {
+                    let (start, end) = range_from_object::<$type>(obj)?;
+                    let end = end.checked_sub(1).ok_or(Error::InvalidRange(
+                        "end is too small to be made inclusive",
+                    ))?;
+                    Ok(start..=end)
+                }
+            }
+        )*
+    };
+}
+
+range_impls!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+
+fn range_to_object<T>(start: T, end: T) -> Result<Object, Error>
+where
+    T: ToObject + PartialOrd,
+{
+    if start > end {
+        return Err(Error::InvalidRange("start is greater than end"));
+    }
+
+    Ok(Array::from_iter([start.to_object()?, end.to_object()?]).into())
+}
+
+fn range_from_object<T>(obj: Object) -> Result<(T, T), Error>
+where
+    T: FromObject + PartialOrd,
+{
+    let array = Array::from_object(obj)?;
+
+    if array.len() != 2 {
+        return Err(Error::InvalidRange("expected an array of two elements"));
+    }
+
+    let mut iter = array.into_iter();
+    let start = T::from_object(iter.next().unwrap())?;
+    let end = T::from_object(iter.next().unwrap())?;
+
+    if start > end {
+        return Err(Error::InvalidRange("start is greater than end"));
+    }
+
+    Ok((start, end))
+}
+
+// A blanket `impl<T: FromObject> TryFrom<Object> for T` would conflict with
+// the standard library's `impl<T, U: Into<T>> TryFrom<U> for T`, so we have to
+// implement `TryFrom<Object>` for every type individually.
+//
+/// Implements `TryFrom<Object>` for a type that implements `FromObject`.
+macro_rules! try_from_obj {
+    ($($type:ty),*) => {
+        $(
+            impl TryFrom<Object> for $type {
+                type Error = Error;
+
+                #[inline(always)]
+                fn try_from(obj: Object) -> Result<Self, Self::Error> {
+                    <Self as FromObject>::from_object(obj)
+                }
+            }
+        )*
+    };
+}
+
+try_from_obj!(
+    (),
+    Boolean,
+    Integer,
+    Float,
+    crate::String,
+    Array,
+    Dictionary,
+    i8,
+    u8,
+    i16,
+    u16,
+    i32,
+    u32,
+    u64,
+    i128,
+    u128,
+    isize,
+    usize,
+    f32,
+    String,
+    Cow<'_, str>,
+    Box<str>
+);
+
+impl<A, R> TryFrom<Object> for Function<A, R> {
+    type Error = Error;
+
+    #[inline(always)]
+    fn try_from(obj: Object) -> Result<Self, Self::Error> {
+        Self::from_object(obj)
+    }
+}
+
+impl<T> TryFrom<Object> for Vec<T>
+where
+    T: FromObject,
+{
+    type Error = Error;
+
+    #[inline(always)]
+    fn try_from(obj: Object) -> Result<Self, Self::Error> {
+        Self::from_object(obj)
+    }
+}
+
+// Types implementing `Into<Object>` already get an infallible `TryFrom` impl
+// from the standard library, these are the ones that can fail.
+//
+/// Implements `TryFrom<..> for Object` for a type that implements `ToObject`.
+macro_rules! try_into_obj {
+    ($($type:ty),*) => {
+        $(
+            impl TryFrom<$type> for Object {
+                type Error = Error;
+
+                #[inline(always)]
+                fn try_from(value: $type) -> Result<Self, Self::Error> {
+                    value.to_object()
+                }
+            }
+        )*
+    };
+}
+
+try_into_obj!(u64, isize, usize, i128, u128);
+
+impl<T> TryFrom<Vec<T>> for Object
+where
+    T: ToObject,
+{
+    type Error = Error;
+
+    #[inline(always)]
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        vec.to_object()
+    }
+}
+
+impl<K, V> TryFrom<HashMap<K, V>> for Object
+where
+    K: Into<crate::String>,
+    V: ToObject,
+{
+    type Error = Error;
+
+    #[inline(always)]
+    fn try_from(map: HashMap<K, V>) -> Result<Self, Self::Error> {
+        map.to_object()
+    }
+}
+
+/// How a [`Flags`] type is represented by Neovim.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlagsFormat {
+    /// A string of comma-separated flag names, e.g. `"menu,preview"` for
+    /// [`completeopt`](https://neovim.io/doc/user/options.html#'completeopt').
+    CommaString,
+
+    /// An integer where each flag is a bit.
+    Bitmask,
+
+    /// A string with one character per flag, e.g. `"tcqj"` for
+    /// [`formatoptions`](https://neovim.io/doc/user/options.html#'formatoptions').
+    CharString,
+}
+
+/// What to do with flags that are not listed in [`Flags::FLAGS`] when
+/// converting an [`Object`] into a [`Flags`] type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnknownFlags {
+    /// Return an [`Error::UnknownFlag`].
+    Error,
+
+    /// Silently drop them.
+    Ignore,
+}
+
+/// Trait implemented by sets of flags (e.g. the types generated by the
+/// [`bitflags`](https://docs.rs/bitflags) crate) that Neovim encodes as
+/// strings or bitmasks.
+///
+/// Once a type implements it, [`impl_flags_conversion!`] can be used to
+/// implement [`FromObject`] and [`ToObject`] for it.
+pub trait Flags: Sized {
+    /// The name and the bit of every flag. With
+    /// [`FlagsFormat::CharString`] the names should be single characters.
+    const FLAGS: &'static [(&'static str, u64)];
+
+    /// How the flags are represented as an [`Object`].
+    const FORMAT: FlagsFormat;
+
+    /// What to do with unknown flags when parsing an [`Object`].
+    const UNKNOWN: UnknownFlags = UnknownFlags::Error;
+
+    /// Returns the bits of the flags that are set.
+    fn bits(&self) -> u64;
+
+    /// Creates the set from its bits. Only bits of flags listed in
+    /// [`FLAGS`](Flags::FLAGS) are ever passed.
+    fn from_bits(bits: u64) -> Self;
+}
+
+/// Converts a set of flags into an [`Object`] in the format given by
+/// [`Flags::FORMAT`].
+pub fn flags_to_object<F: Flags>(flags: &F) -> Object {
+    let bits = flags.bits();
+
+    let set = F::FLAGS
+        .iter()
+        .filter(|&&(_, flag)| flag != 0 && bits & flag == flag)
+        .map(|&(name, _)| name);
+
+    match F::FORMAT {
+        FlagsFormat::CommaString => set.collect::<Vec<_>>().join(",").into(),
+        FlagsFormat::CharString => set.collect::<std::string::String>().into(),
+        FlagsFormat::Bitmask => (bits as Integer).into(),
+    }
+}
+
+/// Parses a set of flags from an [`Object`] in the format given by
+/// [`Flags::FORMAT`], handling unknown flags according to
+/// [`Flags::UNKNOWN`].
+pub fn flags_from_object<F: Flags>(obj: Object) -> Result<F, Error> {
+    let lookup = |name: &str| -> Result<u64, Error> {
+        match F::FLAGS.iter().find(|&&(flag, _)| flag == name) {
+            Some(&(_, bit)) => Ok(bit),
+            None if F::UNKNOWN == UnknownFlags::Ignore => Ok(0),
+            None => Err(Error::UnknownFlag(name.to_owned())),
+        }
+    };
+
+    let bits = match F::FORMAT {
+        FlagsFormat::CommaString => std::string::String::from_object(obj)?
+            .split(',')
+            .filter(|name| !name.is_empty())
+            .try_fold(0, |bits, name| lookup(name).map(|bit| bits | bit))?,
+
+        FlagsFormat::CharString => std::string::String::from_object(obj)?
+            .chars()
+            .try_fold(0, |bits, ch| {
+                lookup(ch.encode_utf8(&mut [0; 4])).map(|bit| bits | bit)
+            })?,
+
+        FlagsFormat::Bitmask => {
+            let bits = u64::from_object(obj)?;
+            let known =
+                F::FLAGS.iter().fold(0, |known, &(_, bit)| known | bit);
+
+            match bits & !known {
+                0 => bits,
+                _ if F::UNKNOWN == UnknownFlags::Ignore => bits & known,
+                unknown => {
+                    return Err(Error::UnknownFlag(format!("{unknown:#x}")))
+                },
+            }
+        },
+    };
+
+    Ok(F::from_bits(bits))
+}
+
+/// Implements [`FromObject`] and [`ToObject`] for a type implementing
+/// [`Flags`].
+///
+/// # Examples
+///
+/// ```
+/// use nvim_types::conversion::{Flags, FlagsFormat};
+///
+/// struct FormatOptions(u64);
+///
+/// impl Flags for FormatOptions {
+///     const FLAGS: &'static [(&'static str, u64)] =
+///         &[("t", 1), ("c", 1 << 1), ("q", 1 << 2)];
+///
+///     const FORMAT: FlagsFormat = FlagsFormat::CharString;
+///
+///     fn bits(&self) -> u64 {
+///         self.0
+///     }
+///
+///     fn from_bits(bits: u64) -> Self {
+///         Self(bits)
+///     }
+/// }
+///
+/// nvim_types::impl_flags_conversion!(FormatOptions);
+/// ```
+#[macro_export]
+macro_rules! impl_flags_conversion {
+    ($type:ty) => {
+        impl $crate::conversion::FromObject for $type {
+            fn from_object(
+                obj: $crate::Object,
+            ) -> ::core::result::Result<Self, $crate::conversion::Error> {
+                $crate::conversion::flags_from_object(obj)
+            }
+        }
+
+        impl ::core::convert::From<$type> for $crate::Object {
+            fn from(flags: $type) -> Self {
+                $crate::conversion::flags_to_object(&flags)
+            }
+        }
+    };
+}
+
+pub use impl_flags_conversion;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_from_object() {
+        let res: Result<i64, _> = Object::from(42).try_into();
+        assert_eq!(Ok(42), res);
+
+        let res = u8::try_from(Object::from(-1));
+        assert!(matches!(res, Err(Error::FromInt(_))), "{res:?}");
+
+        let res =
+            Vec::<String>::try_from(Object::from(Array::from(("foo", "bar"))));
+        assert_eq!(Ok(vec!["foo".to_owned(), "bar".to_owned()]), res);
+
+        let res = bool::try_from(Object::from("foo"));
+        assert_eq!(
+            Err(Error::FromWrongType { expected: "bool", actual: "string" }),
+            res
+        );
+    }
+
+    #[test]
+    fn try_into_object() {
+        let res = Object::try_from(u64::MAX);
+        assert!(matches!(res, Err(Error::FromInt(_))), "{res:?}");
+
+        let res = Object::try_from(vec![1usize, 2, 3]);
+        assert_eq!(Ok(Object::from(Array::from((1, 2, 3)))), res);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct FormatOptions(u64);
+
+    impl Flags for FormatOptions {
+        const FLAGS: &'static [(&'static str, u64)] =
+            &[("t", 1), ("c", 1 << 1), ("q", 1 << 2), ("j", 1 << 3)];
+
+        const FORMAT: FlagsFormat = FlagsFormat::CharString;
+
+        fn bits(&self) -> u64 {
+            self.0
+        }
+
+        fn from_bits(bits: u64) -> Self {
+            Self(bits)
+        }
+    }
+
+    impl_flags_conversion!(FormatOptions);
+
+    #[derive(Debug, PartialEq)]
+    struct CompleteOpt(u64);
+
+    impl Flags for CompleteOpt {
+        const FLAGS: &'static [(&'static str, u64)] =
+            &[("menu", 1), ("menuone", 1 << 1), ("preview", 1 << 2)];
+
+        const FORMAT: FlagsFormat = FlagsFormat::CommaString;
+
+        const UNKNOWN: UnknownFlags = UnknownFlags::Ignore;
+
+        fn bits(&self) -> u64 {
+            self.0
+        }
+
+        fn from_bits(bits: u64) -> Self {
+            Self(bits)
+        }
+    }
+
+    impl_flags_conversion!(CompleteOpt);
+
+    #[test]
+    fn flags_round_trip() {
+        let opts = FormatOptions::from_object(Object::from("jqt")).unwrap();
+        assert_eq!(FormatOptions(0b1101), opts);
+
+        let obj = opts.to_object().unwrap();
+        assert_eq!(Object::from("tqj"), obj);
+        assert_eq!(Ok(FormatOptions(0b1101)), FormatOptions::from_object(obj));
+
+        assert_eq!(
+            Ok(FormatOptions(0)),
+            FormatOptions::from_object(Object::from(""))
+        );
+    }
+
+    #[test]
+    fn flags_unknown() {
+        assert_eq!(
+            Err(Error::UnknownFlag("x".to_owned())),
+            FormatOptions::from_object(Object::from("tx"))
+        );
+
+        let opt =
+            CompleteOpt::from_object(Object::from("menu,noselect,preview"));
+        assert_eq!(Ok(CompleteOpt(0b101)), opt);
+        assert_eq!(
+            Object::from("menu,preview"),
+            Object::from(CompleteOpt(0b101))
+        );
+    }
+
+    #[test]
+    fn ranges() {
+        let obj = (2usize..5).to_object().unwrap();
+        assert_eq!(Object::from(Array::from((2, 5))), obj);
+        assert_eq!(Ok(2usize..5), Range::from_object(obj.clone()));
+        assert_eq!(Ok(2i64..=4), RangeInclusive::from_object(obj.clone()));
+        assert_eq!(Ok(obj), (2i64..=4).to_object());
+
+        // Empty ranges.
+        let end = 2u32;
+        let obj = (3u32..3).to_object().unwrap();
+        assert_eq!(Ok(obj.clone()), (3..=end).to_object());
+        assert_eq!(Ok(3..=end), RangeInclusive::from_object(obj));
+        assert!(RangeInclusive::<usize>::from_object(
+            Array::from((0, 0)).into()
+        )
+        .is_err());
+
+        let start = 5i64;
+        assert_eq!(
+            Err(Error::InvalidRange("start is greater than end")),
+            (start..2).to_object()
+        );
+        assert!((0u8..=u8::MAX).to_object().is_err());
+
+        assert!(Range::<i64>::from_object(Array::from((5, 2)).into()).is_err());
+        assert!(Range::<i64>::from_object(Array::from((1,)).into()).is_err());
+        assert!(
+            Range::<usize>::from_object(Array::from((-1, 2)).into()).is_err()
+        );
+    }
+}
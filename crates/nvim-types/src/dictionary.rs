@@ -60,6 +60,17 @@ impl Dictionary {
         self.iter_mut()
             .find_map(|pair| (&pair.key == query).then_some(&mut pair.value))
     }
+
+    /// Returns a view of the dictionary whose `Serialize` implementation
+    /// sorts the keys by their bytes, both in this dictionary and in all the
+    /// dictionaries nested in it. Useful to get a stable output regardless of
+    /// the order the keys were inserted in, e.g. for snapshot tests.
+    ///
+    /// Serializing a `Dictionary` directly emits its keys in insertion order.
+    #[cfg(feature = "serde")]
+    pub fn sorted_keys(&self) -> SortedKeys<'_> {
+        SortedKeys(self)
+    }
 }
 
 impl fmt::Debug for Dictionary {
@@ -216,6 +227,38 @@ where
     }
 }
 
+/// A view of a [`Dictionary`] serialized with sorted keys, returned by
+/// [`Dictionary::sorted_keys`].
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug)]
+pub struct SortedKeys<'a>(&'a Dictionary);
+
+#[cfg(feature = "serde")]
+mod serde {
+    use serde::ser::{Serialize, Serializer};
+
+    use super::{Dictionary, SortedKeys};
+    use crate::object::serde::serialize_dict;
+
+    impl Serialize for Dictionary {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serialize_dict(self, false, serializer)
+        }
+    }
+
+    impl Serialize for SortedKeys<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serialize_dict(self.0, true, serializer)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Dictionary, Object, String as NvimString};
@@ -282,4 +325,35 @@ mod tests {
 
         assert_eq!(String::from("{foo: {a: 1}}"), format!("{dict}"));
     }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn serialize_dict() {
+        let dict = Dictionary::from_iter([
+            ("b", Object::from(Dictionary::from_iter([("y", 1), ("x", 2)]))),
+            ("a", Object::from(true)),
+        ]);
+
+        let json = serde_json::to_string(&dict).unwrap();
+        assert_eq!(r#"{"b":{"y":1,"x":2},"a":true}"#, json);
+        assert_eq!(json, serde_json::to_string(&dict.clone()).unwrap());
+
+        let json = serde_json::to_string(&dict.sorted_keys()).unwrap();
+        assert_eq!(r#"{"a":true,"b":{"x":2,"y":1}}"#, json);
+        assert_eq!(json, serde_json::to_string(&dict.sorted_keys()).unwrap());
+    }
+
+    #[test]
+    fn clear() {
+        let mut dict = Dictionary::from_iter([("foo", 1), ("bar", 2)]);
+        let capacity = dict.capacity();
+
+        dict.clear();
+        assert!(dict.is_empty());
+        assert_eq!(None, dict.get(&"foo"));
+        assert_eq!(capacity, dict.capacity());
+
+        dict.push(("baz", 3).into());
+        assert_eq!(Some(&Object::from(3)), dict.get(&"baz"));
+    }
 }
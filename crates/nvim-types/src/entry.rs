@@ -0,0 +1,236 @@
+use std::mem;
+
+use super::{Dictionary, KeyValuePair, Object, String};
+
+impl Dictionary {
+    /// Gets the entry for `key` for in-place manipulation, analogous to
+    /// [`HashMap::entry`](std::collections::HashMap::entry).
+    ///
+    /// Dictionaries share their layout with Neovim, so they're stored as a
+    /// flat list of pairs and looking up the key is linear in the number of
+    /// pairs. The lookup only happens once though, the returned [`Entry`]
+    /// remembers where the key is.
+    pub fn entry<K>(&mut self, key: K) -> Entry<'_>
+    where
+        K: Into<String>,
+    {
+        let key = key.into();
+
+        match self.iter().position(|pair| pair.key == key) {
+            Some(index) => {
+                Entry::Occupied(OccupiedEntry { dict: self, index })
+            },
+            None => Entry::Vacant(VacantEntry { dict: self, key }),
+        }
+    }
+}
+
+/// A view into a single entry of a [`Dictionary`], which may either be
+/// vacant or occupied. Returned by [`Dictionary::entry`].
+#[derive(Debug)]
+pub enum Entry<'a> {
+    Occupied(OccupiedEntry<'a>),
+    Vacant(VacantEntry<'a>),
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the key of the entry.
+    #[inline]
+    pub fn key(&self) -> &String {
+        match self {
+            Self::Occupied(entry) => entry.key(),
+            Self::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, and returns a mutable
+    /// reference to the value in the entry.
+    #[inline]
+    pub fn or_insert<V>(self, default: V) -> &'a mut Object
+    where
+        V: Into<Object>,
+    {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the entry is vacant, and returns a
+    /// mutable reference to the value in the entry.
+    #[inline]
+    pub fn or_insert_with<F, V>(self, default: F) -> &'a mut Object
+    where
+        F: FnOnce() -> V,
+        V: Into<Object>,
+    {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Calls `fun` on the value of the entry if it's occupied.
+    #[inline]
+    pub fn and_modify<F>(mut self, fun: F) -> Self
+    where
+        F: FnOnce(&mut Object),
+    {
+        if let Self::Occupied(entry) = &mut self {
+            fun(entry.get_mut());
+        }
+        self
+    }
+}
+
+/// A view into an occupied entry of a [`Dictionary`].
+#[derive(Debug)]
+pub struct OccupiedEntry<'a> {
+    dict: &'a mut Dictionary,
+    index: usize,
+}
+
+impl<'a> OccupiedEntry<'a> {
+    /// Returns the key of the entry.
+    #[inline]
+    pub fn key(&self) -> &String {
+        &self.pair().key
+    }
+
+    /// Returns a reference to the value of the entry.
+    #[inline]
+    pub fn get(&self) -> &Object {
+        &self.pair().value
+    }
+
+    /// Returns a mutable reference to the value of the entry.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Object {
+        &mut self.dict.as_mut_slice()[self.index].value
+    }
+
+    /// Converts the entry into a mutable reference to its value, bound to
+    /// the lifetime of the dictionary.
+    #[inline]
+    pub fn into_mut(self) -> &'a mut Object {
+        let Self { dict, index } = self;
+        &mut dict.as_mut_slice()[index].value
+    }
+
+    /// Sets the value of the entry, returning the old value.
+    #[inline]
+    pub fn insert<V>(&mut self, value: V) -> Object
+    where
+        V: Into<Object>,
+    {
+        mem::replace(self.get_mut(), value.into())
+    }
+
+    /// Removes the entry from the dictionary, returning its value. The other
+    /// pairs keep their order.
+    #[inline]
+    pub fn remove(self) -> Object {
+        self.dict.as_mut_slice()[self.index..].rotate_left(1);
+        self.dict.pop().expect("the dictionary isn't empty").value
+    }
+
+    #[inline]
+    fn pair(&self) -> &KeyValuePair {
+        &self.dict.as_slice()[self.index]
+    }
+}
+
+/// A view into a vacant entry of a [`Dictionary`].
+#[derive(Debug)]
+pub struct VacantEntry<'a> {
+    dict: &'a mut Dictionary,
+    key: String,
+}
+
+impl<'a> VacantEntry<'a> {
+    /// Returns the key that would be used when inserting a value.
+    #[inline]
+    pub fn key(&self) -> &String {
+        &self.key
+    }
+
+    /// Takes ownership of the key.
+    #[inline]
+    pub fn into_key(self) -> String {
+        self.key
+    }
+
+    /// Inserts a pair with the key of the entry and `value` at the end of
+    /// the dictionary, returning a mutable reference to the value.
+    #[inline]
+    pub fn insert<V>(self, value: V) -> &'a mut Object
+    where
+        V: Into<Object>,
+    {
+        let Self { dict, key } = self;
+        dict.push(KeyValuePair { key, value: value.into() });
+        let last = dict.len() - 1;
+        &mut dict.as_mut_slice()[last].value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn or_insert() {
+        let mut dict = Dictionary::from_iter([("foo", 1)]);
+
+        *dict.entry("foo").or_insert(0) = Object::from(2);
+        dict.entry("bar").or_insert(3);
+        dict.entry("bar").or_insert(4);
+
+        assert_eq!(Dictionary::from_iter([("foo", 2), ("bar", 3)]), dict);
+    }
+
+    #[test]
+    fn and_modify() {
+        let mut dict = Dictionary::from_iter([("foo", 1)]);
+
+        for key in ["foo", "bar"] {
+            dict.entry(key)
+                .and_modify(|obj| *obj = Object::from(true))
+                .or_insert(false);
+        }
+
+        assert_eq!(
+            Dictionary::from_iter([("foo", true), ("bar", false)]),
+            dict
+        );
+    }
+
+    #[test]
+    fn occupied_entry() {
+        let mut dict = Dictionary::from_iter([("a", 1), ("b", 2), ("c", 3)]);
+
+        let mut entry = match dict.entry("b") {
+            Entry::Occupied(entry) => entry,
+            Entry::Vacant(_) => panic!("\"b\" is in the dictionary"),
+        };
+
+        assert_eq!(entry.key(), "b");
+        assert_eq!(&Object::from(2), entry.get());
+        assert_eq!(Object::from(2), entry.insert(20));
+        assert_eq!(Object::from(20), entry.remove());
+
+        assert_eq!(Dictionary::from_iter([("a", 1), ("c", 3)]), dict);
+    }
+
+    #[test]
+    fn vacant_entry() {
+        let mut dict = Dictionary::new();
+
+        let entry = match dict.entry("foo") {
+            Entry::Vacant(entry) => entry,
+            Entry::Occupied(_) => panic!("the dictionary is empty"),
+        };
+
+        assert_eq!(entry.key(), "foo");
+        *entry.insert(1) = Object::from(2);
+
+        assert_eq!(Some(&Object::from(2)), dict.get(&"foo"));
+    }
+}
@@ -0,0 +1,215 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Wake, Waker};
+
+use libuv_bindings::AsyncHandle;
+use luajit_bindings::{self as lua, ffi, macros::cstr};
+
+use crate::{Error, Function};
+
+type Task = Pin<Box<dyn Future<Output = ()> + 'static>>;
+
+thread_local! {
+    static EXECUTOR: RefCell<Option<Rc<Executor>>> = RefCell::new(None);
+}
+
+/// Polls the futures passed to [`block_on`] on Neovim's thread, from the
+/// callback of an [`AsyncHandle`] which is triggered every time one of them
+/// is woken up.
+struct Executor {
+    handle: AsyncHandle,
+    tasks: RefCell<HashMap<usize, Task>>,
+    next_id: Cell<usize>,
+    woken: Arc<Mutex<Vec<usize>>>,
+}
+
+/// Wakes up a task from any thread by queueing its id and triggering the
+/// executor's [`AsyncHandle`].
+struct TaskWaker {
+    id: usize,
+    woken: Arc<Mutex<Vec<usize>>>,
+    handle: AsyncHandle,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.lock().unwrap().push(self.id);
+        // This only fails if the handle has been closed, in which case
+        // there's nothing left to poll the task anyway.
+        let _ = self.handle.send();
+    }
+}
+
+impl Executor {
+    /// Returns the executor of the current thread, creating it the first
+    /// time it's needed.
+    fn get() -> Result<Rc<Self>, libuv_bindings::Error> {
+        EXECUTOR.with(|executor| {
+            if let Some(executor) = &*executor.borrow() {
+                return Ok(Rc::clone(executor));
+            }
+
+            let handle = AsyncHandle::new(|| {
+                if let Some(executor) = EXECUTOR.with(|e| e.borrow().clone()) {
+                    executor.poll_woken();
+                }
+                Ok::<_, Infallible>(())
+            })?;
+
+            let new = Rc::new(Self {
+                handle,
+                tasks: RefCell::default(),
+                next_id: Cell::new(0),
+                woken: Arc::default(),
+            });
+
+            *executor.borrow_mut() = Some(Rc::clone(&new));
+
+            Ok(new)
+        })
+    }
+
+    /// Adds `task` to the executor and polls it once, returning its id.
+    fn spawn(&self, task: Task) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.poll(id, task);
+        id
+    }
+
+    /// Drops the task with the given id, if it hasn't completed yet.
+    fn cancel(&self, id: usize) {
+        let task = self.tasks.borrow_mut().remove(&id);
+        drop(task);
+    }
+
+    fn poll_woken(&self) {
+        let woken = std::mem::take(&mut *self.woken.lock().unwrap());
+
+        for id in woken {
+            // The task may have already completed if it was woken up more
+            // than once.
+            let task = self.tasks.borrow_mut().remove(&id);
+            if let Some(task) = task {
+                self.poll(id, task);
+            }
+        }
+    }
+
+    /// Polls `task` without holding a borrow on the other tasks, which lets
+    /// it block on other futures while it's running.
+    fn poll(&self, id: usize, mut task: Task) {
+        let waker = Waker::from(Arc::new(TaskWaker {
+            id,
+            woken: Arc::clone(&self.woken),
+            handle: self.handle.clone(),
+        }));
+
+        let mut cx = Context::from_waker(&waker);
+
+        if task.as_mut().poll(&mut cx).is_pending() {
+            self.tasks.borrow_mut().insert(id, task);
+        }
+    }
+}
+
+/// Runs `fut` to completion on Neovim's thread.
+///
+/// The future is polled from the libuv event loop every time it's woken up,
+/// while the caller waits for it in `vim.wait()`. This keeps the loop
+/// running, so the future can also await work done on Neovim's thread, like
+/// the callbacks of other libuv handles.
+pub(crate) fn block_on<F>(fut: F) -> Result<F::Output, Error>
+where
+    F: Future + 'static,
+{
+    let output = Rc::new(RefCell::new(None));
+
+    let task = {
+        let output = Rc::clone(&output);
+        async move { *output.borrow_mut() = Some(fut.await) }
+    };
+
+    let executor = Executor::get().map_err(Error::from_err)?;
+
+    let id = executor.spawn(Box::pin(task));
+
+    // Futures which are ready right away don't need to wait.
+    if let Some(output) = output.borrow_mut().take() {
+        return Ok(output);
+    }
+
+    let is_done = {
+        let output = Rc::clone(&output);
+        Function::<(), bool>::from_fn(move |()| {
+            Ok::<_, Infallible>(output.borrow().is_some())
+        })
+    };
+
+    let waited = wait(&is_done);
+
+    is_done.remove_from_lua_registry();
+
+    if let Err(err) = waited {
+        executor.cancel(id);
+        return Err(err);
+    }
+
+    let output = output.borrow_mut().take();
+
+    Ok(output.expect("`vim.wait()` returns once the future has completed"))
+}
+
+/// Calls `vim.wait()` until `is_done` returns `true`.
+fn wait(is_done: &Function<(), bool>) -> Result<(), Error> {
+    loop {
+        let (done, code) = unsafe {
+            lua::with_state(|lstate| {
+                let top = ffi::lua_gettop(lstate);
+
+                ffi::lua_getglobal(lstate, cstr!("vim"));
+                ffi::lua_getfield(lstate, -1, cstr!("wait"));
+                ffi::lua_pushinteger(lstate, i32::MAX as ffi::lua_Integer);
+
+                if let Err(err) = lua::Pushable::push(is_done.clone(), lstate)
+                {
+                    ffi::lua_settop(lstate, top);
+                    return Err(Error::from_err(err));
+                }
+
+                let res = match ffi::lua_pcall(lstate, 2, 2, 0) {
+                    ffi::LUA_OK => Ok((
+                        ffi::lua_toboolean(lstate, -2) != 0,
+                        ffi::lua_tointeger(lstate, -1),
+                    )),
+
+                    _ => Err(Error::from_str(lua::utils::error_message(
+                        lstate, -1,
+                    ))),
+                };
+
+                ffi::lua_settop(lstate, top);
+
+                res
+            })?
+        };
+
+        match (done, code) {
+            (true, _) => return Ok(()),
+
+            // Timed out, keep waiting.
+            (false, -1) => continue,
+
+            _ => return Err(Error::from_str("Interrupted while waiting")),
+        }
+    }
+}
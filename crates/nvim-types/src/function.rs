@@ -130,6 +130,64 @@ impl<A, R> Function<A, R> {
         })
     }
 
+    /// Creates a new function from an `async` closure.
+    ///
+    /// Calls from Lua are synchronous, so the caller waits for the returned
+    /// future in `vim.wait()`. The future is polled on Neovim's thread from
+    /// the [libuv] event loop, which keeps running in the meantime: it can be
+    /// driven by an external runtime like `tokio` as well as by the
+    /// callbacks of other libuv handles. Pressing `<C-c>` while waiting
+    /// cancels the future and makes the call fail.
+    ///
+    /// Like `vim.wait()`, the function can't be called from a fast event
+    /// (see `:h api-fast`).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use std::time::Duration;
+    ///
+    /// use nvim_oxi::{self as oxi, Function};
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let handle = runtime.handle().clone();
+    ///
+    /// let sleep = Function::from_async_fn(move |ms: u64| {
+    ///     let duration = Duration::from_millis(ms);
+    ///     let task = handle.spawn(tokio::time::sleep(duration));
+    ///     async move {
+    ///         task.await.unwrap();
+    ///         Ok::<_, oxi::Error>(ms)
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// [libuv]: https://libuv.org/
+    #[cfg(feature = "async")]
+    pub fn from_async_fn<F, Fut, E>(fun: F) -> Self
+    where
+        F: Fn(A) -> Fut + 'static,
+        Fut: std::future::Future<Output = Result<R, E>> + 'static,
+        A: Poppable,
+        R: Pushable + 'static,
+        E: StdError + 'static,
+    {
+        Self::from_fn(move |args| {
+            crate::executor::block_on(fun(args))?.map_err(Error::from_err)
+        })
+    }
+
+    /// Returns whether `self` and `other` reference the same Lua function,
+    /// e.g. because one is a clone of the other or because they were both
+    /// popped from the same Lua value.
+    ///
+    /// Two functions created separately never compare equal, even if they
+    /// were created from the same closure.
+    pub fn ptr_eq<B, S>(&self, other: &Function<B, S>) -> bool {
+        self.lua_ref == other.lua_ref
+            || lua::function::ref_eq(self.lua_ref, other.lua_ref)
+    }
+
     pub fn call(&self, args: A) -> Result<R, lua::Error>
     where
         A: Pushable,
@@ -138,6 +196,36 @@ impl<A, R> Function<A, R> {
         lua::function::call(self.lua_ref, args)
     }
 
+    /// Partially applies the function, returning a new `Function` which calls
+    /// this one with `args` followed by the arguments it's called with.
+    ///
+    /// Both `args` and the remaining arguments `Rest` are pushed as separate
+    /// values, so binding `(a,)` to a `Function<(A, B), R>` gives a
+    /// `Function<(B,), R>`. The bound arguments are owned by the new function
+    /// and cloned on every call.
+    pub fn bind<B, Rest>(&self, args: B) -> Function<Rest, R>
+    where
+        B: Pushable + Clone + 'static,
+        Rest: Pushable + Poppable + 'static,
+        R: Pushable + Poppable + 'static,
+    {
+        let fun = Function::<(B, Rest), R>::from_ref(self.lua_ref);
+        Function::from_fn(move |rest| fun.call((args.clone(), rest)))
+    }
+
+    /// Composes two functions, returning a new `Function` which calls this
+    /// one and then passes its return value to `other`.
+    pub fn then<T>(&self, other: &Function<R, T>) -> Function<A, T>
+    where
+        A: Pushable + Poppable + 'static,
+        R: Pushable + Poppable + 'static,
+        T: Pushable + Poppable + 'static,
+    {
+        let first = Function::<A, R>::from_ref(self.lua_ref);
+        let second = Function::<R, T>::from_ref(other.lua_ref);
+        Function::from_fn(move |args| second.call(first.call(args)?))
+    }
+
     /// Consumes the `Function`, removing the reference stored in the Lua
     /// registry.
     #[doc(hidden)]
@@ -0,0 +1,79 @@
+//! Constructors for [`Array`]s and [`Dictionary`]s from JSON strings.
+
+use serde::de::Error as _;
+
+use crate::conversion::{Error, FromObject};
+use crate::{Array, Dictionary, Object};
+
+/// Parses a JSON string into an [`Object`].
+fn object_from_json_str(json: &str) -> Result<Object, Error> {
+    serde_json::from_str::<Object>(json).map_err(|err| {
+        crate::serde::Error::custom(format!("invalid JSON: {err}")).into()
+    })
+}
+
+impl Array {
+    /// Creates an [`Array`] by parsing a JSON array, e.g. `[1, "foo", true]`.
+    ///
+    /// Note that `null`s are skipped, just like when collecting an iterator
+    /// of [`Object`]s into an [`Array`].
+    pub fn from_json_str(json: &str) -> Result<Self, Error> {
+        Self::from_object(object_from_json_str(json)?)
+    }
+}
+
+impl Dictionary {
+    /// Creates a [`Dictionary`] by parsing a JSON object, e.g.
+    /// `{"foo": 1, "bar": [true]}`.
+    ///
+    /// Note that keys whose value is `null` are skipped, just like when
+    /// collecting an iterator of key-value pairs into a [`Dictionary`].
+    pub fn from_json_str(json: &str) -> Result<Self, Error> {
+        Self::from_object(object_from_json_str(json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dictionary_from_json() {
+        let dict =
+            Dictionary::from_json_str(r#"{"foo": 42, "bar": ["baz", true]}"#);
+        assert!(dict.is_ok(), "{dict:?}");
+
+        let dict = dict.unwrap();
+        assert_eq!(Some(&Object::from(42)), dict.get(&"foo"));
+        assert_eq!(
+            Some(&Object::from(Array::from(("baz", true)))),
+            dict.get(&"bar")
+        );
+    }
+
+    #[test]
+    fn array_from_json() {
+        let arr = Array::from_json_str(r#"[1, 2.5, "foo", {"bar": false}]"#);
+        assert!(arr.is_ok(), "{arr:?}");
+        assert_eq!(4, arr.unwrap().len());
+    }
+
+    #[test]
+    fn from_json_wrong_type() {
+        let res = Dictionary::from_json_str("[1, 2, 3]");
+        assert!(
+            matches!(res, Err(Error::FromWrongType { actual: "array", .. })),
+            "{res:?}"
+        );
+    }
+
+    #[test]
+    fn from_json_invalid() {
+        let res = Array::from_json_str("[1, 2");
+        assert!(res.is_err());
+
+        let msg = res.unwrap_err().to_string();
+        assert!(msg.starts_with("invalid JSON:"), "{msg}");
+        assert!(msg.contains("line 1"), "{msg}");
+    }
+}
@@ -1,6 +1,7 @@
 //! This module contains functionality common to both `Array`s and
 //! `Dictionary`s.
 
+use std::mem::{self, ManuallyDrop};
 use std::ops::{Deref, DerefMut};
 use std::ptr;
 use std::slice;
@@ -36,12 +37,97 @@ impl<T> KVec<T> {
         Self { items: std::ptr::null_mut(), size: 0, capacity: 0 }
     }
 
+    /// Creates a new empty `Collection` with space for at least `capacity`
+    /// items.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity == 0 {
+            return Self::new();
+        }
+        Vec::with_capacity(capacity).into()
+    }
+
     /// The number of items in the collection.
     #[inline]
     pub const fn len(&self) -> usize {
         self.size
     }
 
+    /// The number of items the collection can hold without reallocating.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Reserves capacity for at least `additional` more items.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        if self.capacity - self.size >= additional {
+            return;
+        }
+        let mut vec = Vec::from(mem::take(self));
+        vec.reserve(additional);
+        *self = vec.into();
+    }
+
+    /// Appends an item to the back of the collection.
+    #[inline]
+    pub fn push(&mut self, item: T) {
+        if self.size == self.capacity {
+            self.reserve(1);
+        }
+        unsafe { ptr::write(self.items.add(self.size), item) };
+        self.size += 1;
+    }
+
+    /// Removes the last item from the collection and returns it, or `None`
+    /// if the collection is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.size -= 1;
+        Some(unsafe { ptr::read(self.items.add(self.size)) })
+    }
+
+    /// Retains only the items for which `f` returns `true`, preserving
+    /// their order.
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut vec = Vec::from(mem::take(self));
+        vec.retain(f);
+        *self = vec.into();
+    }
+
+    /// Removes consecutive repeated items from the collection.
+    #[inline]
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut vec = Vec::from(mem::take(self));
+        vec.dedup();
+        *self = vec.into();
+    }
+
+    /// Removes all the items from the collection, keeping the memory it has
+    /// already allocated so that it can be refilled without reallocating.
+    #[inline]
+    pub fn clear(&mut self) {
+        if self.is_empty() {
+            return;
+        }
+        let items = ptr::slice_from_raw_parts_mut(self.items, self.size);
+        // Reset the size first, so the collection is still valid if one of
+        // the destructors panics.
+        self.size = 0;
+        unsafe { ptr::drop_in_place(items) }
+    }
+
     #[inline]
     pub const fn is_empty(&self) -> bool {
         self.len() == 0
@@ -129,6 +215,8 @@ impl<T> From<Vec<T>> for KVec<T> {
 impl<T> From<KVec<T>> for Vec<T> {
     #[inline]
     fn from(coll: KVec<T>) -> Self {
+        // The items are now owned by the `Vec`, so we must not drop them.
+        let coll = ManuallyDrop::new(coll);
         unsafe {
             if coll.items.is_null() {
                 Vec::new()
@@ -1,26 +1,39 @@
 #![allow(clippy::missing_safety_doc)]
 use std::ffi::{c_double, c_int};
 
+mod arena;
 mod array;
 pub mod conversion;
 mod dictionary;
+mod entry;
 mod error;
+#[cfg(feature = "async")]
+mod executor;
 mod function;
+#[cfg(feature = "json")]
+mod json;
 mod kvec;
+mod macros;
 mod non_owning;
 mod object;
 #[cfg(feature = "serde")]
 pub mod serde;
 mod string;
+mod visitor;
 
+pub use arena::ObjectArena;
 pub use array::{Array, ArrayIterator};
+#[cfg(feature = "serde")]
+pub use dictionary::SortedKeys;
 pub use dictionary::{DictIterator, Dictionary, KeyValuePair};
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
 pub use error::Error;
 pub use function::Function;
 pub use kvec::KVec;
 pub use non_owning::NonOwning;
 pub use object::{Object, ObjectKind};
-pub use string::String;
+pub use string::{String, StringBuilder};
+pub use visitor::ObjectVisitor;
 
 // https://github.com/neovim/neovim/blob/master/src/nvim/api/private/defs.h#L67
 #[doc(hidden)]
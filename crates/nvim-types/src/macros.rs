@@ -0,0 +1,133 @@
+/// Creates a [`Dictionary`](crate::Dictionary) from a list of `key => value`
+/// pairs.
+///
+/// The keys can be anything that converts into a [`String`](crate::String),
+/// while the values can be of any type implementing
+/// [`ToObject`](crate::conversion::ToObject), including other dictionaries
+/// and arrays built with [`dict!`] and [`array!`]. Unlike collecting into a
+/// `Dictionary`, values converted to nil are kept.
+///
+/// # Panics
+///
+/// Panics if a value fails to convert, e.g. if a `u64` doesn't fit in an
+/// `i64`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::{array, dict, Object};
+///
+/// let config = dict! {
+///     "relative" => "editor",
+///     "size" => array![80, 24],
+///     "border" => dict! { "style" => "rounded" },
+/// };
+///
+/// assert_eq!(Some(&Object::from("editor")), config.get(&"relative"));
+/// ```
+#[macro_export]
+macro_rules! dict {
+    () => {
+        $crate::Dictionary::new()
+    };
+
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut dict = $crate::Dictionary::new();
+        $(
+            dict.push($crate::KeyValuePair::from((
+                $key,
+                $crate::conversion::ToObject::to_object($value)
+                    .expect("couldn't convert dictionary value"),
+            )));
+        )+
+        dict
+    }};
+}
+
+/// Creates an [`Array`](crate::Array) from a list of values.
+///
+/// The values can be of any type implementing
+/// [`ToObject`](crate::conversion::ToObject), and they don't need to have
+/// the same type.
+///
+/// # Panics
+///
+/// Panics if a value fails to convert, e.g. if a `u64` doesn't fit in an
+/// `i64`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::{array, dict};
+///
+/// let args = array!["foo", 42, true, dict! { "bar" => "baz" }];
+/// assert_eq!(4, args.len());
+/// ```
+#[macro_export]
+macro_rules! array {
+    () => {
+        $crate::Array::new()
+    };
+
+    ($($value:expr),+ $(,)?) => {{
+        let mut array = $crate::Array::new();
+        $(
+            array.push(
+                $crate::conversion::ToObject::to_object($value)
+                    .expect("couldn't convert array value"),
+            );
+        )+
+        array
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversion::FromObject;
+    use crate::{Array, Dictionary, Object};
+
+    #[test]
+    fn empty() {
+        assert!(dict! {}.is_empty());
+        assert!(array![].is_empty());
+    }
+
+    #[test]
+    fn keeps_nils() {
+        let dict = dict! { "foo" => (), "bar" => None::<u8> };
+        assert_eq!(2, dict.len());
+        assert_eq!(Some(&Object::nil()), dict.get(&"foo"));
+
+        let array = array![(), 1, ()];
+        assert_eq!(&[Object::nil(), Object::from(1), Object::nil()], &*array);
+    }
+
+    #[test]
+    fn nested() {
+        let dict = dict! {
+            "name" => "oxi",
+            "version" => 8u64,
+            "tags" => array!["nvim", "rust", array![1, 2.5]],
+            "opts" => dict! { "debug" => true },
+        };
+
+        assert_eq!(Some(&Object::from("oxi")), dict.get(&"name"));
+        assert_eq!(Some(&Object::from(8)), dict.get(&"version"));
+
+        let tags = Array::from_object(dict["tags"].clone()).unwrap();
+        assert_eq!(3, tags.len());
+        assert_eq!(Object::from("rust"), tags[1]);
+
+        let numbers = Array::from_object(tags[2].clone()).unwrap();
+        assert_eq!(&[Object::from(1), Object::from(2.5)], &*numbers);
+
+        let opts = Dictionary::from_object(dict["opts"].clone()).unwrap();
+        assert_eq!(Some(&Object::from(true)), opts.get(&"debug"));
+    }
+
+    #[test]
+    #[should_panic(expected = "couldn't convert array value")]
+    fn conversion_failure() {
+        let _ = array![u64::MAX];
+    }
+}
@@ -1,13 +1,16 @@
 use std::borrow::Cow;
 use std::ffi::c_int;
 use std::fmt;
-use std::mem::ManuallyDrop;
+use std::hash::{Hash, Hasher};
+use std::mem::{self, ManuallyDrop};
 
 use lua::{ffi::*, Poppable, Pushable};
 use luajit_bindings as lua;
 
+use crate::conversion::{self, FromObject};
 use crate::{
     Array,
+    ArrayIterator,
     Boolean,
     Dictionary,
     Float,
@@ -22,6 +25,16 @@ use crate::{
 /// Binding to a Neovim object.
 ///
 /// Represents any valid Neovim type.
+///
+/// # Cloning
+///
+/// Cloning an `Object` always performs a deep copy: strings, arrays and
+/// dictionaries are recursively copied into new allocations, so mutating a
+/// clone never affects the original (and vice versa).
+///
+/// The only exception are Lua references (i.e. objects of kind
+/// [`ObjectKind::LuaRef`]), which are copied as-is and keep pointing to the
+/// same value in the Lua registry.
 #[repr(C)]
 pub struct Object {
     ty: ObjectKind,
@@ -162,6 +175,205 @@ impl Object {
         self.data.luaref
     }
 
+    #[inline(always)]
+    pub(crate) unsafe fn as_string_unchecked(&self) -> &crate::String {
+        &self.data.string
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn as_array_unchecked(&self) -> &Array {
+        &self.data.array
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn as_dict_unchecked(&self) -> &Dictionary {
+        &self.data.dictionary
+    }
+
+    /// Returns the number of bytes this object has allocated on the heap,
+    /// including the allocations of all its nested objects. It doesn't
+    /// include the size of the `Object` itself.
+    pub fn heap_size(&self) -> usize {
+        unsafe {
+            match self.ty {
+                ObjectKind::String => self.data.string.heap_size(),
+
+                ObjectKind::Array => {
+                    let array = &self.data.array;
+                    array.capacity * mem::size_of::<Object>()
+                        + array.iter().map(Object::heap_size).sum::<usize>()
+                },
+
+                ObjectKind::Dictionary => {
+                    let dict = &self.data.dictionary;
+                    dict.capacity * mem::size_of::<crate::KeyValuePair>()
+                        + dict
+                            .iter()
+                            .map(|pair| {
+                                pair.key.heap_size() + pair.value.heap_size()
+                            })
+                            .sum::<usize>()
+                },
+
+                _ => 0,
+            }
+        }
+    }
+
+    /// Lossily coerces the object into an [`Integer`] following Vimscript's
+    /// rules, returning `None` if there's no sensible conversion.
+    ///
+    /// Strings are parsed like Vimscript does, i.e. ignoring leading
+    /// whitespace and anything after the number, so `"5"` and `" 5abc"` both
+    /// give `5`. Hexadecimal (`0x`) and binary (`0b`) prefixes are
+    /// recognized, but, unlike in legacy Vimscript, a leading zero doesn't
+    /// make the number octal. Unlike Vimscript, strings not starting with a
+    /// number give `None` instead of `0`. Floats are truncated towards zero.
+    pub fn coerce_to_integer(&self) -> Option<Integer> {
+        unsafe {
+            match self.ty {
+                ObjectKind::Boolean => Some(self.data.boolean as Integer),
+
+                ObjectKind::Integer
+                | ObjectKind::Buffer
+                | ObjectKind::Window
+                | ObjectKind::TabPage => Some(self.data.integer),
+
+                ObjectKind::Float => {
+                    let float = self.data.float;
+                    (!float.is_nan()).then_some(float as Integer)
+                },
+
+                ObjectKind::String => {
+                    parse_integer(self.data.string.as_bytes())
+                },
+
+                _ => None,
+            }
+        }
+    }
+
+    /// Lossily coerces the object into a [`String`](crate::String). Returns
+    /// `None` for nil, arrays, dictionaries and Lua references.
+    ///
+    /// Booleans and numbers are formatted like Vimscript's [`string()`][1]
+    /// does, so `true` gives `"v:true"` and `1e20` gives `"1.0e20"`, while
+    /// strings are returned as is instead of being quoted.
+    ///
+    /// [1]: https://neovim.io/doc/user/builtin.html#string()
+    pub fn coerce_to_string(&self) -> Option<crate::String> {
+        unsafe {
+            match self.ty {
+                ObjectKind::Boolean => {
+                    let str =
+                        if self.data.boolean { "v:true" } else { "v:false" };
+                    Some(str.into())
+                },
+
+                ObjectKind::Integer
+                | ObjectKind::Buffer
+                | ObjectKind::Window
+                | ObjectKind::TabPage => {
+                    Some(self.data.integer.to_string().into())
+                },
+
+                ObjectKind::Float => {
+                    Some(format_float(self.data.float).into())
+                },
+
+                ObjectKind::String => Some((*self.data.string).clone()),
+
+                _ => None,
+            }
+        }
+    }
+
+    /// Lossily coerces the object into a boolean using Vimscript's notion of
+    /// truthiness, where nil, zero and strings which don't start with a
+    /// non-zero number are false. Returns `None` for arrays, dictionaries and
+    /// Lua references.
+    pub fn coerce_to_bool(&self) -> Option<bool> {
+        match self.ty {
+            ObjectKind::Nil => Some(false),
+            ObjectKind::Boolean => Some(unsafe { self.data.boolean }),
+            ObjectKind::Float => Some(unsafe { self.data.float } != 0.0),
+            ObjectKind::String => {
+                Some(matches!(self.coerce_to_integer(), Some(n) if n != 0))
+            },
+            _ => self.coerce_to_integer().map(|n| n != 0),
+        }
+    }
+
+    /// Returns the object nested in this one at `path`, or `None` if there
+    /// isn't one.
+    ///
+    /// The path is made of segments separated by dots: a segment indexes
+    /// into a dictionary by key, or into an array if it's a number. For
+    /// example `"a.b.1"` returns the second element of the array at key `b`
+    /// of the dictionary at key `a`. An empty path returns the object itself.
+    ///
+    /// There's no way to escape dots, so keys containing them can't be
+    /// reached. Use [`Dictionary::get`] for those.
+    pub fn get_path(&self, path: &str) -> Option<&Object> {
+        if path.is_empty() {
+            return Some(self);
+        }
+
+        path.split('.').try_fold(self, |obj, segment| unsafe {
+            match obj.ty {
+                ObjectKind::Array => {
+                    obj.data.array.get(segment.parse::<usize>().ok()?)
+                },
+                ObjectKind::Dictionary => obj.data.dictionary.get(&segment),
+                _ => None,
+            }
+        })
+    }
+
+    /// Consumes the object, returning an iterator over the elements of the
+    /// [`Array`] it contains. Fails if the object isn't an array.
+    pub fn into_array_iter(self) -> Result<ArrayIterator, conversion::Error> {
+        Array::from_object(self).map(IntoIterator::into_iter)
+    }
+
+    /// Feeds the structure of this object into `state`, such that objects
+    /// which compare equal also hash equally.
+    pub(crate) fn hash_structure<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(&self.ty).hash(state);
+
+        unsafe {
+            use ObjectKind::*;
+            match self.ty {
+                Nil => {},
+                Boolean => self.data.boolean.hash(state),
+                Integer | Buffer | Window | TabPage => {
+                    self.data.integer.hash(state)
+                },
+                // `0.0` and `-0.0` compare equal but have different bits.
+                Float => {
+                    let float = self.data.float;
+                    let bits = if float == 0.0 { 0 } else { float.to_bits() };
+                    bits.hash(state)
+                },
+                String => self.data.string.as_bytes().hash(state),
+                Array => {
+                    self.data.array.len().hash(state);
+                    for obj in self.data.array.iter() {
+                        obj.hash_structure(state);
+                    }
+                },
+                Dictionary => {
+                    self.data.dictionary.len().hash(state);
+                    for pair in self.data.dictionary.iter() {
+                        pair.key.as_bytes().hash(state);
+                        pair.value.hash_structure(state);
+                    }
+                },
+                LuaRef => self.data.luaref.hash(state),
+            }
+        }
+    }
+
     /// Extracts the contained [`String`](crate::String) value without checking
     /// that the object actually contains a [`String`](crate::String).
     pub unsafe fn into_string_unchecked(self) -> crate::String {
@@ -184,6 +396,85 @@ impl Object {
     }
 }
 
+/// Parses the number at the start of `bytes` like Vimscript's `str2nr()`,
+/// saturating on overflow. Returns `None` if `bytes` doesn't start with a
+/// number.
+fn parse_integer(bytes: &[u8]) -> Option<Integer> {
+    let bytes = match bytes.iter().position(|b| !b.is_ascii_whitespace()) {
+        Some(start) => &bytes[start..],
+        None => return None,
+    };
+
+    let (negative, bytes) = match bytes.split_first() {
+        Some((b'-', rest)) => (true, rest),
+        _ => (false, bytes),
+    };
+
+    let (radix, digits) = match bytes {
+        [b'0', b'x' | b'X', digit, ..] if digit.is_ascii_hexdigit() => {
+            (16, &bytes[2..])
+        },
+        [b'0', b'b' | b'B', rest @ ..]
+            if matches!(rest.first(), Some(b'0' | b'1')) =>
+        {
+            (2, rest)
+        },
+        _ => (10, bytes),
+    };
+
+    let mut digits =
+        digits.iter().map_while(|&b| (b as char).to_digit(radix)).peekable();
+
+    digits.peek()?;
+
+    // The magnitude is accumulated unsigned so that the most negative number
+    // is parsed exactly and larger ones saturate to it, like `str2nr()`.
+    let n = digits.fold(0u64, |n, digit| {
+        n.saturating_mul(radix as u64).saturating_add(digit as u64)
+    });
+
+    Some(match (negative, Integer::try_from(n)) {
+        (false, Ok(n)) => n,
+        (false, Err(_)) => Integer::MAX,
+        (true, Ok(n)) => -n,
+        (true, Err(_)) => Integer::MIN,
+    })
+}
+
+/// Formats a float like Vimscript's `string()`, which uses `%g` but always
+/// keeps a fractional part and writes the exponent without a `+` sign or
+/// leading zeroes.
+fn format_float(float: Float) -> std::string::String {
+    if float.is_nan() {
+        return "str2float('nan')".to_owned();
+    }
+
+    if float.is_infinite() {
+        let sign = if float < 0.0 { "-" } else { "" };
+        return format!("{sign}str2float('inf')");
+    }
+
+    let abs = float.abs();
+
+    let formatted = if abs == 0.0 || (0.001..1e7).contains(&abs) {
+        format!("{float:.6}")
+    } else {
+        // Rust's exponent is already formatted the way Vim wants it.
+        format!("{float:.6e}")
+    };
+
+    let (mantissa, exponent) = match formatted.find('e') {
+        Some(idx) => formatted.split_at(idx),
+        None => (&*formatted, ""),
+    };
+
+    // Remove trailing zeroes, but keep the one just after the dot.
+    let mantissa = mantissa.trim_end_matches('0');
+    let zero = if mantissa.ends_with('.') { "0" } else { "" };
+
+    format!("{mantissa}{zero}{exponent}")
+}
+
 macro_rules! clone_copy {
     ($self:expr, $field:ident) => {{
         Self {
@@ -207,6 +498,8 @@ macro_rules! clone_drop {
 }
 
 impl Clone for Object {
+    // Strings, arrays and dictionaries are deep-copied by their own `Clone`
+    // implementations, the other kinds are plain old data.
     fn clone(&self) -> Self {
         match self.ty {
             ObjectKind::Nil => Self::nil(),
@@ -270,6 +563,42 @@ impl PartialEq<Self> for Object {
     }
 }
 
+// The following impls compare an `Object` against a native value. They only
+// check the value if the object is of the corresponding kind, so for example
+// `Object::from(1) == 1.0` is `false`: integer objects never compare equal to
+// floats, even if they hold the same number.
+
+impl PartialEq<Boolean> for Object {
+    #[inline]
+    fn eq(&self, other: &Boolean) -> bool {
+        self.ty == ObjectKind::Boolean
+            && unsafe { self.data.boolean } == *other
+    }
+}
+
+impl PartialEq<Integer> for Object {
+    #[inline]
+    fn eq(&self, other: &Integer) -> bool {
+        self.ty == ObjectKind::Integer
+            && unsafe { self.data.integer } == *other
+    }
+}
+
+impl PartialEq<Float> for Object {
+    #[inline]
+    fn eq(&self, other: &Float) -> bool {
+        self.ty == ObjectKind::Float && unsafe { self.data.float } == *other
+    }
+}
+
+impl PartialEq<&str> for Object {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.ty == ObjectKind::String
+            && unsafe { self.data.string.as_bytes() } == other.as_bytes()
+    }
+}
+
 impl From<()> for Object {
     fn from(_: ()) -> Self {
         Self::nil()
@@ -401,6 +730,13 @@ impl From<Cow<'_, str>> for Object {
     }
 }
 
+impl From<Box<str>> for Object {
+    #[inline(always)]
+    fn from(boxed: Box<str>) -> Self {
+        Self::from(&*boxed)
+    }
+}
+
 impl<T> FromIterator<T> for Object
 where
     T: Into<Object>,
@@ -466,9 +802,9 @@ impl Poppable for Object {
 
             LUA_TTABLE => {
                 if lua::utils::is_table_array(lstate, -1) {
-                    Array::pop(lstate).map(Into::into)
+                    <Array as lua::Poppable>::pop(lstate).map(Into::into)
                 } else {
-                    Dictionary::pop(lstate).map(Into::into)
+                    <Dictionary as lua::Poppable>::pop(lstate).map(Into::into)
                 }
             },
 
@@ -492,14 +828,124 @@ impl Poppable for Object {
 }
 
 #[cfg(feature = "serde")]
-mod serde {
+pub(crate) mod serde {
     use std::fmt;
 
     use serde::de::{self, Deserialize};
+    use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
 
-    use super::Object;
+    use super::{Object, ObjectKind};
     use crate::{Array, Dictionary, Integer, LuaRef};
 
+    /// Buffer, window and tabpage handles are serialized as integers, while
+    /// Lua references are serialized as `f32`s to match the `Deserialize`
+    /// implementation. Dictionaries are serialized in insertion order.
+    impl Serialize for Object {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            ObjectRef { obj: self, sorted_keys: false }.serialize(serializer)
+        }
+    }
+
+    /// A reference to an [`Object`] which, when serialized, optionally sorts
+    /// the keys of all the dictionaries it contains.
+    struct ObjectRef<'a> {
+        obj: &'a Object,
+        sorted_keys: bool,
+    }
+
+    impl Serialize for ObjectRef<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let obj = self.obj;
+
+            unsafe {
+                match obj.kind() {
+                    ObjectKind::Nil => serializer.serialize_unit(),
+
+                    ObjectKind::Boolean => {
+                        serializer.serialize_bool(obj.as_boolean_unchecked())
+                    },
+
+                    ObjectKind::Integer
+                    | ObjectKind::Buffer
+                    | ObjectKind::Window
+                    | ObjectKind::TabPage => {
+                        serializer.serialize_i64(obj.as_integer_unchecked())
+                    },
+
+                    ObjectKind::Float => {
+                        serializer.serialize_f64(obj.as_float_unchecked())
+                    },
+
+                    ObjectKind::String => {
+                        obj.as_string_unchecked().serialize(serializer)
+                    },
+
+                    ObjectKind::Array => serialize_array(
+                        obj.as_array_unchecked(),
+                        self.sorted_keys,
+                        serializer,
+                    ),
+
+                    ObjectKind::Dictionary => serialize_dict(
+                        obj.as_dict_unchecked(),
+                        self.sorted_keys,
+                        serializer,
+                    ),
+
+                    ObjectKind::LuaRef => serializer
+                        .serialize_f32(obj.as_luaref_unchecked() as f32),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn serialize_array<S>(
+        array: &Array,
+        sorted_keys: bool,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(array.len()))?;
+        for obj in array.iter() {
+            seq.serialize_element(&ObjectRef { obj, sorted_keys })?;
+        }
+        seq.end()
+    }
+
+    /// Serializes a dictionary, sorting its keys by their bytes if
+    /// `sorted_keys` is `true`, or in insertion order otherwise.
+    pub(crate) fn serialize_dict<S>(
+        dict: &Dictionary,
+        sorted_keys: bool,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut pairs = dict.iter().collect::<Vec<_>>();
+
+        if sorted_keys {
+            pairs.sort_by(|a, b| a.key.as_bytes().cmp(b.key.as_bytes()));
+        }
+
+        let mut map = serializer.serialize_map(Some(pairs.len()))?;
+        for pair in pairs {
+            map.serialize_entry(
+                &pair.key,
+                &ObjectRef { obj: &pair.value, sorted_keys },
+            )?;
+        }
+        map.end()
+    }
+
     impl<'de> Deserialize<'de> for Object {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
@@ -623,6 +1069,64 @@ mod tests {
         assert_eq!(str, str_again.unwrap());
     }
 
+    #[test]
+    fn clone_dict_is_deep() {
+        let original = Object::from(Dictionary::from_iter([
+            ("foo", Object::from("foo")),
+            ("bar", Object::from(Array::from_iter(["bar"]))),
+        ]));
+
+        let mut clone = original.clone();
+
+        {
+            let dict = unsafe { &mut clone.data.dictionary };
+            dict["foo"] = Object::from("changed");
+
+            let arr = unsafe { &mut dict["bar"].data.array };
+            arr[0] = Object::from(42);
+        }
+
+        assert_eq!("{foo: \"foo\", bar: [\"bar\"]}", &format!("{original}"));
+        assert_eq!("{foo: \"changed\", bar: [42]}", &format!("{clone}"));
+    }
+
+    #[test]
+    fn get_path() {
+        let obj = Object::from(Dictionary::from_iter([(
+            "a",
+            Object::from(Dictionary::from_iter([(
+                "b",
+                Object::from(Array::from((1, "foo", true))),
+            )])),
+        )]));
+
+        assert_eq!(Some(&Object::from("foo")), obj.get_path("a.b.1"));
+        assert_eq!(Some(&obj), obj.get_path(""));
+        assert_eq!(None, obj.get_path("a.b.3"));
+        assert_eq!(None, obj.get_path("a.b.foo"));
+        assert_eq!(None, obj.get_path("a.c"));
+        assert_eq!(None, obj.get_path("a.b.1.0"));
+    }
+
+    #[test]
+    fn into_array_iter() {
+        let obj = Object::from(Array::from((1, "foo", true)));
+        let items = obj.into_array_iter().unwrap().collect::<Vec<_>>();
+        assert_eq!(
+            vec![Object::from(1), Object::from("foo"), Object::from(true)],
+            items
+        );
+
+        let err = Object::from("foo").into_array_iter().err();
+        assert_eq!(
+            Some(conversion::Error::FromWrongType {
+                expected: "array",
+                actual: "string"
+            }),
+            err
+        );
+    }
+
     #[test]
     fn print_nil() {
         let obj = Object::nil();
@@ -681,4 +1185,81 @@ mod tests {
         assert_eq!("LuaRef(42)", &format!("{obj:?}"));
         assert_eq!("LuaRef(42)", &format!("{obj}"));
     }
+
+    #[test]
+    fn eq_native() {
+        assert_eq!(Object::from(true), true);
+        assert_ne!(Object::from(false), true);
+
+        assert_eq!(Object::from(42), 42);
+        assert_ne!(Object::from(42), 43);
+        assert_ne!(Object::from(42.0), 42);
+
+        assert_eq!(Object::from(4.2), 4.2);
+        assert_ne!(Object::from(42), 42.0);
+
+        assert_eq!(Object::from("foo"), "foo");
+        assert_ne!(Object::from("foo"), "bar");
+
+        assert_ne!(Object::nil(), false);
+        assert_ne!(Object::nil(), 0);
+        assert_ne!(Object::nil(), "");
+    }
+
+    #[test]
+    fn coerce() {
+        assert_eq!(Some(5), Object::from("5").coerce_to_integer());
+        assert_eq!(Some(-12), Object::from(" -12abc").coerce_to_integer());
+        assert_eq!(Some(255), Object::from("0xff").coerce_to_integer());
+        assert_eq!(Some(5), Object::from("0b101").coerce_to_integer());
+        assert_eq!(Some(0), Object::from("0x").coerce_to_integer());
+        assert_eq!(None, Object::from("abc").coerce_to_integer());
+        assert_eq!(None, Object::from("").coerce_to_integer());
+        assert_eq!(Some(1), Object::from(true).coerce_to_integer());
+        assert_eq!(Some(-2), Object::from(-2.7).coerce_to_integer());
+        assert_eq!(None, Object::nil().coerce_to_integer());
+
+        let min = "-9223372036854775808";
+        assert_eq!(Some(i64::MIN), Object::from(min).coerce_to_integer());
+        let max = "9223372036854775807";
+        assert_eq!(Some(i64::MAX), Object::from(max).coerce_to_integer());
+        let huge = "99999999999999999999";
+        assert_eq!(Some(i64::MAX), Object::from(huge).coerce_to_integer());
+        let tiny = "-99999999999999999999";
+        assert_eq!(Some(i64::MIN), Object::from(tiny).coerce_to_integer());
+
+        assert_eq!(
+            Some(crate::String::from("42")),
+            Object::from(42).coerce_to_string()
+        );
+        assert_eq!(
+            Some(crate::String::from("1.0")),
+            Object::from(1.0).coerce_to_string()
+        );
+        assert_eq!(None, Object::from(Array::new()).coerce_to_string());
+
+        let string = |obj: Object| {
+            obj.coerce_to_string().unwrap().to_string_lossy().into_owned()
+        };
+        assert_eq!("v:true", string(Object::from(true)));
+        assert_eq!("v:false", string(Object::from(false)));
+        assert_eq!("0.1", string(Object::from(0.1)));
+        assert_eq!("-0.0", string(Object::from(-0.0)));
+        assert_eq!("123456.789", string(Object::from(123456.789)));
+        assert_eq!("1.234568e7", string(Object::from(12345678.9)));
+        assert_eq!("1.0e20", string(Object::from(1e20)));
+        assert_eq!("1.5e-5", string(Object::from(1.5e-5)));
+        assert_eq!("str2float('nan')", string(Object::from(f64::NAN)));
+        assert_eq!(
+            "-str2float('inf')",
+            string(Object::from(f64::NEG_INFINITY))
+        );
+        assert_eq!("foo", string(Object::from("foo")));
+
+        assert_eq!(Some(true), Object::from("1").coerce_to_bool());
+        assert_eq!(Some(false), Object::from("foo").coerce_to_bool());
+        assert_eq!(Some(false), Object::nil().coerce_to_bool());
+        assert_eq!(Some(true), Object::from(3).coerce_to_bool());
+        assert_eq!(None, Object::from(Dictionary::new()).coerce_to_bool());
+    }
 }
@@ -10,3 +10,69 @@ mod ser;
 pub use de::Deserializer;
 pub use error::{Error, Result};
 pub use ser::Serializer;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::Object;
+
+/// Serializes `value` into an [`Object`].
+///
+/// Together with [`from_object`] this can be used to implement
+/// [`ToObject`](crate::conversion::ToObject) and
+/// [`FromObject`](crate::conversion::FromObject) for any type deriving
+/// `Serialize` and `Deserialize`.
+#[inline]
+pub fn to_object<T>(value: &T) -> Result<Object>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer::new())
+}
+
+/// Deserializes an instance of `T` from an [`Object`].
+#[inline]
+pub fn from_object<T>(obj: Object) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Deserializer::new(obj))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::conversion::FromObject;
+    use crate::{Array, Dictionary};
+
+    #[test]
+    fn to_object_basic() {
+        assert_eq!(Ok(Object::nil()), to_object(&()));
+        assert_eq!(Ok(Object::from(1.5)), to_object(&1.5));
+        assert_eq!(Ok(Object::from("foo")), to_object("foo"));
+        assert_eq!(
+            Ok(Object::from(Array::from((1, true)))),
+            to_object(&(1, true))
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let map = HashMap::from([
+            (String::from("foo"), vec![(1, true), (2, false)]),
+            (String::from("bar"), Vec::new()),
+        ]);
+
+        let obj = to_object(&map).unwrap();
+        let dict = Dictionary::from_object(obj.clone()).unwrap();
+        assert_eq!(
+            Some(&Object::from(Array::from_iter([
+                Array::from((1, true)),
+                Array::from((2, false)),
+            ]))),
+            dict.get(&"foo")
+        );
+
+        assert_eq!(Ok(map), from_object(obj));
+    }
+}
@@ -1,9 +1,9 @@
 use std::borrow::Cow;
-use std::ffi::{c_char, c_int, OsStr};
-use std::mem::ManuallyDrop;
+use std::ffi::{c_char, c_int, CStr, FromBytesWithNulError, OsStr, OsString};
+use std::mem::{self, ManuallyDrop};
 use std::path::PathBuf;
 use std::string::{self, String as StdString};
-use std::{fmt, slice, str};
+use std::{fmt, io, slice, str};
 
 use lua::{ffi::*, Poppable, Pushable};
 use luajit_bindings as lua;
@@ -39,17 +39,49 @@ impl String {
     }
 
     /// Creates a [`String`] from a byte vector.
+    ///
+    /// The vector's buffer is reused without copying its contents. It's only
+    /// resized if its spare capacity isn't exactly one byte, which is what's
+    /// needed to store the null terminator.
     #[inline]
     pub fn from_bytes(mut vec: Vec<u8>) -> Self {
         vec.reserve_exact(1);
         Vec::push(&mut vec, 0);
 
         let size = vec.len() - 1;
-        let data = vec.leak().as_mut_ptr() as *mut c_char;
+
+        // `Drop` deallocates `size + 1` bytes, so the allocation has to be
+        // exactly that long.
+        let data =
+            Box::leak(vec.into_boxed_slice()).as_mut_ptr() as *mut c_char;
 
         Self { data, size }
     }
 
+    /// Creates a [`String`] from a Rust string, reusing its buffer like
+    /// [`from_bytes`](String::from_bytes) does.
+    ///
+    /// This is named after [`std::string::String::from_utf8_unchecked`], but
+    /// since a Rust string is always valid UTF-8 there's nothing to check and
+    /// it doesn't need to be `unsafe`. To avoid reallocating, the string
+    /// should have exactly one byte of spare capacity, e.g. if it was created
+    /// with [`with_capacity`](std::string::String::with_capacity)`(len + 1)`.
+    #[inline]
+    pub fn from_utf8_unchecked(string: StdString) -> Self {
+        Self::from_bytes(string.into_bytes())
+    }
+
+    /// Returns a [`StringBuilder`] with room for `capacity` bytes plus the
+    /// null terminator.
+    ///
+    /// Neovim's strings don't store a capacity, so they can't have spare
+    /// room to grow into. The builder can, and it's converted into a
+    /// `String` without reallocating if it's filled up to `capacity` bytes.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> StringBuilder {
+        StringBuilder::with_capacity(capacity)
+    }
+
     /// Returns `true` if the `String` has a length of zero, and `false`
     /// otherwise.
     #[inline]
@@ -87,6 +119,21 @@ impl String {
         str::from_utf8(self.as_bytes())
     }
 
+    /// Borrows the `String` as a [`CStr`] without copying it, relying on the
+    /// final null byte. Fails if the `String` contains interior null bytes.
+    /// An empty `String` that hasn't allocated returns an empty `CStr`.
+    #[inline]
+    pub fn as_c_str(&self) -> Result<&CStr, FromBytesWithNulError> {
+        if self.data.is_null() {
+            Ok(Default::default())
+        } else {
+            let bytes = unsafe {
+                slice::from_raw_parts(self.data as *const u8, self.size + 1)
+            };
+            CStr::from_bytes_with_nul(bytes)
+        }
+    }
+
     /// Converts the `String` into Rust's `std::string::String`. If it already
     /// holds a valid UTF-8 byte sequence no allocation is made. If it doesn't
     /// the `String` is copied and all invalid sequences are replaced with `�`.
@@ -101,12 +148,14 @@ impl String {
         if self.data.is_null() {
             Vec::new()
         } else {
+            // SAFETY: the allocation is `size + 1` bytes long, the last one
+            // being the null terminator which is left out of the vector.
             unsafe {
                 let mdrop = ManuallyDrop::new(self);
                 Vec::from_raw_parts(
                     mdrop.data.cast::<u8>(),
                     mdrop.size,
-                    mdrop.size,
+                    mdrop.size + 1,
                 )
             }
         }
@@ -119,6 +168,199 @@ impl String {
         StdString::from_utf8(self.into_bytes())
     }
 
+    /// Creates a new `String` by repeating this one `n` times. Repeating it
+    /// zero times returns an empty string.
+    #[inline]
+    pub fn repeat(&self, n: usize) -> Self {
+        Self::from_bytes(self.as_bytes().repeat(n))
+    }
+
+    /// Creates a new `String` by concatenating the bytes of all the `parts`.
+    #[inline]
+    pub fn concat(parts: &[&String]) -> Self {
+        let len = parts.iter().map(|part| part.len()).sum::<usize>();
+        let mut bytes = Vec::with_capacity(len + 1);
+        for part in parts {
+            bytes.extend_from_slice(part.as_bytes());
+        }
+        Self::from_bytes(bytes)
+    }
+
+    /// Returns the byte offset of the first occurrence of `needle` in the
+    /// `String`, or `None` if it's not contained in it. An empty `needle`
+    /// matches at offset zero.
+    #[inline]
+    pub fn find(&self, needle: &[u8]) -> Option<usize> {
+        if needle.is_empty() {
+            return Some(0);
+        }
+        self.as_bytes().windows(needle.len()).position(|win| win == needle)
+    }
+
+    /// Returns the byte offset of the last occurrence of `needle` in the
+    /// `String`, or `None` if it's not contained in it. An empty `needle`
+    /// matches at the end of the string.
+    #[inline]
+    pub fn rfind(&self, needle: &[u8]) -> Option<usize> {
+        if needle.is_empty() {
+            return Some(self.len());
+        }
+        self.as_bytes().windows(needle.len()).rposition(|win| win == needle)
+    }
+
+    /// Returns an iterator over the `char`s of the `String` and their byte
+    /// offsets. Since the `String` may not contain valid UTF-8, invalid byte
+    /// sequences are skipped.
+    pub fn char_indices(&self) -> impl Iterator<Item = (usize, char)> + '_ {
+        let mut rest = self.as_bytes();
+        let mut offset = 0;
+
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+
+            let (valid, invalid_len) = match str::from_utf8(rest) {
+                Ok(valid) => (valid, 0),
+
+                Err(err) => {
+                    let (valid, invalid) = rest.split_at(err.valid_up_to());
+                    // SAFETY: `valid_up_to` is the length of the longest
+                    // valid UTF-8 prefix.
+                    let valid = unsafe { str::from_utf8_unchecked(valid) };
+                    (valid, err.error_len().unwrap_or(invalid.len()))
+                },
+            };
+
+            let start = offset;
+            offset += valid.len() + invalid_len;
+            rest = &rest[valid.len() + invalid_len..];
+
+            Some(valid.char_indices().map(move |(idx, ch)| (start + idx, ch)))
+        })
+        .flatten()
+    }
+
+    /// Returns an iterator over the non-empty byte slices of the `String`
+    /// separated by ASCII whitespace. Unlike [`str::split_whitespace`] this
+    /// doesn't require the `String` to contain valid UTF-8.
+    #[inline]
+    pub fn split_whitespace(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.as_bytes()
+            .split(u8::is_ascii_whitespace)
+            .filter(|token| !token.is_empty())
+    }
+
+    /// Splits the `String` into tokens separated by ASCII whitespace, the
+    /// same way a POSIX shell would for simple inputs:
+    ///
+    /// - text between single quotes is taken literally;
+    /// - between double quotes, a backslash escapes a following `"` or `\`,
+    ///   and is kept as is before any other byte;
+    /// - outside of quotes, a backslash escapes the following byte, e.g.
+    ///   `a\ b` is a single token;
+    /// - quoted text joins the surrounding unquoted text, so `a"b c"` is a
+    ///   single token, and `""` is an empty token.
+    ///
+    /// An unterminated quote extends to the end of the string and a trailing
+    /// backslash is kept as is, so this never fails.
+    pub fn shell_split(&self) -> Vec<Self> {
+        let mut tokens = Vec::new();
+        let mut current = None::<Vec<u8>>;
+        let mut bytes = self.as_bytes().iter().copied();
+
+        while let Some(byte) = bytes.next() {
+            match byte {
+                b'\'' => {
+                    let token = current.get_or_insert_with(Vec::new);
+                    token.extend(bytes.by_ref().take_while(|&b| b != b'\''));
+                },
+
+                b'"' => {
+                    let token = current.get_or_insert_with(Vec::new);
+                    while let Some(byte) = bytes.next() {
+                        match byte {
+                            b'"' => break,
+                            b'\\' => match bytes.next() {
+                                Some(next @ (b'"' | b'\\')) => {
+                                    token.push(next)
+                                },
+                                Some(next) => token.extend([byte, next]),
+                                None => token.push(byte),
+                            },
+                            _ => token.push(byte),
+                        }
+                    }
+                },
+
+                b'\\' => {
+                    let token = current.get_or_insert_with(Vec::new);
+                    token.push(bytes.next().unwrap_or(byte));
+                },
+
+                _ if byte.is_ascii_whitespace() => {
+                    tokens.extend(current.take().map(Self::from_bytes));
+                },
+
+                _ => current.get_or_insert_with(Vec::new).push(byte),
+            }
+        }
+
+        tokens.extend(current.map(Self::from_bytes));
+        tokens
+    }
+
+    /// Returns a reader over the bytes of the `String`, which can be passed
+    /// to APIs expecting an [`io::Read`] without copying the string first.
+    #[inline]
+    pub fn reader(&self) -> impl io::Read + '_ {
+        self.as_bytes()
+    }
+
+    /// Returns the number of bytes allocated on the heap by this `String`.
+    #[inline]
+    pub(crate) fn heap_size(&self) -> usize {
+        if self.data.is_null() {
+            0
+        } else {
+            // One extra for null terminator.
+            self.size + 1
+        }
+    }
+
+    /// Appends all the `bufs` to the string with a single reallocation,
+    /// returning the number of bytes appended.
+    fn append(&mut self, bufs: &[&[u8]]) -> usize {
+        let len = bufs.iter().map(|buf| buf.len()).sum::<usize>();
+
+        if len == 0 {
+            return 0;
+        }
+
+        let mut bytes = if self.data.is_null() {
+            Vec::new()
+        } else {
+            let this = ManuallyDrop::new(mem::take(self));
+            // SAFETY: the allocation is `size + 1` bytes long, the last one
+            // being the null terminator which we're about to overwrite.
+            unsafe {
+                Vec::from_raw_parts(
+                    this.data.cast::<u8>(),
+                    this.size,
+                    this.size + 1,
+                )
+            }
+        };
+
+        bytes.reserve_exact(len + 1);
+        for buf in bufs {
+            bytes.extend_from_slice(buf);
+        }
+        *self = Self::from_bytes(bytes);
+
+        len
+    }
+
     /// Makes a non-owning version of this `String`.
     #[inline]
     #[doc(hidden)]
@@ -127,6 +369,119 @@ impl String {
     }
 }
 
+/// A growable buffer used to build a [`String`] out of many pieces without
+/// reallocating it every time, returned by [`String::with_capacity`].
+///
+/// Like a `String`, the bytes pushed so far are always followed by a null
+/// terminator, so they can contain null bytes themselves and the buffer can
+/// be written to by anything implementing [`io::Write`] or [`fmt::Write`].
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// use nvim_types::String;
+///
+/// let mut builder = String::with_capacity(16);
+/// builder.push_bytes(b"foo");
+/// write!(builder, " {}", 42).unwrap();
+///
+/// assert_eq!(String::from("foo 42"), builder.finish());
+/// ```
+#[derive(Clone, Debug)]
+pub struct StringBuilder {
+    /// The bytes pushed so far followed by the null terminator.
+    bytes: Vec<u8>,
+}
+
+impl Default for StringBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StringBuilder {
+    /// Creates a new, empty builder.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a new builder with room for `capacity` bytes plus the null
+    /// terminator.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut bytes = Vec::with_capacity(capacity + 1);
+        Vec::push(&mut bytes, 0);
+        Self { bytes }
+    }
+
+    /// Returns the bytes pushed so far, without the null terminator.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len()]
+    }
+
+    /// Returns the number of bytes pushed so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len() - 1
+    }
+
+    /// Returns `true` if nothing has been pushed yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends `bytes` to the buffer, growing it if needed.
+    #[inline]
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        // Moves the null terminator after the new bytes.
+        self.bytes.pop();
+        self.bytes.extend_from_slice(bytes);
+        Vec::push(&mut self.bytes, 0);
+    }
+
+    /// Appends `str` to the buffer, growing it if needed.
+    #[inline]
+    pub fn push_str(&mut self, str: &str) {
+        self.push_bytes(str.as_bytes());
+    }
+
+    /// Converts the buffer into a [`String`], without reallocating it if
+    /// it's been filled up to its capacity.
+    #[inline]
+    pub fn finish(mut self) -> String {
+        self.bytes.pop();
+        String::from_bytes(self.bytes)
+    }
+}
+
+/// Appends the written bytes to the builder. Flushing does nothing.
+impl io::Write for StringBuilder {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.push_bytes(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl fmt::Write for StringBuilder {
+    #[inline]
+    fn write_str(&mut self, str: &str) -> fmt::Result {
+        self.push_str(str);
+        Ok(())
+    }
+}
+
 impl Default for String {
     #[inline]
     fn default() -> Self {
@@ -146,14 +501,74 @@ impl fmt::Display for String {
     }
 }
 
+/// There's no `AsRef<str>` implementation since a `String` isn't guaranteed
+/// to contain valid UTF-8, and `as_ref` can't fail: it would have to either
+/// panic or allocate a lossy copy, neither of which is expected from a cheap
+/// reference conversion. Use [`String::as_str`] to borrow the contents as a
+/// `&str` if they're valid UTF-8, or [`String::to_string_lossy`] otherwise.
+impl AsRef<[u8]> for String {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
 impl Clone for String {
     fn clone(&self) -> Self {
         Self::from_bytes(self.as_bytes().to_owned())
     }
 }
 
+/// Appends the written bytes to the `String`, keeping it null-terminated
+/// after every write so it's always valid to pass to Neovim.
+///
+/// Neovim's strings don't store a capacity, so they can't grow
+/// geometrically and every write has to reallocate the string. To keep
+/// that to one reallocation per call, [`write_fmt`](io::Write::write_fmt)
+/// (i.e. `write!`) and [`write_vectored`](io::Write::write_vectored) format
+/// or gather all their pieces before appending them. When building a string
+/// out of many separate writes use a [`StringBuilder`] instead, whose
+/// buffer grows in amortised constant time.
+impl io::Write for String {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.append(&[buf]);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_vectored(
+        &mut self,
+        bufs: &[io::IoSlice<'_>],
+    ) -> io::Result<usize> {
+        // `IoSlice` derefs to `[u8]`, but a slice of them can't be turned
+        // into a slice of byte slices without copying the pointers.
+        let bufs = bufs.iter().map(|buf| &**buf).collect::<Vec<_>>();
+        Ok(self.append(&bufs))
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> io::Result<()> {
+        match args.as_str() {
+            Some(str) => self.append(&[str.as_bytes()]),
+            None => self.append(&[fmt::format(args).as_bytes()]),
+        };
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 impl Drop for String {
     fn drop(&mut self) {
+        // Strings created via `String::new` don't own any allocation.
+        if self.data.is_null() {
+            return;
+        }
+
         // One extra for null terminator.
         let _ = unsafe {
             Vec::from_raw_parts(self.data, self.size + 1, self.size + 1)
@@ -161,6 +576,7 @@ impl Drop for String {
     }
 }
 
+/// Reuses the string's buffer, see [`String::from_bytes`].
 impl From<StdString> for String {
     #[inline]
     fn from(string: StdString) -> Self {
@@ -220,6 +636,40 @@ impl From<String> for PathBuf {
     }
 }
 
+#[cfg(not(windows))]
+impl From<String> for OsString {
+    #[inline]
+    fn from(nstr: String) -> Self {
+        use std::os::unix::ffi::OsStrExt;
+        OsStr::from_bytes(nstr.as_bytes()).to_owned()
+    }
+}
+
+#[cfg(windows)]
+impl From<String> for OsString {
+    #[inline]
+    fn from(nstr: String) -> Self {
+        StdString::from_utf8_lossy(nstr.as_bytes()).into_owned().into()
+    }
+}
+
+#[cfg(not(windows))]
+impl From<OsString> for String {
+    #[inline]
+    fn from(ostr: OsString) -> Self {
+        use std::os::unix::ffi::OsStringExt;
+        Self::from_bytes(ostr.into_vec())
+    }
+}
+
+#[cfg(windows)]
+impl From<OsString> for String {
+    #[inline]
+    fn from(ostr: OsString) -> Self {
+        ostr.to_string_lossy().into_owned().into()
+    }
+}
+
 impl PartialEq<Self> for String {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
@@ -274,6 +724,21 @@ mod serde {
     use std::fmt;
 
     use serde::de::{self, Deserialize, Deserializer, Visitor};
+    use serde::ser::{Serialize, Serializer};
+
+    /// Strings containing valid UTF-8 are serialized as strings, all the
+    /// others as byte arrays.
+    impl Serialize for super::String {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match self.as_str() {
+                Ok(str) => serializer.serialize_str(str),
+                Err(_) => serializer.serialize_bytes(self.as_bytes()),
+            }
+        }
+    }
 
     impl<'de> Deserialize<'de> for super::String {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -346,10 +811,277 @@ mod tests {
         assert_eq!(lhs, rhs);
     }
 
+    #[test]
+    fn from_string_reuses_buffer() {
+        let mut foo = StdString::with_capacity(4);
+        foo.push_str("foo");
+        let ptr = foo.as_ptr();
+
+        let s = String::from(foo);
+        assert_eq!(ptr, s.as_ptr() as *const u8);
+        assert_eq!(b"foo\0", unsafe {
+            slice::from_raw_parts(s.as_ptr() as *const u8, 4)
+        });
+    }
+
+    #[test]
+    fn from_bytes_spare_capacity() {
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(b"foo");
+
+        let s = String::from_bytes(bytes);
+        assert_eq!(b"foo", s.as_bytes());
+    }
+
     #[test]
     fn to_bytes() {
         let s = String::from("hello");
         let bytes = s.into_bytes();
         assert_eq!(&[104, 101, 108, 108, 111][..], &bytes[..]);
     }
+
+    #[test]
+    fn into_bytes_then_grow() {
+        let mut bytes = String::from("hello").into_bytes();
+        assert_eq!(6, bytes.capacity());
+
+        bytes.extend_from_slice(b" world");
+        assert_eq!(b"hello world", &bytes[..]);
+        assert_eq!(String::from("hello world"), String::from_bytes(bytes));
+    }
+
+    #[test]
+    fn from_utf8_unchecked() {
+        let mut foo = StdString::with_capacity(4);
+        foo.push_str("foo");
+        let ptr = foo.as_ptr();
+
+        let s = String::from_utf8_unchecked(foo);
+        assert_eq!(ptr, s.as_ptr() as *const u8);
+        assert_eq!(s, "foo");
+
+        assert!(String::from_utf8_unchecked(StdString::new()).is_empty());
+    }
+
+    #[test]
+    fn with_capacity() {
+        let mut builder = String::with_capacity(8);
+        assert!(builder.is_empty());
+
+        builder.push_bytes(b"foo");
+        builder.push_str(" b\u{e4}r");
+        assert_eq!(8, builder.len());
+
+        let ptr = builder.bytes.as_ptr();
+        let s = builder.finish();
+        assert_eq!(ptr, s.as_ptr() as *const u8);
+        assert_eq!(s, "foo b\u{e4}r");
+    }
+
+    #[test]
+    fn builder_write() {
+        use std::io::Write as _;
+
+        let mut builder = String::with_capacity(0);
+        write!(builder, "{} ", 42).unwrap();
+        builder.write_all(b"b\xc3\xa4r").unwrap();
+        fmt::Write::write_fmt(&mut builder, format_args!("!")).unwrap();
+
+        let s = builder.finish();
+        assert_eq!(s, "42 b\u{e4}r!");
+        assert_eq!(Ok(c"42 b\xc3\xa4r!"), s.as_c_str());
+    }
+
+    #[test]
+    fn repeat() {
+        let s = String::from("€a");
+        let repeated = s.repeat(3);
+        assert_eq!(12, repeated.len());
+        assert_eq!(repeated, "€a€a€a");
+
+        let empty = s.repeat(0);
+        assert!(empty.is_empty());
+        assert_eq!(&[] as &[u8], empty.as_bytes());
+    }
+
+    #[test]
+    fn concat() {
+        let foo = String::from("foo");
+        let sep = String::from(" | ");
+        let bar = String::from("bär");
+
+        let s = String::concat(&[&foo, &sep, &bar]);
+        assert_eq!(s, "foo | bär");
+
+        assert!(String::concat(&[]).is_empty());
+    }
+
+    #[test]
+    fn find() {
+        let s = String::from("foo bar foo");
+
+        assert_eq!(Some(0), s.find(b"foo"));
+        assert_eq!(Some(8), s.rfind(b"foo"));
+        assert_eq!(Some(4), s.find(b"bar"));
+        assert_eq!(None, s.find(b"baz"));
+        assert_eq!(None, s.rfind(b"foo bar foo bar"));
+
+        assert_eq!("foo bar foo".find(""), s.find(b""));
+        assert_eq!("foo bar foo".rfind(""), s.rfind(b""));
+
+        let s = String::from("aaa");
+        assert_eq!(Some(0), s.find(b"aa"));
+        assert_eq!(Some(1), s.rfind(b"aa"));
+    }
+
+    #[test]
+    fn char_indices() {
+        let s = String::from_bytes(b"a\xffb\xe2\x82\xacc".to_vec());
+        let indices = s.char_indices().collect::<Vec<_>>();
+        assert_eq!(vec![(0, 'a'), (2, 'b'), (3, '€'), (6, 'c')], indices);
+    }
+
+    #[test]
+    fn as_c_str() {
+        let s = String::from("foo");
+        assert_eq!(Ok(c"foo"), s.as_c_str());
+
+        let s = String::from_bytes(b"foo\0bar".to_vec());
+        assert!(s.as_c_str().is_err());
+
+        let s = String::new();
+        assert_eq!(Ok(c""), s.as_c_str());
+    }
+
+    #[test]
+    fn reader() {
+        use std::io::Read;
+
+        let s = String::from("foo bär\nbaz");
+        let mut bytes = Vec::new();
+        s.reader().read_to_end(&mut bytes).unwrap();
+        assert_eq!(s.as_bytes(), &bytes[..]);
+
+        let mut buf = [0; 4];
+        let mut reader = s.reader();
+        assert_eq!(4, reader.read(&mut buf).unwrap());
+        assert_eq!(b"foo ", &buf);
+
+        let mut bytes = Vec::new();
+        String::new().reader().read_to_end(&mut bytes).unwrap();
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn write() {
+        use std::io::Write;
+
+        let mut s = String::new();
+        write!(s, "foo {}", 42).unwrap();
+        writeln!(s, " bär").unwrap();
+        assert_eq!(s, "foo 42 bär\n");
+        assert_eq!(Ok(c"foo 42 b\xc3\xa4r\n"), s.as_c_str());
+
+        assert_eq!(0, s.write(b"").unwrap());
+        assert_eq!(s, "foo 42 bär\n");
+
+        let mut empty = String::new();
+        assert_eq!(0, empty.write(b"").unwrap());
+        assert!(empty.data.is_null());
+    }
+
+    #[test]
+    fn builder_write_binary() {
+        use std::io::Write as _;
+
+        let mut builder = StringBuilder::new();
+        assert_eq!(b"\0", &builder.bytes[..]);
+
+        builder.write_all(b"foo\0").unwrap();
+        builder.write_all(&[0xff, 0, 0xfe]).unwrap();
+        assert_eq!(b"foo\0\xff\0\xfe", builder.as_bytes());
+        assert_eq!(Some(&0), builder.bytes.last());
+
+        builder.write_all(b"bar").unwrap();
+        builder.flush().unwrap();
+        assert_eq!(b"foo\0\xff\0\xfebar", builder.as_bytes());
+        assert_eq!(10, builder.len());
+        assert_eq!(Some(&0), builder.bytes.last());
+
+        let s = builder.finish();
+        assert_eq!(b"foo\0\xff\0\xfebar", s.as_bytes());
+    }
+
+    #[test]
+    fn write_vectored() {
+        use std::io::{IoSlice, Write};
+
+        let mut s = String::from("foo");
+        let bufs =
+            [IoSlice::new(b" bar"), IoSlice::new(b""), IoSlice::new(b"!")];
+        assert_eq!(5, s.write_vectored(&bufs).unwrap());
+        assert_eq!(s, "foo bar!");
+        assert_eq!(Ok(c"foo bar!"), s.as_c_str());
+
+        assert_eq!(0, s.write_vectored(&[]).unwrap());
+        assert_eq!(s, "foo bar!");
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn to_from_os_string() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let s = String::from_bytes(b"foo\xffbar".to_vec());
+        let os = OsString::from(s.clone());
+        assert_eq!(b"foo\xffbar", os.as_bytes());
+        assert_eq!(s, String::from(os));
+
+        assert_eq!(OsString::new(), OsString::from(String::new()));
+    }
+
+    #[test]
+    fn as_ref_bytes() {
+        fn len<B: AsRef<[u8]>>(bytes: B) -> usize {
+            bytes.as_ref().len()
+        }
+
+        let s = String::from("bär");
+        assert_eq!(4, len(&s));
+        assert_eq!(b"b\xc3\xa4r", s.as_ref());
+        assert_eq!(0, len(String::new()));
+    }
+
+    #[test]
+    fn as_str() {
+        let s = String::from("bär");
+        assert_eq!(Ok("bär"), s.as_str());
+        assert_eq!(Cow::Borrowed("bär"), s.to_string_lossy());
+
+        let s = String::from_bytes(b"b\xffr".to_vec());
+        assert!(s.as_str().is_err());
+        assert_eq!("b\u{fffd}r", s.to_string_lossy());
+    }
+
+    #[test]
+    fn split_whitespace() {
+        let s = String::from_bytes(b" foo\tb\xffr \n baz  ".to_vec());
+        let tokens = s.split_whitespace().collect::<Vec<_>>();
+        assert_eq!(vec![&b"foo"[..], b"b\xffr", b"baz"], tokens);
+
+        assert_eq!(0, String::from("  ").split_whitespace().count());
+    }
+
+    #[test]
+    fn shell_split() {
+        let split = |s: &str| String::from(s).shell_split();
+
+        assert_eq!(split(r#"a "b c" d"#), vec!["a", "b c", "d"]);
+        assert_eq!(split(r"a\ b c"), vec!["a b", "c"]);
+        assert_eq!(split(r#"'a "b" \c'"#), vec![r#"a "b" \c"#]);
+        assert_eq!(split(r#""\"b\c""#), vec![r#""b\c"#]);
+        assert_eq!(split(r#"a"b c" """#), vec!["ab c", ""]);
+        assert_eq!(split(r#"  a  "b c"#), vec!["a", "b c"]);
+        assert!(split(" \t ").is_empty());
+    }
 }
@@ -0,0 +1,248 @@
+use std::slice;
+
+use crate::{
+    Boolean,
+    Float,
+    Integer,
+    KeyValuePair,
+    LuaRef,
+    Object,
+    ObjectKind,
+    String,
+};
+
+/// A visitor used to traverse an [`Object`] via [`Object::walk`].
+///
+/// Every method has a default implementation which does nothing, so
+/// implementors only need to override the callbacks they care about.
+#[allow(unused_variables)]
+pub trait ObjectVisitor {
+    fn visit_nil(&mut self) {}
+
+    fn visit_boolean(&mut self, boolean: Boolean) {}
+
+    fn visit_integer(&mut self, integer: Integer) {}
+
+    fn visit_float(&mut self, float: Float) {}
+
+    fn visit_string(&mut self, string: &String) {}
+
+    /// Called before visiting the `len` elements of an array.
+    fn visit_array_begin(&mut self, len: usize) {}
+
+    /// Called after all the elements of an array have been visited.
+    fn visit_array_end(&mut self) {}
+
+    /// Called before visiting the `len` key-value pairs of a dictionary.
+    fn visit_dict_begin(&mut self, len: usize) {}
+
+    /// Called with the key of each key-value pair of a dictionary, right
+    /// before visiting its value.
+    fn visit_dict_key(&mut self, key: &String) {}
+
+    /// Called after all the key-value pairs of a dictionary have been
+    /// visited.
+    fn visit_dict_end(&mut self) {}
+
+    fn visit_luaref(&mut self, luaref: LuaRef) {}
+
+    /// Called for buffer, window and tabpage handles. `kind` is respectively
+    /// [`ObjectKind::Buffer`], [`ObjectKind::Window`] or
+    /// [`ObjectKind::TabPage`].
+    fn visit_handle(&mut self, kind: ObjectKind, handle: Integer) {}
+}
+
+/// A partially visited array or dictionary.
+enum Frame<'a> {
+    Array(slice::Iter<'a, Object>),
+    Dictionary(slice::Iter<'a, KeyValuePair>),
+}
+
+impl Object {
+    /// Walks through the object depth-first, calling the corresponding
+    /// method of `visitor` for every value it contains.
+    ///
+    /// The traversal is iterative, so arbitrarily nested objects can be
+    /// walked without overflowing the stack.
+    pub fn walk<V: ObjectVisitor>(&self, visitor: &mut V) {
+        let mut stack = Vec::new();
+
+        enter(self, visitor, &mut stack);
+
+        while let Some(frame) = stack.last_mut() {
+            let next = match frame {
+                Frame::Array(iter) => match iter.next() {
+                    Some(obj) => obj,
+
+                    None => {
+                        stack.pop();
+                        visitor.visit_array_end();
+                        continue;
+                    },
+                },
+
+                Frame::Dictionary(iter) => match iter.next() {
+                    Some(pair) => {
+                        visitor.visit_dict_key(&pair.key);
+                        &pair.value
+                    },
+
+                    None => {
+                        stack.pop();
+                        visitor.visit_dict_end();
+                        continue;
+                    },
+                },
+            };
+
+            enter(next, visitor, &mut stack);
+        }
+    }
+}
+
+/// Visits a scalar object, or begins visiting an array or a dictionary by
+/// pushing a new frame on the stack.
+fn enter<'a, V: ObjectVisitor>(
+    obj: &'a Object,
+    visitor: &mut V,
+    stack: &mut Vec<Frame<'a>>,
+) {
+    unsafe {
+        match obj.kind() {
+            ObjectKind::Nil => visitor.visit_nil(),
+            ObjectKind::Boolean => {
+                visitor.visit_boolean(obj.as_boolean_unchecked())
+            },
+            ObjectKind::Integer => {
+                visitor.visit_integer(obj.as_integer_unchecked())
+            },
+            ObjectKind::Float => visitor.visit_float(obj.as_float_unchecked()),
+            ObjectKind::String => {
+                visitor.visit_string(obj.as_string_unchecked())
+            },
+            ObjectKind::Array => {
+                let array = obj.as_array_unchecked();
+                visitor.visit_array_begin(array.len());
+                stack.push(Frame::Array(array.iter()));
+            },
+            ObjectKind::Dictionary => {
+                let dict = obj.as_dict_unchecked();
+                visitor.visit_dict_begin(dict.len());
+                stack.push(Frame::Dictionary(dict.iter()));
+            },
+            ObjectKind::LuaRef => {
+                visitor.visit_luaref(obj.as_luaref_unchecked())
+            },
+            kind @ (ObjectKind::Buffer
+            | ObjectKind::Window
+            | ObjectKind::TabPage) => {
+                visitor.visit_handle(kind, obj.as_integer_unchecked())
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Array, Dictionary};
+
+    #[derive(Default)]
+    struct Counter {
+        booleans: usize,
+        integers: usize,
+        floats: usize,
+        strings: usize,
+        arrays: usize,
+        dicts: usize,
+        keys: usize,
+        depth: usize,
+        max_depth: usize,
+    }
+
+    impl ObjectVisitor for Counter {
+        fn visit_boolean(&mut self, _: Boolean) {
+            self.booleans += 1;
+        }
+
+        fn visit_integer(&mut self, _: Integer) {
+            self.integers += 1;
+        }
+
+        fn visit_float(&mut self, _: Float) {
+            self.floats += 1;
+        }
+
+        fn visit_string(&mut self, _: &String) {
+            self.strings += 1;
+        }
+
+        fn visit_array_begin(&mut self, _: usize) {
+            self.arrays += 1;
+            self.depth += 1;
+            self.max_depth = self.max_depth.max(self.depth);
+        }
+
+        fn visit_array_end(&mut self) {
+            self.depth -= 1;
+        }
+
+        fn visit_dict_begin(&mut self, _: usize) {
+            self.dicts += 1;
+            self.depth += 1;
+            self.max_depth = self.max_depth.max(self.depth);
+        }
+
+        fn visit_dict_key(&mut self, _: &String) {
+            self.keys += 1;
+        }
+
+        fn visit_dict_end(&mut self) {
+            self.depth -= 1;
+        }
+    }
+
+    #[test]
+    fn count_kinds() {
+        let obj = Object::from(Dictionary::from_iter([
+            ("foo", Object::from(Array::from((1, "bar", true)))),
+            (
+                "baz",
+                Object::from(Dictionary::from_iter([
+                    ("a", Object::from(4.2)),
+                    ("b", Object::from(Array::new())),
+                ])),
+            ),
+            ("qux", Object::from(42)),
+        ]));
+
+        let mut counter = Counter::default();
+        obj.walk(&mut counter);
+
+        assert_eq!(1, counter.booleans);
+        assert_eq!(2, counter.integers);
+        assert_eq!(1, counter.floats);
+        assert_eq!(1, counter.strings);
+        assert_eq!(2, counter.arrays);
+        assert_eq!(2, counter.dicts);
+        assert_eq!(5, counter.keys);
+        assert_eq!(0, counter.depth);
+        assert_eq!(3, counter.max_depth);
+    }
+
+    #[test]
+    fn deep_nesting() {
+        let mut obj = Object::from(42);
+        for _ in 0..10_000 {
+            obj = Array::from_iter([obj]).into();
+        }
+
+        let mut counter = Counter::default();
+        obj.walk(&mut counter);
+
+        assert_eq!(1, counter.integers);
+        assert_eq!(10_000, counter.arrays);
+        assert_eq!(10_000, counter.max_depth);
+        assert_eq!(0, counter.depth);
+    }
+}
@@ -0,0 +1,155 @@
+use proc_macro2::TokenStream;
+use syn::parse::ParseStream;
+use syn::{Attribute, Error, Fields, Generics, Ident, LitStr, Token};
+
+/// The options given via `#[oxi(..)]` attributes to a field or a variant.
+#[derive(Default)]
+struct Attributes {
+    /// The key or name used instead of the one of the field or variant.
+    rename: Option<String>,
+
+    /// Whether the field should be skipped.
+    skip: bool,
+
+    /// Whether the field should be kept in the dictionary when it's nil.
+    keep_nil: bool,
+}
+
+impl Attributes {
+    fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut this = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("oxi")) {
+            attr.parse_args_with(|input: ParseStream| {
+                while !input.is_empty() {
+                    let name = input.parse::<Ident>()?;
+
+                    match name.to_string().as_str() {
+                        "rename" => {
+                            input.parse::<Token![=]>()?;
+                            this.rename =
+                                Some(input.parse::<LitStr>()?.value());
+                        },
+
+                        "skip" => this.skip = true,
+
+                        "keep_nil" => this.keep_nil = true,
+
+                        other => {
+                            return Err(Error::new(
+                                name.span(),
+                                format!("unknown attribute `{other}`"),
+                            ))
+                        },
+                    }
+
+                    if !input.is_empty() {
+                        input.parse::<Token![,]>()?;
+                    }
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(this)
+    }
+}
+
+/// A named field of a struct or of an enum variant.
+pub(crate) struct Field {
+    pub(crate) ident: Ident,
+
+    /// The key of the field in the dictionary.
+    pub(crate) key: String,
+
+    /// Whether the field is skipped, in which case it's not converted to an
+    /// object and it's set to its default value when converting from one.
+    pub(crate) skip: bool,
+
+    /// Whether the field is stored in the dictionary even if it's converted
+    /// to nil, instead of being left out.
+    pub(crate) keep_nil: bool,
+
+    /// Whether the type of the field is an `Option`.
+    pub(crate) is_option: bool,
+}
+
+/// Collects the fields of a struct or of an enum variant, failing if they're
+/// not named.
+pub(crate) fn named_fields(fields: &Fields) -> syn::Result<Vec<Field>> {
+    let named = match fields {
+        Fields::Named(named) => named,
+
+        Fields::Unnamed(_) | Fields::Unit => {
+            return Err(Error::new_spanned(
+                fields,
+                "only fields with names are supported",
+            ))
+        },
+    };
+
+    named
+        .named
+        .iter()
+        .map(|field| {
+            let attrs = Attributes::from_attrs(&field.attrs)?;
+            let ident = field.ident.clone().expect("fields are named");
+
+            Ok(Field {
+                key: attrs.rename.unwrap_or_else(|| ident_name(&ident)),
+                ident,
+                skip: attrs.skip,
+                keep_nil: attrs.keep_nil,
+                is_option: is_option(&field.ty),
+            })
+        })
+        .collect()
+}
+
+/// Returns the name of a variant, i.e. the string it's converted to.
+pub(crate) fn variant_name(variant: &syn::Variant) -> syn::Result<String> {
+    let attrs = Attributes::from_attrs(&variant.attrs)?;
+
+    if attrs.skip {
+        return Err(Error::new_spanned(variant, "variants can't be skipped"));
+    }
+
+    if attrs.keep_nil {
+        return Err(Error::new_spanned(
+            variant,
+            "`keep_nil` can only be used on fields",
+        ));
+    }
+
+    Ok(attrs.rename.unwrap_or_else(|| ident_name(&variant.ident)))
+}
+
+/// Adds a `bound` to every type parameter in `generics`.
+pub(crate) fn add_bounds(
+    mut generics: Generics,
+    bound: TokenStream,
+) -> Generics {
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(#bound));
+    }
+    generics
+}
+
+/// Like `Ident::to_string`, but strips the `r#` prefix of raw identifiers.
+fn ident_name(ident: &Ident) -> String {
+    let name = ident.to_string();
+    name.strip_prefix("r#").map(ToOwned::to_owned).unwrap_or(name)
+}
+
+/// Returns whether `ty` is an `Option`, judging by its last path segment.
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => matches!(
+            path.path.segments.last(),
+            Some(segment) if segment.ident == "Option"
+        ),
+
+        _ => false,
+    }
+}
@@ -0,0 +1,167 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields};
+
+use crate::attrs::{self, Field};
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+
+    let generics = attrs::add_bounds(
+        input.generics.clone(),
+        quote! { ::nvim_oxi::conversion::FromObject },
+    );
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = attrs::named_fields(&data.fields)?;
+            let init = init(quote! { Self }, &fields);
+
+            quote! {
+                #[allow(unused_mut)]
+                let mut dict = <::nvim_oxi::Dictionary as
+                    ::nvim_oxi::conversion::FromObject>::from_object(object)?;
+
+                ::std::result::Result::Ok(#init)
+            }
+        },
+
+        Data::Enum(data) => {
+            let mut unit_arms = Vec::new();
+            let mut dict_arms = Vec::new();
+
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                let name = attrs::variant_name(variant)?;
+
+                if let Fields::Unit = variant.fields {
+                    let arm = quote! {
+                        #name => ::std::result::Result::Ok(Self::#ident)
+                    };
+                    unit_arms.push(arm.clone());
+                    dict_arms.push(arm);
+                    continue;
+                }
+
+                let fields = attrs::named_fields(&variant.fields)?;
+                let init = init(quote! { Self::#ident }, &fields);
+
+                dict_arms.push(quote! {
+                    #name => ::std::result::Result::Ok(#init)
+                });
+            }
+
+            quote! {
+                match object.kind() {
+                    ::nvim_oxi::ObjectKind::String => {
+                        let name = <::std::string::String as
+                            ::nvim_oxi::conversion::FromObject>::from_object(
+                            object,
+                        )?;
+
+                        match name.as_str() {
+                            #(#unit_arms,)*
+                            other => ::std::result::Result::Err(
+                                ::nvim_oxi::conversion::Error::UnknownVariant(
+                                    other.to_owned(),
+                                ),
+                            ),
+                        }
+                    },
+
+                    ::nvim_oxi::ObjectKind::Dictionary => {
+                        let mut dict = <::nvim_oxi::Dictionary as
+                            ::nvim_oxi::conversion::FromObject>::from_object(
+                            object,
+                        )?;
+
+                        let name = <::std::string::String as
+                            ::nvim_oxi::conversion::FromObject>::from_object(
+                            __take(&mut dict, "type"),
+                        )?;
+
+                        match name.as_str() {
+                            #(#dict_arms,)*
+                            other => ::std::result::Result::Err(
+                                ::nvim_oxi::conversion::Error::UnknownVariant(
+                                    other.to_owned(),
+                                ),
+                            ),
+                        }
+                    },
+
+                    other => ::std::result::Result::Err(
+                        ::nvim_oxi::conversion::Error::FromWrongType {
+                            expected: "string or dictionary",
+                            actual: other.as_static(),
+                        },
+                    ),
+                }
+            }
+        },
+
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "unions are not supported",
+            ))
+        },
+    };
+
+    let take_fn = take_fn();
+
+    Ok(quote! {
+        impl #impl_generics ::nvim_oxi::conversion::FromObject
+            for #name #ty_generics #where_clause
+        {
+            fn from_object(
+                object: ::nvim_oxi::Object,
+            ) -> ::std::result::Result<Self, ::nvim_oxi::conversion::Error> {
+                #take_fn
+
+                #body
+            }
+        }
+    })
+}
+
+/// Returns an expression building `path` from the fields taken out of
+/// `dict`. Missing keys are converted from nil, while skipped fields are set
+/// to their default value.
+fn init(path: TokenStream, fields: &[Field]) -> TokenStream {
+    let fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let key = &field.key;
+
+        if field.skip {
+            quote! { #ident: ::std::default::Default::default() }
+        } else {
+            quote! {
+                #ident: ::nvim_oxi::conversion::FromObject::from_object(
+                    __take(&mut dict, #key),
+                )?
+            }
+        }
+    });
+
+    quote! { #path { #(#fields),* } }
+}
+
+/// Returns the code of a function taking the value of `key` out of a
+/// dictionary, or returning nil if the key isn't there.
+pub(crate) fn take_fn() -> TokenStream {
+    quote! {
+        #[allow(dead_code)]
+        fn __take(
+            dict: &mut ::nvim_oxi::Dictionary,
+            key: &str,
+        ) -> ::nvim_oxi::Object {
+            match dict.entry(key) {
+                ::nvim_oxi::Entry::Occupied(entry) => entry.remove(),
+                ::nvim_oxi::Entry::Vacant(_) => ::nvim_oxi::Object::nil(),
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod attrs;
+mod from_object;
+mod to_object;
+
+/// Derives `FromObject` for structs with named fields and for enums whose
+/// variants are either unit variants or have named fields.
+///
+/// Structs are converted from a `Dictionary` whose keys are the names of the
+/// fields. A missing key is converted as if it was nil, so `Option` fields
+/// can be left out.
+///
+/// Unit variants are converted from a string containing the name of the
+/// variant, while variants with fields are converted from a `Dictionary`
+/// whose `"type"` key holds the name of the variant, and whose other keys
+/// hold the fields.
+///
+/// # Attributes
+///
+/// - `#[oxi(rename = "name")]` on a field or a variant uses `name` instead
+///   of the Rust name;
+/// - `#[oxi(skip)]` on a field ignores it, setting it to its
+///   [`Default`] value.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::{self as oxi, conversion::FromObject, FromObject};
+///
+/// #[derive(FromObject)]
+/// struct Config {
+///     #[oxi(rename = "max-width")]
+///     max_width: u32,
+///     border: Option<String>,
+///     #[oxi(skip)]
+///     cache: Vec<String>,
+/// }
+///
+/// let dict = oxi::Dictionary::from_iter([("max-width", 79)]);
+/// let config = Config::from_object(dict.into()).unwrap();
+/// assert_eq!(79, config.max_width);
+/// assert_eq!(None, config.border);
+/// ```
+#[proc_macro_derive(FromObject, attributes(oxi))]
+pub fn derive_from_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    from_object::expand(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives `ToObject` for structs with named fields and for enums whose
+/// variants are either unit variants or have named fields.
+///
+/// The generated objects have the shape described in the docs of
+/// [`FromObject`](derive@FromObject), and the same attributes are
+/// supported. Fields which are converted to nil, like `None`s, are left out
+/// of the `Dictionary`, unless they're marked with `#[oxi(keep_nil)]`, in
+/// which case they're stored as explicit nils. This is useful with APIs
+/// which treat a missing key differently from a nil one.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::{self as oxi, conversion::ToObject, ToObject};
+///
+/// #[derive(ToObject)]
+/// enum Event {
+///     Quit,
+///     Resize { width: u32, height: u32 },
+/// }
+///
+/// assert_eq!(
+///     oxi::Object::from("Quit"),
+///     Event::Quit.to_object().unwrap(),
+/// );
+///
+/// assert_eq!(
+///     oxi::Object::from(oxi::Dictionary::from_iter([
+///         ("type", oxi::Object::from("Resize")),
+///         ("width", oxi::Object::from(80)),
+///         ("height", oxi::Object::from(24)),
+///     ])),
+///     Event::Resize { width: 80, height: 24 }.to_object().unwrap(),
+/// );
+/// ```
+#[proc_macro_derive(ToObject, attributes(oxi))]
+pub fn derive_to_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    to_object::expand(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
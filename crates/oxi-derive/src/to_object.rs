@@ -0,0 +1,137 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields};
+
+use crate::attrs::{self, Field};
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+
+    let generics = attrs::add_bounds(
+        input.generics.clone(),
+        quote! { ::nvim_oxi::conversion::ToObject },
+    );
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = attrs::named_fields(&data.fields)?;
+            let dict = dict(None, &fields, |ident| quote! { self.#ident });
+            quote! { ::std::result::Result::Ok(#dict.into()) }
+        },
+
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let name = attrs::variant_name(variant)?;
+
+                    if let Fields::Unit = variant.fields {
+                        return Ok(quote! {
+                            Self::#ident => ::std::result::Result::Ok(
+                                ::nvim_oxi::Object::from(#name)
+                            )
+                        });
+                    }
+
+                    let fields = attrs::named_fields(&variant.fields)?;
+
+                    let bindings = fields
+                        .iter()
+                        .filter(|field| !field.skip)
+                        .map(|field| &field.ident);
+
+                    let dict =
+                        dict(Some(&name), &fields, |ident| quote! { #ident });
+
+                    Ok(quote! {
+                        Self::#ident { #(#bindings,)* .. } => {
+                            ::std::result::Result::Ok(#dict.into())
+                        }
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        },
+
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "unions are not supported",
+            ))
+        },
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::nvim_oxi::conversion::ToObject
+            for #name #ty_generics #where_clause
+        {
+            fn to_object(
+                self,
+            ) -> ::std::result::Result<
+                ::nvim_oxi::Object,
+                ::nvim_oxi::conversion::Error,
+            > {
+                #body
+            }
+        }
+    })
+}
+
+/// Returns an expression building the `Dictionary` of the fields which
+/// aren't skipped, leaving out the ones which are `None` unless they're
+/// marked with `keep_nil`. If `variant` is set its name is stored under the
+/// `"type"` key.
+fn dict<F>(variant: Option<&str>, fields: &[Field], value: F) -> TokenStream
+where
+    F: Fn(&syn::Ident) -> TokenStream,
+{
+    let tag = variant.map(|name| {
+        quote! { ("type", ::nvim_oxi::Object::from(#name), true), }
+    });
+
+    let pairs = fields.iter().filter(|field| !field.skip).map(|field| {
+        let key = &field.key;
+        let value = value(&field.ident);
+
+        let object = if field.is_option {
+            quote! {
+                match #value {
+                    ::std::option::Option::Some(value) => {
+                        ::nvim_oxi::conversion::ToObject::to_object(value)?
+                    },
+                    ::std::option::Option::None => ::nvim_oxi::Object::nil(),
+                }
+            }
+        } else {
+            quote! { ::nvim_oxi::conversion::ToObject::to_object(#value)? }
+        };
+
+        let keep_nil = field.keep_nil;
+
+        quote! { (#key, #object, #keep_nil) }
+    });
+
+    // The pairs are pushed one by one since collecting them into a
+    // `Dictionary` would also drop the nils we want to keep.
+    quote! {
+        ::std::iter::IntoIterator::into_iter([#tag #(#pairs),*]).fold(
+            ::nvim_oxi::Dictionary::new(),
+            |mut dict: ::nvim_oxi::Dictionary,
+             (key, object, keep_nil): (&str, ::nvim_oxi::Object, bool)| {
+                if keep_nil || !object.is_nil() {
+                    dict.push(::nvim_oxi::KeyValuePair::from((key, object)));
+                }
+                dict
+            },
+        )
+    }
+}
@@ -1,7 +1,8 @@
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
-use syn::{parse_macro_input, Error};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Error, Token};
 
 /// Tests a piece of code inside a Neovim session.
 ///
@@ -17,20 +18,60 @@ use syn::{parse_macro_input, Error};
 ///     assert_eq!(Ok(()), api::del_var("foo"));
 /// }
 /// ```
+///
+/// # Fixtures
+///
+/// Passing `buffer = [..]` creates a scratch buffer containing the given
+/// lines and makes it the current buffer before running the test. The
+/// buffer is deleted once the test is over, and can be accessed by giving the
+/// test function a single argument of type
+/// [`Buffer`](https://docs.rs/nvim-oxi/latest/nvim_oxi/api/struct.Buffer.html).
+///
+/// ```ignore
+/// use nvim_oxi::{self as nvim, api::Buffer};
+///
+/// #[nvim::test(buffer = ["foo", "bar"])]
+/// fn line_count(buf: Buffer) {
+///     assert_eq!(Ok(2), buf.line_count());
+/// }
+/// ```
+///
+/// # Timeouts
+///
+/// Passing `timeout_ms = N` fails the test with `timed out after N ms` if the
+/// Neovim instance running it hasn't exited after `N` milliseconds, which
+/// catches tests blocking forever. The timeout of the tests which don't set
+/// one can be set via the `OXI_TEST_TIMEOUT_MS` environment variable, and
+/// defaults to no timeout.
+///
+/// ```ignore
+/// use nvim_oxi::{self as nvim, api};
+///
+/// #[nvim::test(timeout_ms = 5000)]
+/// fn wait_for_job() {
+///     // ..
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn oxi_test(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let args = parse_macro_input!(attr as syn::AttributeArgs);
+    let attrs = parse_macro_input!(attr as Attributes);
 
-    if !args.is_empty() {
-        return Error::new(Span::call_site(), "no attributes are supported")
-            .to_compile_error()
-            .into();
-    }
+    let timeout_ms = match &attrs.timeout_ms {
+        Some(ms) => quote! { Some(#ms) },
+        None => quote! { None },
+    };
 
     let item = parse_macro_input!(item as syn::ItemFn);
 
     let syn::ItemFn { sig, block, .. } = item;
 
+    let fixture = match Fixture::new(attrs, &sig) {
+        Ok(fixture) => fixture,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let Fixture { setup, binding, teardown } = fixture;
+
     // TODO: here we'd need to append something like the module path of the
     // call site to `test_name` to avoid collisions between equally named tests
     // across different modules. Unfortunately that doesn't seem to be possible
@@ -109,7 +150,13 @@ pub fn oxi_test(attr: TokenStream, item: TokenStream) -> TokenStream {
                 }
             }
 
-            let out = ::std::process::Command::new("nvim")
+            let timeout_ms: Option<u64> = #timeout_ms.or_else(|| {
+                ::std::env::var("OXI_TEST_TIMEOUT_MS")
+                    .ok()
+                    .and_then(|ms| ms.parse().ok())
+            });
+
+            let mut child = ::std::process::Command::new("nvim")
                 .args(["-u", "NONE", "--headless"])
                 .args(["-c", "set noswapfile"])
                 .args([
@@ -124,10 +171,39 @@ pub fn oxi_test(attr: TokenStream, item: TokenStream) -> TokenStream {
                     &format!("lua require('__{}')", stringify!(#test_name)),
                 ])
                 .args(["+quit"])
-                .output()
+                .stdout(::std::process::Stdio::null())
+                .stderr(::std::process::Stdio::piped())
+                .spawn()
                 .expect("Couldn't find `nvim` binary in $PATH!");
 
-            let stderr = String::from_utf8_lossy(&out.stderr);
+            // Read stderr on another thread so that Neovim can't block on a
+            // full pipe while we're waiting for it to exit.
+            let mut stderr_pipe = child.stderr.take().unwrap();
+            let stderr_reader = ::std::thread::spawn(move || {
+                let mut stderr = Vec::new();
+                let _ = ::std::io::Read::read_to_end(
+                    &mut stderr_pipe,
+                    &mut stderr,
+                );
+                stderr
+            });
+
+            let start = ::std::time::Instant::now();
+
+            while child.try_wait().unwrap().is_none() {
+                if let Some(ms) = timeout_ms {
+                    if start.elapsed().as_millis() >= ms as u128 {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        panic!("timed out after {} ms", ms);
+                    }
+                }
+
+                ::std::thread::sleep(::std::time::Duration::from_millis(10));
+            }
+
+            let stderr = stderr_reader.join().unwrap();
+            let stderr = String::from_utf8_lossy(&stderr);
 
             if !stderr.is_empty() {
                 // Remove the last 2 lines from stderr for a cleaner error msg.
@@ -146,10 +222,15 @@ pub fn oxi_test(attr: TokenStream, item: TokenStream) -> TokenStream {
 
         #[::nvim_oxi::module]
         fn #module_name() -> ::nvim_oxi::Result<()> {
+            #setup
+
             let result = ::std::panic::catch_unwind(|| {
+                #binding
                 #test_body
             });
 
+            #teardown
+
             ::std::process::exit(match result {
                 Ok(_) => 0,
 
@@ -162,3 +243,122 @@ pub fn oxi_test(attr: TokenStream, item: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// The arguments given to the `#[oxi_test]` attribute.
+#[derive(Default)]
+struct Attributes {
+    /// The lines of the buffer fixture, if any.
+    buffer: Option<syn::ExprArray>,
+
+    /// The number of milliseconds after which the test fails.
+    timeout_ms: Option<u64>,
+}
+
+impl Parse for Attributes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+
+        while !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+
+            match name.to_string().as_str() {
+                "buffer" => {
+                    input.parse::<Token![=]>()?;
+                    attrs.buffer = Some(input.parse()?);
+                },
+
+                "timeout_ms" => {
+                    input.parse::<Token![=]>()?;
+                    let ms = input.parse::<syn::LitInt>()?;
+                    attrs.timeout_ms = Some(ms.base10_parse()?);
+                },
+
+                other => {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("unknown attribute `{other}`"),
+                    ))
+                },
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// The code setting up and tearing down the test fixtures.
+#[derive(Default)]
+struct Fixture {
+    /// Runs before the test body, outside of `catch_unwind`.
+    setup: proc_macro2::TokenStream,
+
+    /// Binds the fixture to the argument of the test function.
+    binding: proc_macro2::TokenStream,
+
+    /// Runs after the test body, even if it panicked.
+    teardown: proc_macro2::TokenStream,
+}
+
+impl Fixture {
+    fn new(attrs: Attributes, sig: &syn::Signature) -> syn::Result<Self> {
+        let lines = match (attrs.buffer, sig.inputs.first()) {
+            (Some(lines), _) => lines,
+
+            (None, Some(arg)) => {
+                return Err(Error::new_spanned(
+                    arg,
+                    "test functions can only take arguments when using a \
+                     fixture",
+                ))
+            },
+
+            (None, None) => return Ok(Self::default()),
+        };
+
+        let binding = match sig.inputs.len() {
+            0 => quote! {},
+
+            1 => match sig.inputs.first().unwrap() {
+                syn::FnArg::Typed(syn::PatType { pat, ty, .. }) => {
+                    quote! { let #pat: #ty = __buffer.clone(); }
+                },
+                receiver => {
+                    return Err(Error::new_spanned(
+                        receiver,
+                        "test functions can't take `self`",
+                    ))
+                },
+            },
+
+            _ => {
+                return Err(Error::new_spanned(
+                    &sig.inputs,
+                    "the buffer fixture is passed as a single argument",
+                ))
+            },
+        };
+
+        let setup = quote! {
+            let __buffer = {
+                let mut buf = ::nvim_oxi::api::create_buf(false, true)?;
+                buf.set_lines(.., true, #lines)?;
+                ::nvim_oxi::api::set_current_buf(&buf)?;
+                buf
+            };
+        };
+
+        let teardown = quote! {
+            let _ = __buffer.delete(
+                &::nvim_oxi::api::opts::BufDeleteOpts::builder()
+                    .force(true)
+                    .build(),
+            );
+        };
+
+        Ok(Self { setup, binding, teardown })
+    }
+}
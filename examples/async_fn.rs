@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use nvim_oxi::{self as oxi, Dictionary, Function, Object};
+use tokio::runtime::Runtime;
+
+#[oxi::module]
+fn async_fn() -> oxi::Result<Dictionary> {
+    // The runtime's worker threads drive the spawned tasks while Neovim
+    // waits for them. It has to outlive the plugin, so we leak it.
+    let runtime: &'static Runtime =
+        Box::leak(Box::new(Runtime::new().unwrap()));
+
+    let sleep = Function::from_async_fn(move |ms: u64| {
+        let task =
+            runtime.spawn(tokio::time::sleep(Duration::from_millis(ms)));
+
+        async move {
+            task.await.unwrap();
+            Ok::<_, oxi::Error>(format!("slept for {ms}ms"))
+        }
+    });
+
+    Ok(Dictionary::from_iter([("sleep", Object::from(sleep))]))
+}
@@ -1,6 +1,5 @@
 use nvim_oxi::{self as oxi, api, lua, print, Dictionary, Function, Object};
 use oxi::conversion::{self, FromObject, ToObject};
-use oxi::serde::{Deserializer, Serializer};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -37,13 +36,13 @@ enum CarProblem {
 
 impl FromObject for Car {
     fn from_object(obj: Object) -> Result<Self, conversion::Error> {
-        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+        oxi::serde::from_object(obj).map_err(Into::into)
     }
 }
 
 impl ToObject for Car {
     fn to_object(self) -> Result<Object, conversion::Error> {
-        self.serialize(Serializer::new()).map_err(Into::into)
+        oxi::serde::to_object(&self).map_err(Into::into)
     }
 }
 
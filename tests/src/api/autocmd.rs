@@ -1,5 +1,10 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
 use all_asserts::*;
 use nvim_oxi as oxi;
+use nvim_oxi::api::types::{AutocmdEvent, AutocmdPattern};
 use nvim_oxi::api::{self, opts::*, Buffer};
 
 #[oxi::test]
@@ -65,6 +70,42 @@ fn create_autocmd() {
     assert!(id.is_ok(), "{id:?}");
 }
 
+#[oxi::test]
+fn create_autocmd_typed_events() {
+    let event = AutocmdEvent::User("Typed".into());
+    assert_eq!("User", event.name());
+    assert_eq!(Some("Typed"), event.pattern());
+
+    let opts = CreateAutocmdOpts::builder()
+        .command("let g:typed = get(g:, 'typed', 0) + 1")
+        .build();
+
+    let also = AutocmdEvent::User("AlsoTyped".into());
+    api::create_autocmd([&event, &also], &opts).unwrap();
+
+    // A `User` event with a different pattern doesn't match.
+    let other = AutocmdEvent::User("Other".into());
+    api::exec_autocmds([other], &Default::default()).unwrap();
+    assert!(api::get_var::<u8>("typed").is_err());
+
+    api::exec_autocmds([&event], &Default::default()).unwrap();
+    assert_eq!(Ok(1), api::get_var::<u8>("typed"));
+
+    // Every pattern is executed.
+    api::exec_autocmds([&event, &also], &Default::default()).unwrap();
+    assert_eq!(Ok(3), api::get_var::<u8>("typed"));
+
+    // The patterns would also apply to the other events.
+    let res = api::create_autocmd([&event, &AutocmdEvent::BufEnter], &opts);
+    assert!(res.is_err());
+
+    let opts = CreateAutocmdOpts::builder()
+        .command("let g:typed = 0")
+        .patterns(["*.rs"])
+        .build();
+    assert!(api::create_autocmd([&event], &opts).is_err());
+}
+
 #[oxi::test]
 fn create_autocmd_buffer_n_patterns() {
     let opts = CreateAutocmdOpts::builder()
@@ -146,3 +187,126 @@ fn set_exec_del_autocmd() {
 
     assert_eq!(Ok(()), api::del_autocmd(id));
 }
+
+#[oxi::test]
+fn create_autocmd_debounced() {
+    let calls = Rc::new(Cell::new(0));
+
+    let id = oxi::create_autocmd_debounced(
+        ["User"],
+        CreateAutocmdOpts::builder().patterns(["Debounced"]),
+        Duration::from_millis(20),
+        {
+            let calls = Rc::clone(&calls);
+            move |_args| {
+                calls.set(calls.get() + 1);
+                Ok(false)
+            }
+        },
+    )
+    .unwrap();
+
+    let opts = ExecAutocmdsOpts::builder().patterns("Debounced").build();
+
+    for _ in 0..5 {
+        api::exec_autocmds(["User"], &opts).unwrap();
+    }
+    assert_eq!(0, calls.get());
+
+    // Process events for long enough for the timer to fire.
+    api::call_function::<_, i64>("wait", (100, "v:false")).unwrap();
+    assert_eq!(1, calls.get());
+
+    // The callback isn't called if the autocommand is deleted in the
+    // meantime.
+    api::exec_autocmds(["User"], &opts).unwrap();
+    api::del_autocmd(id).unwrap();
+    api::call_function::<_, i64>("wait", (100, "v:false")).unwrap();
+    assert_eq!(1, calls.get());
+}
+
+#[oxi::test]
+fn create_autocmd_debounced_delete() {
+    let calls = Rc::new(Cell::new(0));
+
+    let id = oxi::create_autocmd_debounced(
+        ["User"],
+        CreateAutocmdOpts::builder().patterns(["DebouncedOnce"]),
+        Duration::from_millis(10),
+        {
+            let calls = Rc::clone(&calls);
+            move |_args| {
+                calls.set(calls.get() + 1);
+                Ok(true)
+            }
+        },
+    )
+    .unwrap();
+
+    let opts = ExecAutocmdsOpts::builder().patterns("DebouncedOnce").build();
+
+    api::exec_autocmds(["User"], &opts).unwrap();
+    api::call_function::<_, i64>("wait", (50, "v:false")).unwrap();
+    assert_eq!(1, calls.get());
+
+    // Returning `true` deleted the autocommand.
+    let opts = GetAutocmdsOpts::builder().events(["User"]).build();
+    let mut autocmds = api::get_autocmds(&opts).unwrap();
+    assert!(!autocmds.any(|infos| infos.id == Some(id)));
+
+    let opts = ExecAutocmdsOpts::builder().patterns("DebouncedOnce").build();
+
+    api::exec_autocmds(["User"], &opts).unwrap();
+    api::call_function::<_, i64>("wait", (50, "v:false")).unwrap();
+    assert_eq!(1, calls.get());
+}
+
+#[oxi::test]
+fn create_autocmd_with_timeout() {
+    let id = oxi::create_autocmd_with_timeout(
+        ["User"],
+        CreateAutocmdOpts::builder().patterns(["NeverFired"]),
+        Duration::from_millis(10),
+        |_args| panic!("the event is never triggered"),
+        || Ok(api::set_var("timed_out", true)?),
+    )
+    .unwrap();
+
+    // Process events until the timeout callback has been called.
+    let res = api::call_function::<_, i32>(
+        "wait",
+        (1000, "get(g:, 'timed_out', v:false)"),
+    );
+    assert_eq!(Ok(0), res);
+
+    let opts = GetAutocmdsOpts::builder().events(["User"]).build();
+    let mut autocmds = api::get_autocmds(&opts).unwrap();
+    assert!(autocmds.all(|infos| infos.id != Some(id)));
+}
+
+#[oxi::test]
+fn autocmd_pattern_matches() {
+    let pattern = AutocmdPattern::new("*.rs").unwrap();
+    assert_eq!("*.rs", pattern.as_str());
+
+    assert_eq!(Ok(true), pattern.matches("main.rs"));
+    assert_eq!(Ok(true), pattern.matches("src/lib.rs"));
+    assert_eq!(Ok(true), pattern.matches("/home/user/crate/build.rs"));
+    assert_eq!(Ok(false), pattern.matches("main.rsx"));
+    assert_eq!(Ok(false), pattern.matches("lib.rs.bak"));
+    assert_eq!(Ok(false), pattern.matches("Cargo.toml"));
+
+    // Matching doesn't depend on the user's 'ignorecase'.
+    api::set_option("ignorecase", true).unwrap();
+    assert_eq!(Ok(false), pattern.matches("MAIN.RS"));
+    api::set_option("ignorecase", false).unwrap();
+
+    let pattern = AutocmdPattern::new("*.{rs,toml}").unwrap();
+    assert_eq!(Ok(true), pattern.matches("Cargo.toml"));
+    assert_eq!(Ok(true), pattern.matches("src/main.rs"));
+    assert_eq!(Ok(false), pattern.matches("README.md"));
+
+    let pattern = AutocmdPattern::new("*/src/**/*.rs").unwrap();
+    assert_eq!(Ok(true), pattern.matches("/crate/src/api/buffer.rs"));
+    assert_eq!(Ok(false), pattern.matches("/crate/tests/buffer.rs"));
+}
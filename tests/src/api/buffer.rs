@@ -1,6 +1,9 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
 use all_asserts::*;
 use nvim_oxi as oxi;
-use nvim_oxi::api::{self, opts::*, types::*, Buffer};
+use nvim_oxi::api::{self, opts::*, types::*, Buffer, BufferObserver, Window};
 
 #[oxi::test]
 fn attach() {
@@ -21,6 +24,26 @@ fn attach() {
     assert!(bytes_written.is_ok(), "{bytes_written:?}");
 }
 
+#[oxi::test]
+fn byte_char_cols() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(.., true, ["\tä€x"]).unwrap();
+
+    assert_eq!(Ok(0), buf.byte_to_char_col(0, 0));
+    assert_eq!(Ok(1), buf.byte_to_char_col(0, 1));
+    assert_eq!(Ok(1), buf.byte_to_char_col(0, 2));
+    assert_eq!(Ok(2), buf.byte_to_char_col(0, 3));
+    assert_eq!(Ok(3), buf.byte_to_char_col(0, 6));
+    assert_eq!(Ok(4), buf.byte_to_char_col(0, 7));
+    assert!(buf.byte_to_char_col(0, 8).is_err());
+
+    assert_eq!(Ok(0), buf.char_to_byte_col(0, 0));
+    assert_eq!(Ok(3), buf.char_to_byte_col(0, 2));
+    assert_eq!(Ok(6), buf.char_to_byte_col(0, 3));
+    assert_eq!(Ok(7), buf.char_to_byte_col(0, 4));
+    assert!(buf.char_to_byte_col(0, 5).is_err());
+}
+
 #[oxi::test]
 fn buf_call() {
     let buf = Buffer::current();
@@ -53,6 +76,78 @@ fn buf_create_del_user_command() {
     assert_eq!(Ok(()), buf.del_user_command("Bar"));
 }
 
+#[oxi::test]
+fn edit_as_undo_block() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["a"]).unwrap();
+
+    let res = buf.edit_as_undo_block(|buf| {
+        buf.set_lines(.., true, ["b"])?;
+        buf.set_lines(.., true, ["c"])
+    });
+    assert_eq!(Ok(()), res);
+
+    buf.set_lines(.., true, ["d"]).unwrap();
+
+    let lines = |buf: &Buffer| {
+        buf.get_lines(.., true)
+            .unwrap()
+            .map(|line| line.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+    };
+
+    api::command("undo").unwrap();
+    assert_eq!(vec!["c"], lines(&buf));
+
+    api::command("undo").unwrap();
+    assert_eq!(vec!["a"], lines(&buf));
+}
+
+#[oxi::test]
+fn transaction() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar", "baz"]).unwrap();
+
+    let mut win = Window::current();
+    win.set_cursor(3, 1).unwrap();
+
+    let res = buf.transaction(&TxOpts::default(), |tx| {
+        tx.set_text(0..=0, 0, 3, ["FOO"])?;
+        api::command("normal! gg")?;
+        tx.set_text(1..=1, 0, 3, ["BAR"])
+    });
+    assert_eq!(Ok(()), res);
+    assert_eq!(Ok((3, 1)), win.get_cursor());
+
+    let lines = |buf: &Buffer| {
+        buf.get_lines(.., true)
+            .unwrap()
+            .map(|line| line.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(vec!["FOO", "BAR", "baz"], lines(&buf));
+
+    api::command("undo").unwrap();
+    assert_eq!(vec!["foo", "bar", "baz"], lines(&buf));
+
+    // Commands run by the transaction apply to its buffer, even if it's not
+    // the current one.
+    let mut other = api::create_buf(true, true).unwrap();
+    other.set_lines(.., true, ["foo", "bar"]).unwrap();
+
+    let res = other
+        .transaction(&TxOpts::default(), |_| api::command("normal! ggdd"));
+    assert_eq!(Ok(()), res);
+    assert_eq!(vec!["bar"], lines(&other));
+    assert_eq!(vec!["foo", "bar", "baz"], lines(&buf));
+
+    // Errors returned by the body are passed through as is.
+    let res = other.transaction(&TxOpts::default(), |_| {
+        Err(api::Error::Other("oops".into()))
+    });
+    assert_eq!(Err(api::Error::Other("oops".into())), res);
+}
+
 #[oxi::test]
 fn get_changedtick() {
     let buf = Buffer::current();
@@ -66,12 +161,141 @@ fn loaded_n_valid() {
     assert!(buf.is_valid());
 }
 
+#[oxi::test]
+fn load_file() {
+    let dir = std::env::temp_dir().join("nvim-oxi-load-file");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let text = dir.join("preview.rs");
+    std::fs::write(&text, "fn main() {\n    todo!()\n}\n").unwrap();
+
+    let binary = dir.join("preview.bin");
+    std::fs::write(&binary, b"foo\0bar").unwrap();
+
+    api::command("filetype on").unwrap();
+
+    let mut buf = api::create_buf(false, true).unwrap();
+
+    let lines = |buf: &Buffer| {
+        buf.get_lines(.., true)
+            .unwrap()
+            .map(|line| line.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+    };
+
+    let opts = LoadFileOpts::builder().filetype(true).build();
+    assert_eq!(Ok(()), buf.load_file(&text, &opts));
+
+    assert_eq!(vec!["fn main() {", "    todo!()", "}"], lines(&buf));
+    assert_eq!(Ok(String::from("rust")), buf.get_option("filetype"));
+    assert_eq!(Ok(false), buf.is_modified());
+
+    let opts = LoadFileOpts::builder().max_size(6).build();
+    assert_eq!(Ok(()), buf.load_file(&text, &opts));
+    assert_eq!(vec!["fn mai"], lines(&buf));
+
+    assert_eq!(Ok(()), buf.load_file(&binary, &Default::default()));
+    assert_eq!(vec!["[binary file]"], lines(&buf));
+
+    assert!(buf.load_file(dir.join("missing"), &Default::default()).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
 #[oxi::test]
 fn new_buf_delete() {
     let buf = api::create_buf(true, false).unwrap();
     assert_eq!(Ok(()), buf.delete(&Default::default()));
 }
 
+/// Records the events it's notified of as strings.
+struct Recorder(Rc<RefCell<Vec<String>>>);
+
+impl BufferObserver for Recorder {
+    fn on_lines(&mut self, change: LinesChange) {
+        self.0.borrow_mut().push(format!(
+            "lines {}..{} -> {}",
+            change.first_row, change.last_row, change.new_last_row
+        ));
+    }
+
+    fn on_detach(&mut self, _buffer: Buffer) {
+        self.0.borrow_mut().push("detach".into());
+    }
+}
+
+#[oxi::test]
+fn observe() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let handle = buf.observe(Recorder(Rc::clone(&events))).unwrap();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+    assert!(!handle.is_detached());
+
+    let opts = BufDeleteOpts::builder().force(true).build();
+    buf.clone().delete(&opts).unwrap();
+    assert!(handle.is_detached());
+
+    assert_eq!(vec!["lines 0..1 -> 2", "detach"], *events.borrow());
+}
+
+#[oxi::test]
+fn observe_drop_handle() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let handle = buf.observe(Recorder(Rc::clone(&events))).unwrap();
+    buf.set_lines(.., true, ["foo"]).unwrap();
+    drop(handle);
+    buf.set_lines(.., true, ["bar"]).unwrap();
+
+    assert_eq!(vec!["lines 0..1 -> 1"], *events.borrow());
+}
+
+#[oxi::test]
+fn new_buf_wipe() {
+    let mut buf = api::create_buf(true, false).unwrap();
+
+    let fired = Rc::new(Cell::new(0));
+
+    // This one detaches on the first change, so it's already gone by the
+    // time the buffer is wiped.
+    let opts = BufAttachOpts::builder().on_lines(|_args| Ok(true)).build();
+    buf.attach(false, &opts).unwrap();
+
+    let on_lines = Rc::clone(&fired);
+    let on_bytes = Rc::clone(&fired);
+    let opts = BufAttachOpts::builder()
+        .on_lines(move |_args| {
+            on_lines.set(on_lines.get() + 1);
+            Ok(false)
+        })
+        .on_bytes(move |_args| {
+            on_bytes.set(on_bytes.get() + 1);
+            Ok(false)
+        })
+        .build();
+    buf.attach(false, &opts).unwrap();
+
+    buf.set_lines(.., true, ["foo"]).unwrap();
+    let before_wipe = fired.get();
+    assert_gt!(before_wipe, 0);
+
+    // The buffer has unsaved changes, so wiping it has to be forced.
+    assert_eq!(Ok(true), buf.get_option::<bool>("modified"));
+
+    let opts = WipeOpts::builder().force(true).build();
+    assert_eq!(Ok(()), buf.clone().wipe(&opts));
+    assert_eq!(before_wipe, fired.get());
+
+    // Unlike an unloaded buffer, a wiped out one is gone from the buffer
+    // list entirely.
+    assert!(!buf.is_valid());
+    assert!(!api::list_bufs().any(|b| b == buf));
+    assert_eq!(Ok(0), api::call_function::<_, u8>("bufexists", (buf,)));
+}
+
 #[oxi::test]
 fn buf_set_get_del_keymap() {
     let mut buf = Buffer::current();
@@ -132,6 +356,37 @@ fn set_get_del_lines() {
     assert_eq!(Ok(1), buf.line_count());
 }
 
+#[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+#[oxi::test(buffer = ["a/b a/b", "a/b", "a/b", "a/b"])]
+fn substitute(buf: Buffer) {
+    let count = buf.substitute(..3, "a/b", "&c\\'", "g").unwrap();
+    assert_eq!(3, count);
+
+    assert_eq!(
+        vec!["&c\\' &c\\'", "&c\\'", "a/b", "a/b"],
+        buf.get_lines(.., true)
+            .unwrap()
+            .flat_map(String::try_from)
+            .collect::<Vec<String>>()
+    );
+
+    assert_eq!(Ok(0), buf.substitute(.., "xyz", "foo", ""));
+}
+
+#[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+#[oxi::test(buffer = ["foo", "bar", "foo", "foo"])]
+fn global(buf: Buffer) {
+    buf.global(1.., "^fo\\+$", "delete").unwrap();
+
+    assert_eq!(
+        vec!["foo", "bar"],
+        buf.get_lines(.., true)
+            .unwrap()
+            .flat_map(String::try_from)
+            .collect::<Vec<String>>()
+    );
+}
+
 #[oxi::test]
 fn buf_set_get_del_mark() {
     let mut buf = Buffer::current();
@@ -182,12 +437,66 @@ fn set_get_del_text() {
     assert_eq!(Ok(1), buf.line_count());
 }
 
+#[oxi::test]
+fn get_set_region() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(.., true, ["föo bär", "baz qux"]).unwrap();
+
+    let region = Region::new(Position::chars(0, 4), Position::bytes(1, 3));
+    assert_eq!(Ok(vec!["bär".into(), "baz".into()]), buf.get_region(region));
+
+    assert_eq!(Ok(()), buf.set_region(region, ["hello"]));
+    assert_eq!(
+        vec!["föo hello qux"],
+        buf.get_lines(.., true)
+            .unwrap()
+            .flat_map(String::try_from)
+            .collect::<Vec<String>>()
+    );
+
+    let inverted = Region::new(Position::bytes(0, 3), Position::chars(0, 1));
+    assert!(buf.get_region(inverted).is_err());
+    assert!(buf.set_region(inverted, ["foo"]).is_err());
+}
+
+#[oxi::test]
+fn get_number() {
+    let buf = api::create_buf(true, false).unwrap();
+    let bufnr = api::call_function::<_, u32>("bufnr", (buf.clone(),));
+    assert_eq!(bufnr, buf.get_number());
+
+    buf.clone().delete(&Default::default()).unwrap();
+    assert!(buf.get_number().is_err());
+}
+
 #[oxi::test]
 fn buf_set_get_del_var() {
     let mut buf = Buffer::current();
+    assert!(!buf.has_var("foo"));
     buf.set_var("foo", 42).unwrap();
+    assert!(buf.has_var("foo"));
     assert_eq!(Ok(42), buf.get_var("foo"));
     assert_eq!(Ok(()), buf.del_var("foo"));
+    assert!(!buf.has_var("foo"));
+}
+
+#[oxi::test]
+fn buf_get_var_errors() {
+    let mut buf = Buffer::current();
+
+    assert_eq!(
+        Err(api::Error::VarNotFound("foo".into())),
+        buf.get_var::<u32>("foo")
+    );
+
+    buf.set_var("foo", "bar").unwrap();
+    assert!(matches!(
+        buf.get_var::<u32>("foo"),
+        Err(api::Error::ObjectConversion(_))
+    ));
+
+    buf.clone().delete(&Default::default()).unwrap();
+    assert!(matches!(buf.get_var::<u32>("foo"), Err(api::Error::Nvim(_))));
 }
 
 #[oxi::test]
@@ -216,3 +525,64 @@ fn buf_set_get_option() {
     buf.set_option("modified", false).unwrap();
     assert!(!buf.get_option::<bool>("modified").unwrap());
 }
+
+#[oxi::test(buffer = ["foo", "bar"])]
+fn buffer_fixture(buf: Buffer) {
+    assert_eq!(Buffer::current(), buf);
+    assert_eq!(
+        vec!["foo", "bar"],
+        buf.get_lines(.., true)
+            .unwrap()
+            .flat_map(String::try_from)
+            .collect::<Vec<String>>()
+    );
+}
+
+#[oxi::test]
+fn stats() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+
+    let ns_id = api::create_namespace("stats");
+    let opts = SetExtmarkOpts::default();
+    buf.set_extmark(ns_id, 0, 0, &opts).unwrap();
+    buf.set_extmark(ns_id, 0, 1, &opts).unwrap();
+
+    // Deleting the first line saves the positions of both extmarks in the
+    // undo header.
+    buf.set_lines(0..1, true, std::iter::empty::<&str>()).unwrap();
+
+    let stats = buf.stats().unwrap();
+    assert_eq!(0, stats.dirty_bytes);
+    assert_ge!(stats.uhp_extmark_size.unwrap(), 2);
+}
+
+#[oxi::test]
+fn apply_diff() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar", "baz", "qux"]).unwrap();
+
+    let mut win = Window::current();
+    win.set_cursor(4, 1).unwrap();
+
+    let ns_id = api::create_namespace("apply_diff");
+    let opts = SetExtmarkOpts::default();
+    let id = buf.set_extmark(ns_id, 2, 1, &opts).unwrap();
+
+    let opts = DiffOpts::default();
+    buf.apply_diff(&["foo", "BAR", "new", "baz", "qux"], &opts).unwrap();
+
+    assert_eq!(
+        vec!["foo", "BAR", "new", "baz", "qux"],
+        buf.get_lines(.., true)
+            .unwrap()
+            .flat_map(String::try_from)
+            .collect::<Vec<String>>()
+    );
+
+    // The extmark and the cursor have been moved down by the inserted line.
+    let opts = GetExtmarkByIdOpts::default();
+    let (row, col, _) = buf.get_extmark_by_id(ns_id, id, &opts).unwrap();
+    assert_eq!((3, 1), (row, col));
+    assert_eq!(Ok((5, 1)), win.get_cursor());
+}
@@ -0,0 +1,69 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_oxi as oxi;
+use nvim_oxi::api::{
+    self,
+    opts::{OptionScope, OptionValueOpts},
+    types::{CompletionItem, CompletionRequest, Mode},
+    Buffer,
+};
+
+#[oxi::test]
+fn register_completion_func() {
+    let requests = Rc::new(RefCell::new(Vec::<CompletionRequest>::new()));
+
+    let expr = {
+        let requests = Rc::clone(&requests);
+        api::register_completion_func(move |request| {
+            requests.borrow_mut().push(request);
+            Ok(vec![
+                CompletionItem::builder().word("foobar").build(),
+                CompletionItem::builder().word("foobaz").build(),
+            ])
+        })
+        .unwrap()
+    };
+
+    assert!(expr.starts_with("v:lua."));
+    assert_eq!(
+        expr,
+        api::get_option_value::<String>("completefunc", &Default::default())
+            .unwrap()
+    );
+
+    api::set_current_line("let x = fo").unwrap();
+
+    let keys = api::replace_termcodes("A<C-x><C-u>", true, false, true);
+    api::feedkeys(&keys.to_string_lossy(), Mode::Visual, false);
+
+    assert_eq!("let x = foobar", api::get_current_line().unwrap());
+
+    let requests = requests.borrow();
+    assert_eq!(1, requests.len());
+    assert_eq!(Buffer::current(), requests[0].buffer);
+    assert_eq!(1, requests[0].row);
+    assert_eq!(8, requests[0].col);
+    assert_eq!("fo", requests[0].base);
+}
+
+#[oxi::test]
+fn register_completion_func_buffer_local() {
+    let expr = api::register_completion_func(|_| Ok(vec![])).unwrap();
+
+    let opts = OptionValueOpts::builder().scope(OptionScope::Global).build();
+    let global = api::get_option_value::<String>("completefunc", &opts);
+    assert_eq!(Ok(String::new()), global);
+
+    // New buffers don't inherit the function.
+    let buf = api::create_buf(true, false).unwrap();
+    api::set_current_buf(&buf).unwrap();
+    assert_eq!(
+        Ok(String::new()),
+        api::get_option_value::<String>("completefunc", &Default::default())
+    );
+
+    // All the buffers share the same expression.
+    let other = api::register_completion_func(|_| Ok(vec![])).unwrap();
+    assert_eq!(expr, other);
+}
@@ -167,3 +167,70 @@ fn set_get_del_extmark() {
     let res = buf.del_extmark(ns_id, extmark_id);
     assert_eq!(Ok(()), res);
 }
+
+#[oxi::test]
+fn extmark_id_gravity_spell() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo"]).unwrap();
+    let ns_id = api::create_namespace("Foo");
+
+    let opts = SetExtmarkOpts::builder()
+        .id(42)
+        .right_gravity(false)
+        .spell(true)
+        .build();
+
+    let extmark_id = buf.set_extmark(ns_id, 0, 1, &opts).unwrap();
+    assert_eq!(ExtmarkId::from(42), extmark_id);
+    assert_eq!(42, extmark_id.as_u32());
+
+    // With left gravity, text inserted at the extmark's position is placed
+    // after it.
+    buf.set_text(0..=0, 1, 1, ["xx"]).unwrap();
+
+    let opts = GetExtmarkByIdOpts::builder().details(true).build();
+    let (row, col, infos) =
+        buf.get_extmark_by_id(ns_id, ExtmarkId::from(42), &opts).unwrap();
+    assert_eq!((0, 1), (row, col));
+    assert!(!infos.unwrap().right_gravity);
+
+    let opts = SetExtmarkOpts::builder().right_gravity(true).build();
+    let extmark_id = buf.set_extmark(ns_id, 0, 1, &opts).unwrap();
+    buf.set_text(0..=0, 1, 1, ["yy"]).unwrap();
+    let (_, col, _) =
+        buf.get_extmark_by_id(ns_id, extmark_id, &Default::default()).unwrap();
+    assert_eq!(3, col);
+
+    // The returned id can be used to move the extmark.
+    let opts = SetExtmarkOpts::builder().id(extmark_id).build();
+    assert_eq!(Ok(extmark_id), buf.set_extmark(ns_id, 0, 0, &opts));
+    let (_, col, _) =
+        buf.get_extmark_by_id(ns_id, extmark_id, &Default::default()).unwrap();
+    assert_eq!(0, col);
+
+    assert!(buf.del_extmark(ns_id, ExtmarkId::from(4242)).is_err());
+}
+
+#[oxi::test]
+fn set_extmark_scoped() {
+    let mut buf = Buffer::current();
+    let ns_id = api::create_namespace("Foo");
+
+    let guard = buf.set_extmark_scoped(ns_id, 0, 0, &Default::default());
+    assert!(guard.is_ok(), "{guard:?}");
+
+    let guard = guard.unwrap();
+    let extmark_id = guard.id();
+    assert!(buf
+        .get_extmark_by_id(ns_id, extmark_id, &Default::default())
+        .is_ok());
+
+    drop(guard);
+
+    let got = buf.get_extmark_by_id(ns_id, extmark_id, &Default::default());
+    assert!(got.is_err(), "{got:?}");
+
+    let guard = buf.set_extmark_scoped(ns_id, 0, 0, &Default::default());
+    let extmark_id = guard.unwrap().forget();
+    assert_eq!(Ok(()), buf.del_extmark(ns_id, extmark_id));
+}
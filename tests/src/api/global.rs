@@ -1,6 +1,10 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
 use all_asserts::*;
-use nvim_oxi as oxi;
 use nvim_oxi::api::{self, opts::*, types::*, Buffer, Window};
+use nvim_oxi::conversion::FromObject;
+use nvim_oxi::{self as oxi, Array, Dictionary, Object};
 
 #[oxi::test]
 fn chan_send_fail() {
@@ -31,6 +35,23 @@ fn create_del_user_command() {
     assert_eq!(Ok(()), api::del_user_command("Bar"));
 }
 
+#[oxi::test]
+fn cursor_word() {
+    api::set_current_line("foo-bar.baz  qux").unwrap();
+    let mut win = Window::current();
+
+    win.set_cursor(1, 5).unwrap();
+    assert_eq!(Ok(Some("bar".into())), api::cursor_word());
+    assert_eq!(Ok(Some("foo-bar.baz".into())), api::cursor_WORD());
+
+    Buffer::current().set_option("iskeyword", "@,48-57,_,192-255,-").unwrap();
+    assert_eq!(Ok(Some("foo-bar".into())), api::cursor_word());
+
+    win.set_cursor(1, 12).unwrap();
+    assert_eq!(Ok(None), api::cursor_word());
+    assert_eq!(Ok(None), api::cursor_WORD());
+}
+
 #[oxi::test]
 fn eval_statusline() {
     let opts = EvalStatuslineOpts::builder().highlights(true).build();
@@ -38,12 +59,60 @@ fn eval_statusline() {
     assert_eq!(Ok("foo".into()), res.map(|infos| infos.str));
 }
 
+#[oxi::test]
+fn export_colorscheme() {
+    let opts =
+        SetHighlightOpts::builder().foreground("#ff0000").bold(true).build();
+    api::set_hl(0, "OxiFoo", &opts).unwrap();
+
+    let opts = SetHighlightOpts::builder().link("OxiFoo").build();
+    api::set_hl(0, "OxiBar", &opts).unwrap();
+
+    let opts = SetHighlightOpts::builder().link("OxiBar").build();
+    api::set_hl(0, "OxiBaz", &opts).unwrap();
+
+    let colorscheme = api::export_colorscheme(false).unwrap();
+    assert_eq!(Some(0xff0000), colorscheme["OxiFoo"].foreground);
+    assert_eq!(Some(true), colorscheme["OxiFoo"].bold);
+    assert_eq!(Some("OxiFoo"), colorscheme["OxiBar"].link.as_deref());
+    assert_eq!(None, colorscheme["OxiBar"].foreground);
+
+    let colorscheme = api::export_colorscheme(true).unwrap();
+    assert_eq!(colorscheme["OxiFoo"], colorscheme["OxiBar"]);
+    assert_eq!(None, colorscheme["OxiBar"].link);
+    assert_eq!(colorscheme["OxiFoo"], colorscheme["OxiBaz"]);
+}
+
+#[oxi::test]
+fn get_api_info() {
+    let infos = api::get_api_info().unwrap();
+    assert_le!(NeovimVersion::new(0, 7, 0), infos.version);
+    assert!(infos.functions.iter().any(|f| f.name == "nvim_get_mode"));
+
+    assert!(api::has_api((0, 7, 0)));
+    assert!(!api::has_api((42, 0, 0)));
+}
+
 #[oxi::test]
 fn get_chan_info() {
     let res = api::get_chan_info(0);
     assert!(res.is_err());
 }
 
+#[oxi::test]
+fn get_chan_info_open_term() {
+    let buf = api::create_buf(true, true).unwrap();
+    let id = api::open_term(&buf, &Default::default()).unwrap();
+
+    let infos = api::get_chan_info(id).unwrap();
+    assert_eq!(id, infos.id);
+    assert_eq!(ChannelStream::Internal, infos.stream);
+    assert_eq!(ChannelMode::Terminal, infos.mode);
+    assert_eq!(Some(buf), infos.buffer);
+
+    assert!(api::list_chans().any(|infos| infos.id == id));
+}
+
 #[oxi::test]
 fn get_colors() {
     let colors = api::get_color_map().collect::<Vec<_>>();
@@ -59,6 +128,42 @@ fn get_context() {
     assert!(res.is_ok());
 }
 
+#[oxi::test]
+fn get_hl() {
+    let opts = SetHighlightOpts::builder()
+        .foreground("#ff0000")
+        .background("#00ff00")
+        .ctermfg("1")
+        .bold(true)
+        .underdotted(true)
+        .build();
+    api::set_hl(0, "OxiGetHl", &opts).unwrap();
+
+    let attrs = api::get_hl(0, "OxiGetHl").unwrap();
+    assert_eq!(Some(0xff0000), attrs.fg);
+    assert_eq!(Some(0x00ff00), attrs.bg);
+    assert_eq!(None, attrs.sp);
+    assert_eq!(Some(1), attrs.ctermfg);
+    assert!(attrs.bold);
+    assert!(attrs.underdotted);
+    assert!(!attrs.italic);
+    assert_eq!(None, attrs.link);
+
+    // Setting the attributes on another group gives back the same ones.
+    api::set_hl(0, "OxiGetHlCopy", &(&attrs).into()).unwrap();
+    assert_eq!(Ok(attrs), api::get_hl(0, "OxiGetHlCopy"));
+
+    let opts = SetHighlightOpts::builder().link("OxiGetHl").build();
+    api::set_hl(0, "OxiGetHlLink", &opts).unwrap();
+
+    let attrs = api::get_hl(0, "OxiGetHlLink").unwrap();
+    assert_eq!(Some("OxiGetHl"), attrs.link.as_deref());
+    assert_eq!(None, attrs.fg);
+
+    assert!(api::get_hl(0, "OxiNotAGroup").is_err());
+    assert!(api::get_hl(1, "OxiGetHl").is_err());
+}
+
 #[oxi::test]
 fn get_highlights() {
     let (name, _) = api::get_color_map().next().unwrap();
@@ -75,8 +180,13 @@ fn get_mode() {
 
 #[oxi::test]
 fn get_options() {
-    let res = api::get_all_options_info();
-    assert_lt!(0, res.unwrap().collect::<Vec<_>>().len());
+    let infos = api::get_all_options_info().unwrap();
+    assert_lt!(0, infos.len());
+
+    assert!(infos["hidden"].scope.is_global());
+    assert!(infos["number"].scope.is_window());
+    assert_eq!("nu", infos["number"].shortname);
+    assert!(infos["filetype"].scope.is_buffer());
 }
 
 #[oxi::test]
@@ -129,6 +239,48 @@ fn list_wins() {
     );
 }
 
+#[oxi::test]
+fn match_fuzzy() {
+    let items = ["barfoo", "foo", "fxoxo", "bar"];
+
+    let matches =
+        api::match_fuzzy(&items, "foo", &Default::default()).unwrap();
+
+    let indices = matches.iter().map(|&(idx, _, _)| idx).collect::<Vec<_>>();
+    assert_eq!(vec![1, 0, 2], indices);
+    assert!(matches.windows(2).all(|w| w[0].2 >= w[1].2));
+    assert_eq!(vec![3, 4, 5], matches[1].1);
+
+    let opts = MatchFuzzyOpts::builder().limit(1).build();
+    assert_eq!(1, api::match_fuzzy(&items, "foo", &opts).unwrap().len());
+
+    let all = api::match_fuzzy(&items, "", &Default::default()).unwrap();
+    assert_eq!(4, all.len());
+
+    let opts = MatchFuzzyOpts::builder().limit(2).build();
+    let first = api::match_fuzzy(&items, "", &opts).unwrap();
+    assert_eq!(vec![(0, vec![], 0), (1, vec![], 0)], first);
+}
+
+#[oxi::test]
+fn set_current_buf_noautocmd() {
+    let opts = CreateAutocmdOpts::builder()
+        .command("let g:entered = get(g:, 'entered', 0) + 1")
+        .build();
+    api::create_autocmd(["BufEnter"], &opts).unwrap();
+
+    let buf = api::create_buf(true, false).unwrap();
+    let opts = SetBufOpts::builder().noautocmd(true).build();
+    assert_eq!(Ok(()), api::set_current_buf_with(&buf, &opts));
+    assert_eq!(buf, Buffer::current());
+    assert!(api::get_var::<u32>("entered").is_err());
+    assert_eq!(Ok(String::new()), api::get_option::<String>("eventignore"));
+
+    let buf = api::create_buf(true, false).unwrap();
+    assert_eq!(Ok(()), api::set_current_buf_with(&buf, &Default::default()));
+    assert_eq!(Ok(1), api::get_var::<u32>("entered"));
+}
+
 #[oxi::test]
 fn set_get_del_current_line() {
     let res = api::set_current_line("foo");
@@ -159,6 +311,76 @@ fn set_get_del_keymap() {
     assert_eq!(Ok(()), res);
 }
 
+#[oxi::test]
+fn set_keymap_desc() {
+    let desc = format!("maps {} to {}", "j", "gj");
+    let opts = SetKeymapOpts::builder().noremap(true).desc(desc).build();
+    api::set_keymap(Mode::Visual, "j", "gj", &opts).unwrap();
+
+    let keymap = api::get_keymap(Mode::Visual)
+        .find(|keymap| keymap.lhs == "j")
+        .unwrap();
+
+    assert_eq!(Some("maps j to gj"), keymap.desc.as_deref());
+    assert_eq!(Mode::Visual, keymap.mode);
+    assert!(keymap.noremap);
+
+    assert_eq!(Ok(()), api::del_keymap(Mode::Visual, "j"));
+}
+
+#[oxi::test]
+fn set_plug_keymap() {
+    assert_eq!("<Plug>(a<Space>b<Bar>c<lt>d)", api::plug_map("a b|c<d"));
+
+    let called = Rc::new(Cell::new(false));
+    let also_called = Rc::clone(&called);
+
+    let res = api::set_plug_keymap(
+        Mode::Normal,
+        "oxi-test|callback",
+        move |()| {
+            also_called.set(true);
+            Ok(())
+        },
+        &Default::default(),
+    );
+    assert_eq!(Ok(()), res);
+
+    let res = api::set_plug_keymap(
+        Mode::Normal,
+        "oxi-test string",
+        ":let g:plugged = 1<CR>",
+        &Default::default(),
+    );
+    assert_eq!(Ok(()), res);
+
+    let opts = SetKeymapOpts::builder().noremap(false).build();
+
+    let lhs = api::plug_map("oxi-test|callback");
+    api::set_keymap(Mode::Normal, "x", &lhs, &opts).unwrap();
+    api::command("normal x").unwrap();
+    assert!(called.get());
+
+    let lhs = api::plug_map("oxi-test string");
+    api::set_keymap(Mode::Normal, "y", &lhs, &opts).unwrap();
+    api::command("normal y").unwrap();
+    assert_eq!(Ok(1), api::get_var::<u8>("plugged"));
+}
+
+#[oxi::test]
+fn set_play_macro() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["x"]).unwrap();
+
+    assert_eq!(Ok(()), api::set_macro('q', "A foo<Esc>"));
+    assert_eq!(Ok(()), api::play_macro('q', 2));
+
+    assert_eq!(Ok("x foo foo".into()), api::get_current_line());
+
+    assert_eq!(Ok(()), api::play_macro('q', 0));
+    assert_eq!(Ok("x foo foo".into()), api::get_current_line());
+}
+
 #[oxi::test]
 fn set_get_del_mark() {
     let mut buf = Buffer::current();
@@ -182,6 +404,26 @@ fn set_get_del_var() {
     assert_eq!(Ok(()), api::del_var("foo"));
 }
 
+#[oxi::test]
+fn set_get_var_dict() {
+    let config = oxi::dict! {
+        "name" => "oxi",
+        "sizes" => oxi::array![80, 24],
+        "border" => oxi::dict! { "style" => "rounded" },
+    };
+
+    api::set_var("config", config).unwrap();
+
+    let config = api::get_var::<Dictionary>("config").unwrap();
+    assert_eq!(Some(&Object::from("oxi")), config.get(&"name"));
+
+    let sizes = Array::from_object(config["sizes"].clone()).unwrap();
+    assert_eq!(&[Object::from(80), Object::from(24)], &*sizes);
+
+    let border = Dictionary::from_object(config["border"].clone()).unwrap();
+    assert_eq!(Some(&Object::from("rounded")), border.get(&"style"));
+}
+
 #[oxi::test]
 fn set_get_option() {
     api::set_option("modified", true).unwrap();
@@ -206,3 +448,193 @@ fn user_command_with_count() {
 
     assert!(res.is_ok(), "{res:?}");
 }
+
+#[oxi::test]
+fn with_cwd() {
+    let previous = std::env::current_dir().unwrap();
+    let temp_dir = std::env::temp_dir().canonicalize().unwrap();
+
+    let res = api::with_cwd(&temp_dir, || {
+        api::call_function::<_, String>("getcwd", Array::new()).unwrap()
+    });
+    assert_eq!(Ok(temp_dir.display().to_string()), res);
+
+    let cwd = api::call_function::<_, String>("getcwd", Array::new());
+    assert_eq!(Ok(previous.display().to_string()), cwd);
+
+    let res = api::with_cwd("/this/does/not/exist", || unreachable!());
+    assert!(res.is_err());
+}
+
+#[oxi::test]
+fn modified_buffers() {
+    assert_eq!(0, api::modified_buffers().len());
+
+    let mut buf = api::create_buf(true, false).unwrap();
+    assert_eq!(Ok(false), buf.is_modified());
+
+    buf.set_lines(.., true, ["foo"]).unwrap();
+    assert_eq!(Ok(true), buf.is_modified());
+
+    let mut scratch = api::create_buf(true, true).unwrap();
+    scratch.set_lines(.., true, ["bar"]).unwrap();
+
+    let modified = api::modified_buffers().collect::<Vec<_>>();
+    assert_eq!(vec![buf], modified);
+}
+
+#[oxi::test]
+fn confirm() {
+    let choices = ["&Yes", "&No", "&Cancel"];
+    let ty = ConfirmType::Question;
+
+    // The keys are queued in the typeahead buffer and read by the prompt.
+    api::feedkeys("n", Mode::Normal, false);
+    assert_eq!(Ok(2), api::confirm("Save changes?", &choices, 1, ty));
+
+    api::feedkeys("\r", Mode::Normal, false);
+    assert_eq!(Ok(1), api::confirm("Save changes?", &choices, 1, ty));
+
+    api::feedkeys("\x1b", Mode::Normal, false);
+    assert_eq!(Ok(0), api::confirm("Save changes?", &choices, 1, ty));
+}
+
+#[oxi::test]
+fn get_effective_option() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    let win = Window::current();
+
+    api::set_option("shiftwidth", 8).unwrap();
+    buf.set_option("shiftwidth", 2).unwrap();
+
+    assert_eq!(
+        Ok(2),
+        api::get_effective_option::<u32>("shiftwidth", &buf, &win)
+    );
+    assert_eq!(Ok(8), api::get_option::<u32>("shiftwidth"));
+
+    // The local value of a global-local option is only used once it's set.
+    api::set_option("undolevels", 500).unwrap();
+    let undolevels = |buf: &Buffer| {
+        api::get_effective_option::<i64>("undolevels", buf, &win)
+    };
+    assert_eq!(Ok(500), undolevels(&buf));
+    buf.set_option("undolevels", 10).unwrap();
+    assert_eq!(Ok(10), undolevels(&buf));
+    assert_eq!(Ok(500), undolevels(&Buffer::current()));
+}
+
+#[oxi::test]
+fn feedkeys_restore_mode() {
+    let buf = Buffer::current();
+    let mode = || api::get_mode().map(|infos| infos.mode);
+    let lines = || {
+        buf.get_lines(.., true)
+            .unwrap()
+            .map(|line| line.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+    };
+
+    // `Mode::Visual` is the `x` flag, i.e. the keys are executed right away.
+    let res = api::feedkeys_restore_mode("ifoo", Mode::Visual, true);
+    assert_eq!(Ok(()), res);
+    assert_eq!(Ok(Mode::Normal), mode());
+    assert_eq!(vec!["foo"], lines());
+
+    let res = api::feedkeys_restore_mode("v", Mode::Visual, true);
+    assert_eq!(Ok(()), res);
+    assert_eq!(Ok(Mode::Normal), mode());
+    assert_eq!(vec!["foo"], lines());
+
+    // Errors raised by the keys aren't returned, but the mode is still
+    // restored.
+    api::set_vvar("errmsg", "").unwrap();
+    let res =
+        api::feedkeys_restore_mode(":nosuchcommand\r", Mode::Visual, true);
+    assert_eq!(Ok(()), res);
+    assert_eq!(Ok(Mode::Normal), mode());
+    assert_eq!(vec!["foo"], lines());
+
+    let errmsg = api::get_vvar::<String>("errmsg").unwrap();
+    assert!(errmsg.starts_with("E492:"), "{errmsg}");
+}
+
+#[oxi::test]
+fn feedkeys_restore_mode_insert() {
+    let buf = Buffer::current();
+    let entered = Rc::new(Cell::new(None));
+
+    // Mappings of the restoring keys must not be applied.
+    api::set_keymap(Mode::Normal, "gi", "<Nop>", &Default::default()).unwrap();
+
+    let opts = SetKeymapOpts::builder()
+        .callback({
+            let entered = Rc::clone(&entered);
+            move |_| {
+                entered.set(Some(api::get_mode()?.mode));
+                api::feedkeys_restore_mode("\x1b", Mode::Normal, false)?;
+                api::feedkeys("bar", Mode::Normal, false);
+                Ok(())
+            }
+        })
+        .build();
+
+    api::set_keymap(Mode::Insert, "<C-b>", "", &opts).unwrap();
+
+    // `\x02` is `<C-b>`.
+    api::feedkeys("ifoo\x02", Mode::Visual, false);
+
+    api::del_keymap(Mode::Insert, "<C-b>").unwrap();
+    api::del_keymap(Mode::Normal, "gi").unwrap();
+
+    // `bar` was typed back in Insert mode, right where `foo` ended.
+    assert_eq!(Some(Mode::Insert), entered.get());
+    let lines = buf.get_lines(.., true).unwrap().collect::<Vec<_>>();
+    assert_eq!(vec![oxi::String::from("foobar")], lines);
+}
+
+#[oxi::test]
+fn feedkeys_restore_mode_visual() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo bar"]).unwrap();
+    let entered = Rc::new(Cell::new(None));
+
+    // Mappings of the restoring keys must not be applied.
+    api::set_keymap(Mode::Normal, "gv", "<Nop>", &Default::default()).unwrap();
+
+    let opts = SetKeymapOpts::builder()
+        .callback({
+            let entered = Rc::clone(&entered);
+            move |_| {
+                entered.set(Some(api::get_mode()?.mode));
+                api::feedkeys_restore_mode("\x1b", Mode::Normal, false)?;
+                api::feedkeys("d", Mode::Normal, false);
+                Ok(())
+            }
+        })
+        .build();
+
+    api::set_keymap(Mode::Visual, "<C-b>", "", &opts).unwrap();
+
+    // `\x02` is `<C-b>`.
+    api::feedkeys("wve\x02", Mode::Visual, false);
+
+    api::del_keymap(Mode::Visual, "<C-b>").unwrap();
+    api::del_keymap(Mode::Normal, "gv").unwrap();
+
+    // The selection of `bar` was restored and then deleted.
+    // `nvim_get_mode` reports characterwise Visual mode as `v`.
+    assert_eq!(Some(Mode::VisualSelect), entered.get());
+    let lines = buf.get_lines(.., true).unwrap().collect::<Vec<_>>();
+    assert_eq!(vec![oxi::String::from("foo ")], lines);
+}
+
+#[oxi::test]
+fn out_write_fragments() {
+    api::out_write("foo");
+    api::out_write(b"bar".to_vec());
+    api::out_write("\n");
+
+    let messages = api::exec("messages", true).unwrap().unwrap_or_default();
+    assert!(messages.contains("foobar"), "{messages:?}");
+}
@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use nvim_oxi as oxi;
+use nvim_oxi::api::{self, Job};
+
+#[oxi::test]
+fn job_wait() {
+    let job = Job::start(["echo", "hi"]).unwrap();
+
+    let res = job.wait(Duration::from_secs(5)).unwrap();
+    assert_eq!(Some(0), res.code);
+    assert_eq!("hi\n", res.stdout);
+    assert_eq!("", res.stderr);
+}
+
+#[oxi::test]
+fn job_wait_timeout() {
+    let job = Job::start(["sleep", "10"]).unwrap();
+
+    let res = job.wait(Duration::from_millis(10)).unwrap();
+    assert!(res.timed_out());
+
+    let stopped = api::call_function::<_, u8>("jobstop", (job.id(),));
+    assert_eq!(Ok(1), stopped);
+}
@@ -1,8 +1,11 @@
 mod autocmd;
 mod buffer;
+mod completion;
 mod extmark;
 mod global;
+mod job;
 mod tabpage;
+mod task;
 mod vimscript;
 mod win_config;
 mod window;
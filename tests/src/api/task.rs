@@ -0,0 +1,73 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::{self, Task};
+
+/// Replaces `vim.notify()` with a function storing every notification in
+/// `g:notifications` as a `"{id}:{kind}:{msg}"` string.
+fn record_notifications() {
+    api::exec(
+        r#"
+        lua << trim EOF
+        vim.notify = function(msg, _, opts)
+            local n = vim.g.notifications or {}
+            table.insert(n, ('%s:%s:%s'):format(opts.id, opts.kind, msg))
+            vim.g.notifications = n
+        end
+        EOF
+        "#,
+        false,
+    )
+    .unwrap();
+}
+
+fn notifications() -> Vec<String> {
+    api::get_var("notifications").unwrap()
+}
+
+#[oxi::test]
+fn task_start_report_finish() {
+    record_notifications();
+
+    let task = Task::start("Indexing").unwrap();
+    let id = task.token();
+    task.report(50, "Halfway there").unwrap();
+    task.finish("Done").unwrap();
+
+    assert_eq!(
+        vec![
+            format!("{id}:begin:Indexing"),
+            format!("{id}:report:Halfway there"),
+            format!("{id}:end:Done"),
+        ],
+        notifications()
+    );
+}
+
+#[oxi::test]
+fn task_drop_cancels() {
+    record_notifications();
+
+    let task = Task::start("Indexing").unwrap();
+    let id = task.token();
+    drop(task);
+
+    assert_eq!(
+        vec![format!("{id}:begin:Indexing"), format!("{id}:end:Cancelled")],
+        notifications()
+    );
+}
+
+#[oxi::test]
+fn task_tokens_shared_between_plugins() {
+    record_notifications();
+
+    // Other plugins built with nvim-oxi use the same counter, stored in the
+    // Lua registry.
+    api::exec("lua debug.getregistry().nvim_oxi_task_last_token = 41", false)
+        .unwrap();
+
+    let task = Task::start("Indexing").unwrap();
+    assert_eq!(42, task.token());
+    task.finish("Done").unwrap();
+
+    assert_eq!(Ok(43), Task::start("Indexing").map(|task| task.token()));
+}
@@ -95,6 +95,23 @@ fn parse_cmd_basic() {
     assert_eq!(None, infos.range);
 }
 
+#[oxi::test]
+fn expand_fnamemodify() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_name("foo.txt").unwrap();
+    api::set_current_buf(&buf).unwrap();
+
+    let cwd = std::env::current_dir().unwrap();
+    let path = api::expand("%:p").unwrap();
+    assert_eq!(cwd.join("foo.txt").display().to_string(), path);
+
+    let parent = api::fnamemodify(&path, ":h").unwrap();
+    assert_eq!(cwd.display().to_string(), parent);
+
+    let home = std::env::var("HOME").unwrap();
+    assert_eq!(Ok(home), api::expand("~"));
+}
+
 #[oxi::test]
 fn parse_expression_basic() {
     let res = api::parse_expression("lua print('a')", "", true);
@@ -166,18 +183,14 @@ fn parse_expression_basic() {
         leaf1.ty
     );
 
-    // BUG: why is it not deserializing the second leaf? Using a `Vec` instead
-    // of a `BTreeSet` for the `children` field of `VimLExpressionAst` fixes
-    // it.
-    // assert_eq!(2, node.children.len()); // fails with `right = 1`
+    assert_eq!(2, node.children.len());
     assert_eq!((0, 9), node.start);
     assert_eq!(1, node.len);
     assert_eq!(VimLAstNode::Call, node.ty);
 
     let mut iter = node.children.into_iter();
     let leaf2 = iter.next().unwrap();
-    // TODO: uncomment when bug gets resolved.
-    // let leaf3 = iter.next().unwrap();
+    let leaf3 = iter.next().unwrap();
 
     assert!(
         leaf2.children.is_empty(),
@@ -194,15 +207,14 @@ fn parse_expression_basic() {
         leaf2.ty
     );
 
-    // TODO: uncomment when bug gets resolved.
-    // assert!(
-    //     leaf3.children.is_empty(),
-    //     "tree has {} elements",
-    //     leaf3.children.len()
-    // );
-    // assert_eq!((0, 10), leaf3.start);
-    // assert_eq!(3, leaf3.len);
-    // assert_eq!(VimLAstNode::SingleQuotedString("a".into()), leaf3.ty);
+    assert!(
+        leaf3.children.is_empty(),
+        "tree has {} elements",
+        leaf3.children.len()
+    );
+    assert_eq!((0, 10), leaf3.start);
+    assert_eq!(3, leaf3.len);
+    assert_eq!(VimLAstNode::SingleQuotedString("a".into()), leaf3.ty);
 
     let error = error.expect("error is set");
     assert_eq!("print('a')", error.arg);
@@ -224,3 +236,25 @@ fn parse_expression_basic() {
 
     assert_eq!(14, len);
 }
+
+#[oxi::test]
+fn parse_expression_arithmetic() {
+    let res = api::parse_expression("1 + 2", "", false);
+    assert!(res.is_ok(), "{res:?}");
+
+    let ParsedVimLExpression { ast, error, highlight, len, .. } = res.unwrap();
+
+    assert_eq!(None, error);
+    assert!(highlight.is_empty());
+    assert_eq!(5, len);
+
+    let ast = ast.expect("ast is set");
+    assert_eq!(VimLAstNode::BinaryPlus, ast.ty);
+
+    let children =
+        ast.children.into_iter().map(|node| node.ty).collect::<Vec<_>>();
+    assert_eq!(
+        vec![VimLAstNode::Integer(1), VimLAstNode::Integer(2)],
+        children
+    );
+}
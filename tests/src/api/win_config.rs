@@ -1,5 +1,5 @@
 use nvim_oxi as oxi;
-use nvim_oxi::api::{self, types::*, Buffer, Window};
+use nvim_oxi::api::{self, types::*, Buffer, FloatWinBuilder, Window};
 
 #[oxi::test]
 fn open_win_empty_config() {
@@ -109,3 +109,103 @@ fn set_config() {
 
     assert_eq!(Ok(()), win.set_config(&config));
 }
+
+#[oxi::test]
+fn update_config() {
+    let buf = api::create_buf(true, true).unwrap();
+
+    let config = WindowConfig::builder()
+        .relative(WindowRelativeTo::Editor)
+        .height(3)
+        .width(5)
+        .row(0)
+        .col(2)
+        .zindex(100u32)
+        .build();
+
+    let mut win = api::open_win(&buf, false, &config).unwrap();
+
+    for _ in 0..4 {
+        let res = win.update_config(|config| {
+            config.row = config.row.map(|row| row + 1.0);
+        });
+        assert_eq!(Ok(()), res);
+    }
+
+    let got = win.get_config().unwrap();
+    assert_eq!(Some(4.0), got.row);
+    assert_eq!(config.col, got.col);
+    assert_eq!(config.height, got.height);
+    assert_eq!(config.width, got.width);
+    assert_eq!(config.zindex, got.zindex);
+    assert_eq!(config.relative, got.relative);
+}
+
+#[oxi::test]
+fn float_win_builder() {
+    let buf = api::create_buf(true, true).unwrap();
+
+    let err = FloatWinBuilder::new().width(5).open(&buf).unwrap_err();
+    assert_eq!(
+        "Floating window is missing required fields: relative, height",
+        err.to_string()
+    );
+
+    let win = FloatWinBuilder::new()
+        .relative(WindowRelativeTo::Editor)
+        .width(5)
+        .height(3)
+        .row(1)
+        .col(2)
+        .border(WindowBorder::Rounded)
+        .focusable(false)
+        .zindex(100)
+        .open(&buf)
+        .unwrap();
+
+    let got = win.get_config().unwrap();
+    assert_eq!(Some(WindowRelativeTo::Editor), got.relative);
+    assert_eq!(Some(5), got.width);
+    assert_eq!(Some(3), got.height);
+    assert_eq!(Some(1.0), got.row);
+    assert_eq!(Some(2.0), got.col);
+    assert_eq!(Some(false), got.focusable);
+    assert_ne!(Window::current(), win);
+}
+
+#[oxi::test]
+fn open_win_vsplit() {
+    let win = Window::current();
+    let buf = api::create_buf(false, true).unwrap();
+
+    let config = WindowConfig::builder()
+        .split(SplitDirection::Right)
+        .win(win.clone())
+        .width(20)
+        .build();
+
+    let split = api::open_win(&buf, false, &config).unwrap();
+
+    assert_eq!(win, Window::current());
+    assert_eq!(Ok(buf), split.get_buf());
+    assert_eq!(Ok(20), split.get_width());
+    assert_eq!(2, api::list_wins().len());
+
+    let (row, col) = win.get_position().unwrap();
+    let (split_row, split_col) = split.get_position().unwrap();
+    assert_eq!(row, split_row);
+    assert_eq!(col + win.get_width().unwrap() as usize + 1, split_col);
+}
+
+#[oxi::test]
+fn open_win_split_relative() {
+    let buf = api::create_buf(false, true).unwrap();
+
+    let config = WindowConfig::builder()
+        .split(SplitDirection::Below)
+        .relative(WindowRelativeTo::Editor)
+        .build();
+
+    assert!(api::open_win(&buf, false, &config).is_err());
+    assert_eq!(1, api::list_wins().len());
+}
@@ -1,5 +1,6 @@
-use nvim_oxi as oxi;
-use nvim_oxi::api::{self, types::*, Buffer, TabPage, Window};
+use all_asserts::*;
+use nvim_oxi::api::{self, opts::*, types::*, Buffer, TabPage, Window};
+use nvim_oxi::{self as oxi, Dictionary, Object};
 
 #[oxi::test]
 fn win_call() {
@@ -32,6 +33,20 @@ fn win_get_number() {
     assert_eq!(Ok(1), Window::current().get_number());
 }
 
+#[oxi::test]
+fn win_get_number_matches_winnr() {
+    api::command("split").unwrap();
+    api::command("vsplit").unwrap();
+
+    let wins = api::list_wins().collect::<Vec<_>>();
+    assert_eq!(3, wins.len());
+
+    for win in wins {
+        let winnr = api::call_function("win_id2win", (win.clone(),));
+        assert_eq!(winnr, win.get_number());
+    }
+}
+
 #[oxi::test]
 fn get_position() {
     assert_eq!(Ok((0, 0)), Window::current().get_position());
@@ -102,6 +117,157 @@ fn set_get_cursor() {
     assert_eq!(Ok((1, 0)), win.get_cursor());
 }
 
+#[oxi::test]
+fn jumplist() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, (0..50).map(|i| i.to_string())).unwrap();
+
+    api::command("normal! 10G").unwrap();
+    api::command("normal! 20G").unwrap();
+
+    let win = Window::current();
+    let (entries, current) = win.jumplist().unwrap();
+    assert_eq!(
+        vec![1, 10],
+        entries.iter().map(|entry| entry.lnum).collect::<Vec<_>>()
+    );
+    assert!(entries.iter().all(|entry| entry.buffer == buf));
+    assert_eq!(entries.len(), current);
+
+    api::command("execute \"normal! \\<C-o>\"").unwrap();
+    let (_, current) = win.jumplist().unwrap();
+    assert_eq!(1, current);
+}
+
+#[oxi::test]
+fn match_add_delete() {
+    let mut win = Window::current();
+    let matches = |win: &Window| {
+        api::call_function::<_, Vec<Object>>("getmatches", (win.clone(),))
+            .unwrap()
+            .len()
+    };
+
+    let id =
+        win.match_add("Error", r"\s\+$", 10, &Default::default()).unwrap();
+    assert_eq!(1, matches(&win));
+
+    let opts = MatchAddOpts::builder().id(42).build();
+    let other = win.match_add("Todo", "TODO", 20, &opts).unwrap();
+    assert_eq!(42, other.as_u32());
+    assert_eq!(2, matches(&win));
+
+    let opts = MatchAddOpts::builder().conceal('x').build();
+    let concealed = win.match_add("Conceal", "foo", 10, &opts).unwrap();
+    let conceal =
+        api::call_function::<_, Vec<Dictionary>>("getmatches", (win.clone(),))
+            .unwrap()
+            .into_iter()
+            .find_map(|m| m.get(&"conceal").cloned());
+    assert_eq!(Some(Object::from("x")), conceal);
+    assert_eq!(Ok(()), win.match_delete(concealed));
+
+    assert_eq!(Ok(()), win.match_delete(id));
+    assert_eq!(1, matches(&win));
+
+    assert!(win.match_delete(id).is_err());
+}
+
+#[oxi::test]
+fn save_restore_view() {
+    let mut buf = Buffer::current();
+    let lines = (0..200).map(|i| format!("{i} {}", "x".repeat(200)));
+    buf.set_lines(.., true, lines).unwrap();
+
+    let mut win = Window::current();
+    win.set_option("wrap", false).unwrap();
+    win.set_cursor(150, 180).unwrap();
+
+    let view = win.save_view().unwrap();
+    assert_eq!(150, view.lnum);
+    assert_eq!(180, view.col);
+    assert_gt!(view.topline, 1);
+    assert_gt!(view.leftcol, 0);
+
+    win.set_cursor(1, 0).unwrap();
+    assert_eq!(1, win.save_view().unwrap().topline);
+
+    assert_eq!(Ok(()), win.restore_view(&view));
+    assert_eq!(Ok((150, 180)), win.get_cursor());
+    assert_eq!(Ok(view), win.save_view());
+
+    // Restoring the view of a window which isn't the current one.
+    api::command("split").unwrap();
+    win.set_cursor(1, 0).unwrap();
+    assert_ne!(win, Window::current());
+    assert_eq!(Ok(()), win.restore_view(&view));
+    assert_eq!(Ok((150, 180)), win.get_cursor());
+}
+
+#[oxi::test]
+fn screen_pos() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "\tbar"]).unwrap();
+
+    let win = Window::current();
+    let (row, col) = win.get_position().unwrap();
+
+    let first = win.screen_pos(1, 0).unwrap().unwrap();
+    assert_eq!(row as u32 + 1, first.row);
+    assert_eq!(col as u32 + 1, first.col);
+
+    let third = win.screen_pos(1, 2).unwrap().unwrap();
+    assert_eq!(first.row, third.row);
+    assert_eq!(first.col + 2, third.col);
+
+    // The tab is drawn as multiple cells.
+    let tab = win.screen_pos(2, 0).unwrap().unwrap();
+    assert_eq!(first.row + 1, tab.row);
+    assert!(tab.endcol > tab.col);
+
+    assert_eq!(Ok(None), win.screen_pos(42, 0));
+}
+
+#[oxi::test]
+fn tagstack() {
+    let win = Window::current();
+
+    let stack = win.tagstack().unwrap();
+    assert_eq!(1, stack.current);
+    assert!(stack.items.is_empty());
+
+    api::command(
+        "call settagstack(0, {'curidx': 2, 'items': [{'tagname': 'foo', \
+         'from': [bufnr(), 3, 5, 0]}]})",
+    )
+    .unwrap();
+
+    let stack = win.tagstack().unwrap();
+    assert_eq!(2, stack.current);
+    assert_eq!(1, stack.items.len());
+
+    let item = &stack.items[0];
+    assert_eq!("foo", item.tagname);
+    assert_eq!(Buffer::current(), item.from.buffer);
+    assert_eq!(3, item.from.lnum);
+    assert_eq!(4, item.from.col);
+}
+
+#[oxi::test]
+fn virtcol() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(.., true, ["\tä€x"]).unwrap();
+    buf.set_option("tabstop", 4).unwrap();
+
+    let mut win = Window::current();
+    win.set_buf(&buf).unwrap();
+
+    assert_eq!(Ok(4), win.virtcol(1, 0));
+    assert_eq!(Ok(5), win.virtcol(1, 1));
+    assert_eq!(Ok(6), win.virtcol(1, 3));
+    assert_eq!(Ok(7), win.virtcol(1, 6));
+}
+
 #[oxi::test]
 fn win_set_get_option() {
     let mut win = Window::current();
@@ -116,7 +282,51 @@ fn win_set_get_option() {
 #[oxi::test]
 fn win_set_get_del_var() {
     let mut win = Window::current();
+    assert!(!win.has_var("foo"));
     win.set_var("foo", 42).unwrap();
+    assert!(win.has_var("foo"));
     assert_eq!(Ok(42), win.get_var("foo"));
     assert_eq!(Ok(()), win.del_var("foo"));
+    assert!(!win.has_var("foo"));
+}
+
+#[oxi::test]
+fn set_cursor_virtual() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["ab", "\tä"]).unwrap();
+
+    let mut win = Window::current();
+
+    api::set_option("virtualedit", "all").unwrap();
+    assert_eq!(Ok(()), win.set_cursor_virtual(1, 5));
+    assert_eq!(Ok(6), api::call_function::<_, usize>("virtcol", (".",)));
+
+    api::set_option("virtualedit", "").unwrap();
+    assert_eq!(Ok(()), win.set_cursor_virtual(1, 5));
+    assert_eq!(Ok((1, 1)), win.get_cursor());
+
+    // The tab spans the first 8 display columns, so `ä` starts at byte 1.
+    assert_eq!(Ok(()), win.set_cursor_virtual(2, 8));
+    assert_eq!(Ok((2, 1)), win.get_cursor());
+}
+
+#[oxi::test]
+fn set_cursor_open_folds() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["a", "b", "c", "d"]).unwrap();
+
+    let mut win = Window::current();
+    win.set_option("foldmethod", "manual").unwrap();
+    api::command("2,3fold").unwrap();
+
+    let foldclosed = || api::call_function::<_, i32>("foldclosed", (3,));
+    assert_eq!(Ok(2), foldclosed());
+
+    assert_eq!(Ok(()), win.set_cursor_open_folds(3, 0));
+    assert_eq!(Ok((3, 0)), win.get_cursor());
+    assert_eq!(Ok(-1), foldclosed());
+
+    // Without folds it's just a regular cursor set.
+    assert_eq!(Ok(()), win.set_cursor_open_folds(4, 0));
+    assert_eq!(Ok((4, 0)), win.get_cursor());
 }
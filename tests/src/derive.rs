@@ -0,0 +1,126 @@
+use nvim_oxi::conversion::{Error, FromObject, ToObject};
+use nvim_oxi::{self as oxi, Dictionary, Object};
+
+#[derive(Debug, PartialEq, oxi::FromObject, oxi::ToObject)]
+struct Config {
+    #[oxi(rename = "max-width")]
+    max_width: u32,
+    border: Option<String>,
+    #[oxi(skip)]
+    cache: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, oxi::ToObject)]
+struct Update {
+    title: Option<String>,
+    #[oxi(keep_nil)]
+    border: Option<String>,
+}
+
+#[derive(Debug, PartialEq, oxi::FromObject, oxi::ToObject)]
+enum Event {
+    Quit,
+    #[oxi(rename = "resized")]
+    Resize {
+        width: u32,
+        height: u32,
+    },
+}
+
+#[oxi::test]
+fn derive_struct() {
+    let config = Config {
+        max_width: 79,
+        border: Some("rounded".into()),
+        cache: vec!["foo".into()],
+    };
+
+    let obj = config.to_object().unwrap();
+
+    assert_eq!(
+        Object::from(Dictionary::from_iter([
+            ("max-width", Object::from(79)),
+            ("border", Object::from("rounded")),
+        ])),
+        obj
+    );
+
+    assert_eq!(
+        Ok(Config {
+            max_width: 79,
+            border: Some("rounded".into()),
+            cache: Vec::new(),
+        }),
+        Config::from_object(obj)
+    );
+}
+
+#[oxi::test]
+fn derive_struct_missing_option() {
+    let config = Config { max_width: 10, border: None, cache: Vec::new() };
+
+    let obj = config.to_object().unwrap();
+    assert_eq!(Object::from(Dictionary::from_iter([("max-width", 10)])), obj);
+
+    assert_eq!(
+        Ok(Config { max_width: 10, border: None, cache: Vec::new() }),
+        Config::from_object(obj)
+    );
+}
+
+#[oxi::test]
+fn derive_struct_keep_nil() {
+    let update = Update { title: None, border: None };
+    let obj = update.to_object().unwrap();
+    let dict = Dictionary::from_object(obj).unwrap();
+
+    assert_eq!(1, dict.len());
+    assert_eq!(None, dict.get(&"title"));
+    assert_eq!(Some(&Object::nil()), dict.get(&"border"));
+
+    let update = Update { title: Some("foo".into()), border: None };
+    let obj = update.to_object().unwrap();
+    let dict = Dictionary::from_object(obj).unwrap();
+
+    assert_eq!(2, dict.len());
+    assert_eq!(Some(&Object::from("foo")), dict.get(&"title"));
+    assert_eq!(Some(&Object::nil()), dict.get(&"border"));
+}
+
+#[oxi::test]
+fn derive_enum() {
+    assert_eq!(Ok(Object::from("Quit")), Event::Quit.to_object());
+    assert_eq!(Ok(Event::Quit), Event::from_object(Object::from("Quit")));
+
+    let obj = Event::Resize { width: 80, height: 24 }.to_object().unwrap();
+
+    assert_eq!(
+        Object::from(Dictionary::from_iter([
+            ("type", Object::from("resized")),
+            ("width", Object::from(80)),
+            ("height", Object::from(24)),
+        ])),
+        obj
+    );
+
+    assert_eq!(
+        Ok(Event::Resize { width: 80, height: 24 }),
+        Event::from_object(obj)
+    );
+}
+
+#[oxi::test]
+fn derive_enum_errors() {
+    assert_eq!(
+        Err(Error::UnknownVariant("Resize".into())),
+        Event::from_object(Object::from("Resize"))
+    );
+
+    assert_eq!(
+        Err(Error::FromWrongType {
+            expected: "string or dictionary",
+            actual: "integer",
+        }),
+        Event::from_object(Object::from(42))
+    );
+}
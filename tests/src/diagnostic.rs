@@ -0,0 +1,129 @@
+use nvim_oxi::api::{self, Buffer, Window};
+use nvim_oxi::diagnostic::{self, opts::*, types::*};
+use nvim_oxi::{self as oxi, Object};
+
+#[oxi::test]
+fn set_get_reset() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+
+    let ns_id = api::create_namespace("oxi-diagnostic");
+
+    let diagnostics = [
+        Diagnostic::builder()
+            .lnum(0)
+            .col(0)
+            .end_col(3)
+            .severity(DiagnosticSeverity::Error)
+            .message("first")
+            .source("oxi")
+            .code("E001")
+            .build(),
+        Diagnostic::builder()
+            .lnum(1)
+            .col(1)
+            .severity(DiagnosticSeverity::Hint)
+            .message("second")
+            .build(),
+    ];
+
+    let opts = DiagnosticOpts::builder().virtual_text(false).build();
+    assert_eq!(Ok(()), diagnostic::set(ns_id, &buf, diagnostics, &opts));
+
+    let got = diagnostic::get(Some(&buf), &Default::default()).unwrap();
+    assert_eq!(2, got.len());
+
+    let first = &got[0];
+    assert_eq!((0, 0), (first.lnum, first.col));
+    assert_eq!(Some(3), first.end_col);
+    assert_eq!(Some(DiagnosticSeverity::Error), first.severity);
+    assert_eq!("first", first.message);
+    assert_eq!(Some(String::from("oxi")), first.source);
+    assert_eq!(Some(Object::from("E001")), first.code);
+    assert_eq!(ns_id, first.namespace);
+
+    let opts = GetOpts::builder().lnum(1).build();
+    let got = diagnostic::get(Some(&buf), &opts).unwrap();
+    assert_eq!(1, got.len());
+    assert_eq!("second", got[0].message);
+
+    let opts =
+        GetOpts::builder().min_severity(DiagnosticSeverity::Warn).build();
+    let got = diagnostic::get(None, &opts).unwrap();
+    assert_eq!(
+        vec!["first"],
+        got.iter().map(|d| &*d.message).collect::<Vec<_>>()
+    );
+
+    assert_eq!(Ok(()), diagnostic::reset(ns_id, &buf));
+    assert_eq!(
+        Ok(Vec::new()),
+        diagnostic::get(Some(&buf), &Default::default())
+    );
+}
+
+#[oxi::test]
+fn goto_next_prev() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo", "bar", "baz"]).unwrap();
+
+    let ns_id = api::create_namespace("oxi-diagnostic");
+
+    let diagnostics = [
+        Diagnostic::builder().lnum(0).col(1).message("first").build(),
+        Diagnostic::builder()
+            .lnum(2)
+            .col(2)
+            .severity(DiagnosticSeverity::Warn)
+            .message("second")
+            .build(),
+    ];
+
+    assert_eq!(
+        Ok(()),
+        diagnostic::set(ns_id, &buf, diagnostics, &Default::default())
+    );
+
+    let mut win = Window::current();
+    win.set_cursor(1, 0).unwrap();
+
+    let opts = GotoOpts::builder().float(false).wrap(false).build();
+
+    assert_eq!(Ok(()), diagnostic::goto_next(&opts));
+    assert_eq!(Ok((1, 1)), win.get_cursor());
+
+    assert_eq!(Ok(()), diagnostic::goto_next(&opts));
+    assert_eq!(Ok((3, 2)), win.get_cursor());
+
+    // There are no more diagnostics and `wrap` is off, so we don't move.
+    assert_eq!(Ok(()), diagnostic::goto_next(&opts));
+    assert_eq!(Ok((3, 2)), win.get_cursor());
+
+    assert_eq!(Ok(()), diagnostic::goto_prev(&opts));
+    assert_eq!(Ok((1, 1)), win.get_cursor());
+
+    // Only the second diagnostic is a warning.
+    let opts = GotoOpts::builder()
+        .float(false)
+        .severity(DiagnosticSeverity::Warn)
+        .build();
+
+    assert_eq!(Ok(()), diagnostic::goto_next(&opts));
+    assert_eq!(Ok((3, 2)), win.get_cursor());
+}
+
+#[oxi::test]
+fn open_float() {
+    let buf = Buffer::current();
+    let ns_id = api::create_namespace("oxi-diagnostic");
+
+    let opts = OpenFloatOpts::builder().scope(FloatScope::Buffer).build();
+    assert_eq!(Ok(None), diagnostic::open_float(&opts));
+
+    let diagnostics =
+        [Diagnostic::builder().lnum(0).col(0).message("foo").build()];
+    diagnostic::set(ns_id, &buf, diagnostics, &Default::default()).unwrap();
+
+    let win = diagnostic::open_float(&opts);
+    assert!(matches!(win, Ok(Some(_))), "{win:?}");
+}
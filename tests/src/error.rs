@@ -0,0 +1,24 @@
+use std::error::Error as _;
+
+use nvim_oxi::{self as oxi, api, ResultExt};
+
+#[oxi::test]
+fn error_context() {
+    let buf = api::get_current_buf();
+
+    let err = buf
+        .get_var::<String>("foo")
+        .context("reading foo")
+        .with_context(|| format!("processing {buf}"))
+        .unwrap_err();
+
+    assert_eq!("processing Buffer(1)", err.to_string());
+    assert_eq!(
+        "processing Buffer(1): reading foo: Variable not found: foo",
+        format!("{err:#}")
+    );
+
+    let source = err.source().unwrap();
+    assert_eq!("reading foo", source.to_string());
+    assert!(source.source().is_some());
+}
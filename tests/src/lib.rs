@@ -1 +1,9 @@
 mod api;
+mod derive;
+mod diagnostic;
+mod error;
+mod libuv;
+mod lsp;
+mod lua;
+mod profile;
+mod treesitter;
@@ -0,0 +1,561 @@
+use std::cell::{Cell, RefCell};
+use std::future::{self, Future};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{mpsc, Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+
+use nvim_oxi::{self as oxi, api, libuv, Function};
+use oxi::libuv::fs;
+
+#[oxi::test]
+fn fs_roundtrip() {
+    let dir = std::env::temp_dir()
+        .join(format!("nvim-oxi-fs-roundtrip-{}", std::process::id()));
+    let file = dir.join("foo.txt");
+
+    let done = Rc::new(Cell::new(false));
+    let contents = Rc::new(RefCell::new(Vec::new()));
+
+    fs::mkdir(&dir, 0o755, {
+        let done = Rc::clone(&done);
+        let contents = Rc::clone(&contents);
+        let file = file.clone();
+
+        move |res| {
+            res.unwrap();
+            let path = file.clone();
+            fs::write_file(&path, b"Hello from Rust".to_vec(), move |res| {
+                res.unwrap();
+                fs::read_file(&file, move |res| {
+                    *contents.borrow_mut() = res.unwrap();
+                    done.set(true);
+                })
+                .unwrap();
+            })
+            .unwrap();
+        }
+    })
+    .unwrap();
+
+    wait_for(&done);
+    assert_eq!(b"Hello from Rust", &contents.borrow()[..]);
+
+    let metadata = Rc::new(RefCell::new(None));
+    let entries = Rc::new(RefCell::new(Vec::new()));
+    let done = Rc::new(Cell::new(false));
+
+    fs::stat(&file, {
+        let metadata = Rc::clone(&metadata);
+        move |res| *metadata.borrow_mut() = Some(res.unwrap())
+    })
+    .unwrap();
+
+    fs::readdir(&dir, {
+        let entries = Rc::clone(&entries);
+        let done = Rc::clone(&done);
+        move |res| {
+            *entries.borrow_mut() = res.unwrap();
+            done.set(true);
+        }
+    })
+    .unwrap();
+
+    wait_for(&done);
+
+    let metadata = metadata.borrow_mut().take().unwrap();
+    assert_eq!(fs::FileType::File, metadata.file_type);
+    assert_eq!(15, metadata.len);
+
+    let entries = entries.borrow();
+    assert_eq!(1, entries.len());
+    assert_eq!("foo.txt", entries[0].name);
+    assert_eq!(fs::FileType::File, entries[0].file_type);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[oxi::test]
+fn fs_stat_before_epoch() {
+    let file = std::env::temp_dir()
+        .join(format!("nvim-oxi-fs-stat-{}", std::process::id()));
+
+    std::fs::write(&file, "").unwrap();
+
+    let status = std::process::Command::new("touch")
+        .args(["-m", "-d", "1960-01-01T00:00:00Z"])
+        .arg(&file)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let metadata = Rc::new(RefCell::new(None));
+    let done = Rc::new(Cell::new(false));
+
+    fs::stat(&file, {
+        let metadata = Rc::clone(&metadata);
+        let done = Rc::clone(&done);
+        move |res| {
+            *metadata.borrow_mut() = Some(res.unwrap());
+            done.set(true);
+        }
+    })
+    .unwrap();
+
+    wait_for(&done);
+    std::fs::remove_file(&file).unwrap();
+
+    let modified = metadata.borrow_mut().take().unwrap().modified;
+    let before = std::time::UNIX_EPOCH - Duration::from_secs(315_619_200);
+    assert_eq!(before, modified);
+}
+
+#[oxi::test]
+fn fs_read_missing_file() {
+    let path = PathBuf::from("/this/file/does/not/exist");
+
+    let done = Rc::new(Cell::new(false));
+    let failed = Rc::new(Cell::new(false));
+
+    fs::read_file(&path, {
+        let done = Rc::clone(&done);
+        let failed = Rc::clone(&failed);
+        move |res| {
+            failed.set(matches!(res, Err(libuv::Error::Uv(_))));
+            done.set(true);
+        }
+    })
+    .unwrap();
+
+    wait_for(&done);
+    assert!(failed.get());
+}
+
+#[oxi::test]
+fn timer_set_repeat() {
+    let fired = Rc::new(Cell::new(0));
+
+    let mut timer = libuv::TimerHandle::start(
+        Duration::from_millis(5),
+        Duration::from_millis(5),
+        {
+            let fired = Rc::clone(&fired);
+            move |_| {
+                fired.set(fired.get() + 1);
+                Ok::<_, oxi::Error>(())
+            }
+        },
+    )
+    .unwrap();
+
+    api::call_function::<_, i64>("wait", (50, "v:false")).unwrap();
+    assert!(fired.get() >= 2);
+
+    // The firing already scheduled still uses the old interval, the ones
+    // after it use the new one.
+    timer.set_repeat(Duration::from_secs(10));
+    assert_eq!(Duration::from_secs(10), timer.get_repeat());
+
+    let fired_before = fired.get();
+    api::call_function::<_, i64>("wait", (50, "v:false")).unwrap();
+    assert!(fired.get() <= fired_before + 1);
+
+    timer.again().unwrap();
+    assert!(timer.get_due_in() > Duration::from_secs(9));
+
+    timer.stop().unwrap();
+}
+
+#[oxi::test]
+fn timer_close() {
+    let fired = Rc::new(Cell::new(0));
+
+    let timer = libuv::TimerHandle::start(
+        Duration::from_millis(1),
+        Duration::from_millis(1),
+        {
+            let fired = Rc::clone(&fired);
+            move |_| {
+                fired.set(fired.get() + 1);
+                Ok::<_, oxi::Error>(())
+            }
+        },
+    )
+    .unwrap();
+
+    api::call_function::<_, i64>("wait", (20, "v:false")).unwrap();
+    assert!(fired.get() > 0);
+
+    timer.close();
+
+    let fired_before = fired.get();
+    api::call_function::<_, i64>("wait", (20, "v:false")).unwrap();
+    assert_eq!(fired_before, fired.get());
+
+    // The callback, and with it its clone of `fired`, has been dropped.
+    assert_eq!(1, Rc::strong_count(&fired));
+}
+
+#[oxi::test]
+fn shutdown_on_vim_leave_pre() {
+    let fired = Rc::new(Cell::new(0));
+
+    let timer = libuv::TimerHandle::start(
+        Duration::from_millis(1),
+        Duration::from_millis(1),
+        {
+            let fired = Rc::clone(&fired);
+            move |_| {
+                fired.set(fired.get() + 1);
+                Ok::<_, oxi::Error>(())
+            }
+        },
+    );
+    assert!(timer.is_ok());
+
+    let hooks_called = Rc::new(Cell::new(0));
+
+    libuv::on_shutdown({
+        let hooks_called = Rc::clone(&hooks_called);
+        move || hooks_called.set(hooks_called.get() + 1)
+    });
+
+    // Give the timer a chance to fire.
+    api::call_function::<_, i64>("wait", (20, "v:false")).unwrap();
+    assert!(fired.get() > 0);
+
+    api::exec_autocmds(["VimLeavePre"], &Default::default()).unwrap();
+    assert_eq!(1, hooks_called.get());
+
+    let fired_before = fired.get();
+    api::call_function::<_, i64>("wait", (20, "v:false")).unwrap();
+    assert_eq!(fired_before, fired.get());
+
+    // Shutting down again is a no-op.
+    libuv::shutdown();
+    assert_eq!(1, hooks_called.get());
+}
+
+#[oxi::test(timeout_ms = 5000)]
+fn tcp_echo() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = thread::spawn(move || {
+        let (mut socket, _) = listener.accept().unwrap();
+        let mut buf = [0; 5];
+        socket.read_exact(&mut buf).unwrap();
+        socket.write_all(&buf).unwrap();
+    });
+
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let written = Rc::new(Cell::new(0));
+    let closed = Rc::new(Cell::new(false));
+    let leftover = Rc::new(RefCell::new(None));
+
+    let tcp = libuv::TcpHandle::new().unwrap();
+
+    let res = tcp.connect(addr, {
+        let received = Rc::clone(&received);
+        let written = Rc::clone(&written);
+        let closed = Rc::clone(&closed);
+        let leftover = Rc::clone(&leftover);
+
+        move |stream| {
+            let mut stream = stream.unwrap();
+            *leftover.borrow_mut() = Some(stream.clone());
+
+            let on_write = move |res: Result<usize, libuv::Error>| {
+                written.set(res.unwrap())
+            };
+            stream.write(b"hello".to_vec(), on_write).unwrap();
+
+            let mut reader = stream.clone();
+            let mut stream = Some(stream);
+
+            reader
+                .read_start(move |bytes| {
+                    let bytes = bytes.unwrap();
+                    received.borrow_mut().extend(bytes);
+
+                    if received.borrow().len() == 5 {
+                        let closed = Rc::clone(&closed);
+                        let stream = stream.take().unwrap();
+                        stream.close(move || closed.set(true));
+                    }
+                })
+                .unwrap();
+        }
+    });
+    assert_eq!(Ok(()), res);
+
+    wait_for(&closed);
+
+    server.join().unwrap();
+
+    assert_eq!(5, written.get());
+    assert_eq!(b"hello", &received.borrow()[..]);
+
+    // The other clones of a closed stream can't be used anymore.
+    let mut leftover = leftover.borrow_mut().take().unwrap();
+    assert_eq!(
+        Err(libuv::Error::StreamClosed),
+        leftover.write(b"hello".to_vec(), |_| ())
+    );
+    assert_eq!(Err(libuv::Error::StreamClosed), leftover.read_start(|_| ()));
+    leftover.read_stop();
+}
+
+#[oxi::test]
+fn tcp_connect_refused() {
+    // Nothing listens on the port once the listener is dropped.
+    let addr = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+    let error = Rc::new(RefCell::new(None));
+    let done = Rc::new(Cell::new(false));
+
+    let tcp = libuv::TcpHandle::new().unwrap();
+
+    let res = tcp.connect(addr, {
+        let error = Rc::clone(&error);
+        let done = Rc::clone(&done);
+        move |stream| {
+            *error.borrow_mut() = stream.err();
+            done.set(true);
+        }
+    });
+    assert_eq!(Ok(()), res);
+
+    wait_for(&done);
+
+    let error = error.borrow_mut().take().unwrap();
+    assert!(matches!(error, libuv::Error::Uv(_)), "{error:?}");
+    assert_eq!("connection refused", error.to_string());
+}
+
+#[cfg(unix)]
+#[oxi::test(timeout_ms = 5000)]
+fn process_cat() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let exit_status = Rc::new(Cell::new(None));
+    let done = Rc::new(Cell::new(false));
+    let exited = Rc::new(Cell::new(false));
+
+    let opts =
+        libuv::SpawnOpts::default().stderr(libuv::Stdio::Null).on_exit({
+            let exit_status = Rc::clone(&exit_status);
+            let exited = Rc::clone(&exited);
+            move |status, signal| {
+                exit_status.set(Some((status, signal)));
+                exited.set(true);
+            }
+        });
+
+    let process = libuv::Process::spawn("cat", &[], opts).unwrap();
+    assert!(process.pid() > 0);
+    assert!(process.stderr().is_none());
+
+    process
+        .stdout()
+        .unwrap()
+        .read_start({
+            let output = Rc::clone(&output);
+            let done = Rc::clone(&done);
+            move |bytes| {
+                let bytes = bytes.unwrap();
+                if bytes.is_empty() {
+                    done.set(true);
+                }
+                output.borrow_mut().extend(bytes);
+            }
+        })
+        .unwrap();
+
+    let mut stdin = process.stdin().unwrap();
+    stdin.write(b"hello".to_vec(), |res| assert_eq!(Ok(5), res)).unwrap();
+    // Closing stdin makes `cat` exit.
+    stdin.close(|| ());
+
+    // The clone held by the process has been closed too.
+    let res = process.stdin().unwrap().write(b"world".to_vec(), |_| ());
+    assert_eq!(Err(libuv::Error::StreamClosed), res);
+
+    wait_for(&done);
+    assert_eq!(b"hello", &output.borrow()[..]);
+
+    wait_for(&exited);
+    assert_eq!(Some((0, 0)), exit_status.get());
+}
+
+#[cfg(unix)]
+#[oxi::test(timeout_ms = 5000)]
+fn process_env_cwd_kill() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let signal = Rc::new(Cell::new(0));
+    let done = Rc::new(Cell::new(false));
+
+    let opts =
+        libuv::SpawnOpts::default().env("OXI_FOO", "bar").cwd("/").on_exit({
+            let signal = Rc::clone(&signal);
+            let done = Rc::clone(&done);
+            move |_, sig| {
+                signal.set(sig);
+                done.set(true);
+            }
+        });
+
+    let mut process = libuv::Process::spawn(
+        "sh",
+        &["-c", "printf '%s %s' \"$OXI_FOO\" \"$PWD\"; sleep 10"],
+        opts,
+    )
+    .unwrap();
+
+    process
+        .stdout()
+        .unwrap()
+        .read_start({
+            let output = Rc::clone(&output);
+            move |bytes| output.borrow_mut().extend(bytes.unwrap())
+        })
+        .unwrap();
+
+    for _ in 0..50 {
+        if !output.borrow().is_empty() {
+            break;
+        }
+        api::call_function::<_, i64>("wait", (20, "v:false")).unwrap();
+    }
+    assert_eq!(b"bar /", &output.borrow()[..]);
+
+    // SIGTERM.
+    process.kill(15).unwrap();
+    wait_for(&done);
+    assert_eq!(15, signal.get());
+
+    // The process has already exited.
+    assert!(process.kill(15).is_err());
+}
+
+#[oxi::test]
+fn process_spawn_missing_cmd() {
+    let res = libuv::Process::spawn(
+        "this-command-does-not-exist",
+        &[],
+        Default::default(),
+    );
+    assert!(matches!(res, Err(libuv::Error::Uv(_))));
+}
+
+#[oxi::test(timeout_ms = 5000)]
+fn async_fn_awaits_timer() {
+    let sleep = Function::<u64, u64>::from_async_fn(|ms| {
+        let sleep = Sleep::new(Duration::from_millis(ms));
+        async move {
+            sleep.await;
+            Ok::<_, oxi::Error>(ms)
+        }
+    });
+
+    // The timer is fired by the event loop, which would deadlock if the
+    // future was blocking Neovim's thread.
+    assert_eq!(Ok(10), sleep.call(10));
+    assert_eq!(Ok(20), sleep.call(20));
+}
+
+#[oxi::test(timeout_ms = 5000)]
+fn async_fn_woken_from_thread() {
+    let double = Function::<i32, i32>::from_async_fn(|n| {
+        let (tx, rx) = mpsc::channel();
+        let waker = Arc::new(Mutex::new(None::<Waker>));
+
+        thread::spawn({
+            let waker = Arc::clone(&waker);
+            move || {
+                thread::sleep(Duration::from_millis(10));
+                tx.send(n * 2).unwrap();
+                if let Some(waker) = waker.lock().unwrap().take() {
+                    waker.wake();
+                }
+            }
+        });
+
+        let recv = future::poll_fn(move |cx| {
+            *waker.lock().unwrap() = Some(cx.waker().clone());
+            rx.try_recv().map_or(Poll::Pending, Poll::Ready)
+        });
+
+        async move { Ok::<_, oxi::Error>(recv.await) }
+    });
+
+    assert_eq!(Ok(42), double.call(21));
+}
+
+#[oxi::test]
+fn async_fn_error() {
+    let fail = Function::<(), ()>::from_async_fn(|()| async {
+        Err::<(), _>(api::Error::Other("oops".into()))
+    });
+
+    let err = fail.call(()).unwrap_err();
+    assert!(err.to_string().contains("oops"), "{err}");
+}
+
+/// A future which completes once a libuv timer has fired.
+struct Sleep {
+    state: Rc<RefCell<(bool, Option<Waker>)>>,
+    _timer: libuv::TimerHandle,
+}
+
+impl Sleep {
+    fn new(duration: Duration) -> Self {
+        let state = Rc::new(RefCell::new((false, None::<Waker>)));
+
+        let timer = libuv::TimerHandle::once(duration, {
+            let state = Rc::clone(&state);
+            move || {
+                let mut state = state.borrow_mut();
+                state.0 = true;
+                if let Some(waker) = state.1.take() {
+                    waker.wake();
+                }
+                Ok::<_, oxi::Error>(())
+            }
+        })
+        .unwrap();
+
+        Self { state, _timer: timer }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.borrow_mut();
+
+        if state.0 {
+            Poll::Ready(())
+        } else {
+            state.1 = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Runs the event loop until `flag` is set, for at most a second.
+fn wait_for(flag: &Cell<bool>) {
+    for _ in 0..50 {
+        if flag.get() {
+            return;
+        }
+        api::call_function::<_, i64>("wait", (20, "v:false")).unwrap();
+    }
+
+    panic!("timed out");
+}
@@ -0,0 +1,24 @@
+use nvim_oxi as oxi;
+use nvim_oxi::lsp::{self, types::*, Error};
+
+#[oxi::test]
+fn start_client_missing_cmd() {
+    let config = LspClientConfig::builder()
+        .name("oxi-test")
+        .cmd(["nvim-oxi-no-such-server"])
+        .root_dir(".")
+        .build();
+
+    assert_eq!(
+        Err(Error::StartFailed("oxi-test".into())),
+        lsp::start_client(config)
+    );
+}
+
+#[oxi::test]
+fn get_client_unknown_id() {
+    let id = ClientId::from(4242);
+    assert_eq!(4242, id.as_u32());
+
+    assert_eq!(Err(Error::ClientNotFound(4242)), lsp::get_client(id));
+}
@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+
+use nvim_oxi::{self as oxi, lua, Function, Object};
+
+#[oxi::test]
+fn push_pop_cow_str() {
+    let borrowed = Cow::Borrowed("foo");
+
+    let popped = unsafe {
+        lua::with_state(|lstate| {
+            lua::Pushable::push(borrowed, lstate).unwrap();
+            <String as lua::Poppable>::pop(lstate)
+        })
+    };
+
+    assert_eq!(Ok(String::from("foo")), popped);
+}
+
+#[oxi::test]
+fn push_pop_boxed_str() {
+    let boxed: Box<str> = "bar".into();
+
+    let popped = unsafe {
+        lua::with_state(|lstate| {
+            lua::Pushable::push(boxed, lstate).unwrap();
+            <Box<str> as lua::Poppable>::pop(lstate)
+        })
+    };
+
+    assert_eq!(Ok(Box::from("bar")), popped);
+}
+
+#[oxi::test]
+fn cow_str_to_from_object() {
+    use oxi::conversion::FromObject;
+
+    let obj = Object::from(Cow::Borrowed("baz"));
+    let moo = Cow::<str>::from_object(obj).unwrap();
+    assert!(matches!(moo, Cow::Owned(ref s) if s == "baz"));
+}
+
+#[oxi::test]
+fn function_bind() {
+    let sub = Function::<(i32, i32), i32>::from_fn(|(a, b)| {
+        Ok::<_, oxi::Error>(a - b)
+    });
+
+    let sub_from_ten = sub.bind::<_, (i32,)>((10,));
+
+    assert_eq!(Ok(7), sub_from_ten.call((3,)));
+    assert_eq!(Ok(-2), sub_from_ten.call((12,)));
+}
+
+#[oxi::test]
+fn function_then() {
+    let double = Function::<i32, i32>::from_fn(|n| Ok::<_, oxi::Error>(n * 2));
+
+    let to_string = Function::<i32, String>::from_fn(|n| {
+        Ok::<_, oxi::Error>(n.to_string())
+    });
+
+    let double_to_string = double.then(&to_string);
+
+    assert_eq!(Ok(String::from("42")), double_to_string.call(21));
+}
+
+#[oxi::test]
+fn function_ptr_eq() {
+    let body = |n: i32| Ok::<_, oxi::Error>(n);
+
+    let fun = Function::<i32, i32>::from_fn(body);
+    assert!(fun.ptr_eq(&fun.clone()));
+    assert!(!fun.ptr_eq(&Function::<i32, i32>::from_fn(body)));
+
+    // Popping the same Lua value twice gives two different refs to the same
+    // function.
+    let (first, second) = unsafe {
+        lua::with_state(|lstate| {
+            lua::Pushable::push(fun.clone(), lstate).unwrap();
+            lua::ffi::lua_pushvalue(lstate, -1);
+            let first = <Function<i32, i32> as lua::Poppable>::pop(lstate);
+            let second = <Function<i32, i32> as lua::Poppable>::pop(lstate);
+            (first.unwrap(), second.unwrap())
+        })
+    };
+
+    assert_ne!(first.lua_ref(), second.lua_ref());
+    assert!(first.ptr_eq(&second));
+    assert!(fun.ptr_eq(&first));
+}
+
+#[oxi::test]
+fn function_panic() {
+    let fun = Function::<(), ()>::from_fn(|()| -> Result<(), oxi::Error> {
+        panic!("oh no")
+    });
+
+    // Don't print the panic message, the test would be considered failed.
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let res = fun.call(());
+    std::panic::set_hook(hook);
+
+    match res {
+        Err(lua::Error::RuntimeError(msg)) => {
+            assert!(msg.contains("oh no"), "{msg}")
+        },
+        other => panic!("{other:?}"),
+    }
+}
+
+#[oxi::test]
+fn stack_guard() {
+    unsafe {
+        lua::with_state(|lstate| {
+            let top = lua::ffi::lua_gettop(lstate);
+
+            {
+                let guard = lua::StackGuard::new(lstate);
+                assert_eq!(top, guard.top());
+                lua::Pushable::push(42, lstate).unwrap();
+                lua::Pushable::push("foo", lstate).unwrap();
+                assert_eq!(top + 2, lua::ffi::lua_gettop(lstate));
+            }
+
+            assert_eq!(top, lua::ffi::lua_gettop(lstate));
+
+            let guard = lua::StackGuard::new(lstate);
+            lua::Pushable::push(true, lstate).unwrap();
+            guard.leak();
+
+            assert_eq!(top + 1, lua::ffi::lua_gettop(lstate));
+            lua::ffi::lua_pop(lstate, 1);
+        })
+    }
+}
+
+#[oxi::test]
+fn error_message() {
+    unsafe {
+        lua::with_state(|lstate| {
+            let _guard = lua::StackGuard::new(lstate);
+
+            lua::Pushable::push("oh no", lstate).unwrap();
+            assert_eq!("oh no", lua::utils::error_message(lstate, -1));
+
+            lua::ffi::lua_pushinteger(lstate, 42);
+            assert_eq!("42", lua::utils::error_message(lstate, -1));
+
+            lua::ffi::lua_createtable(lstate, 0, 0);
+            assert_eq!(
+                "(error object is a table value)",
+                lua::utils::error_message(lstate, -1)
+            );
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use std::thread;
+use std::time::Duration;
+
+use nvim_oxi::{self as oxi, profile};
+
+#[oxi::test]
+fn profile_scopes() {
+    profile::reset();
+
+    // Scopes aren't recorded while profiling is disabled.
+    drop(profile::scope("disabled"));
+    assert_eq!(None, profile::stats("disabled"));
+
+    profile::enable();
+
+    for _ in 0..2 {
+        let _scope = profile::scope("fast");
+    }
+
+    {
+        let _scope = profile::scope("slow");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    profile::disable();
+
+    let fast = profile::stats("fast").unwrap();
+    assert_eq!(2, fast.count);
+    assert!(fast.max <= fast.total);
+
+    let slow = profile::stats("slow").unwrap();
+    assert_eq!(1, slow.count);
+    assert!(slow.total >= Duration::from_millis(10));
+    assert_eq!(slow.total, slow.max);
+
+    let names = profile::all_stats()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["slow", "fast"], names);
+
+    profile::reset();
+    assert!(profile::all_stats().is_empty());
+}
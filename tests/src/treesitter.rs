@@ -0,0 +1,44 @@
+use nvim_oxi as oxi;
+use nvim_oxi::api::Buffer;
+use nvim_oxi::treesitter;
+
+#[oxi::test]
+fn parse_buffer() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["int x = 1;", "int y;"]).unwrap();
+
+    let parser = treesitter::get_parser(buf.clone(), "c").unwrap();
+    let tree = parser.parse().unwrap();
+
+    let root = tree.root_node();
+    assert_eq!("translation_unit", root.kind());
+    assert_eq!((0, 0), root.start_position());
+    assert_eq!(2, root.named_child_count());
+
+    let decl = root.child(0).unwrap().unwrap();
+    assert_eq!("declaration", decl.kind());
+    assert_eq!((0, 0), decl.start_position());
+    assert_eq!((0, 10), decl.end_position());
+    assert_eq!(Ok("int x = 1;".into()), decl.text(&buf));
+
+    assert!(root.child(2).unwrap().is_none());
+}
+
+#[oxi::test]
+fn root_node_text() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["int x = 1;", "int y;"]).unwrap();
+
+    let parser = treesitter::get_parser(buf.clone(), "c").unwrap();
+    let root = parser.parse().unwrap().root_node();
+
+    // The root node ends at the start of the line past the last one.
+    assert_eq!((2, 0), root.end_position());
+    assert_eq!(Ok("int x = 1;\nint y;".into()), root.text(&buf));
+}
+
+#[oxi::test]
+fn get_parser_unknown_lang() {
+    let buf = Buffer::current();
+    assert!(treesitter::get_parser(buf, "not-a-language").is_err());
+}